@@ -14,7 +14,7 @@ TOOLCHAINS=(
     stable
     beta
     nightly
-    "1.60.0"
+    "1.64.0"
 )
 
 cargo +stable fmt --all -- --check
@@ -31,7 +31,7 @@ for TOOLCHAIN in "${TOOLCHAINS[@]}"; do
         cargo +$TOOLCHAIN build --verbose --all
     )
     (
-        if [[ $TOOLCHAIN != "1.60.0" ]]; then
+        if [[ $TOOLCHAIN != "1.64.0" ]]; then
             cd ./tests/no-std
             cargo +$TOOLCHAIN clippy --all -- -D warnings
             cargo +$TOOLCHAIN build --verbose --all
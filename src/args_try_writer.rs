@@ -0,0 +1,178 @@
+use core::fmt::{Arguments, Debug};
+
+use crate::{Flush, IntoFlushFn, IntoTryWriteFn, NeverError, ReturnConvention, WriteFmt};
+
+/// A writer that calls `write_fmt` with the formatted arguments as is,
+/// without intermediate chunking, buffering or allocations.
+///
+/// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
+///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
+/// Writer propagates error to the caller if the write or flush function returns `Result::Err`.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArgsTryWriter<F1, F2 = ()>(F1, F2);
+
+/// A helper trait used by [`ArgsTryWriter`]
+/// to convert wrapped function result to [`Result`]`<T, E>`.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoArgsWriteResult {
+    /// The resulting type after convertion.
+    type Output;
+
+    /// Performs the conversion.
+    fn into_args_write_result(self) -> Self::Output;
+}
+
+/// A helper trait used by [`ArgsTryWriter`] flush method
+/// to convert wrapped function result to [`Result`]`<(), E>`.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoArgsFlushResult {
+    /// The resulting type after convertion.
+    type Output;
+
+    /// Performs the conversion.
+    fn into_args_flush_result(self) -> Self::Output;
+}
+
+impl<F1> ArgsTryWriter<F1, ()>
+where
+    F1: WriteFmt,
+{
+    /// Creates a new `ArgsTryWriter` from an object that implements [`WriteFmt`].
+    pub fn new(write: F1) -> Self {
+        Self(write, ())
+    }
+
+    /// Creates a new `ArgsTryWriter` with a [`WriteFmt`] wrapper
+    /// deduced with [`IntoTryWriteFn`] by the closure signature and constructed from it.
+    pub fn from_closure<F, Ts>(closure: F) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+    {
+        Self(closure.into_try_write_fn(), ())
+    }
+}
+
+impl<F1, F2> ArgsTryWriter<F1, F2>
+where
+    F1: WriteFmt,
+    F2: Flush,
+{
+    /// Creates a new `ArgsTryWriter` from an object that implements [`WriteFmt`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(write, flush)
+    }
+
+    /// Creates a new `ArgsTryWriter` with [`WriteFmt`] and [`Flush`] wrappers
+    /// deduced with [`IntoTryWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(
+            write_closure.into_try_write_fn(),
+            flush_closure.into_flush_fn(),
+        )
+    }
+}
+
+impl<F1, F2> ArgsTryWriter<F1, F2>
+where
+    Self: WriteFmt,
+{
+    /// Writes a formatted string into this writer, returning any error encountered.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteFmt>::Output {
+        WriteFmt::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2> ArgsTryWriter<F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function, returning any error encountered.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<F1, F2, Output> WriteFmt for ArgsTryWriter<F1, F2>
+where
+    F1: WriteFmt,
+    F1::Output: IntoArgsWriteResult<Output = Output>,
+{
+    type Output = Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Output {
+        self.0.write_fmt(args).into_args_write_result()
+    }
+}
+
+impl<F1, F2, Output> Flush for ArgsTryWriter<F1, F2>
+where
+    F2: Flush,
+    F2::Output: IntoArgsFlushResult<Output = Output>,
+{
+    type Output = Output;
+
+    fn flush(&mut self) -> Output {
+        self.1.flush().into_args_flush_result()
+    }
+}
+
+impl IntoArgsWriteResult for () {
+    type Output = Result<(), NeverError>;
+    fn into_args_write_result(self) -> Self::Output {
+        Ok(())
+    }
+}
+
+impl<T, E: Debug> IntoArgsWriteResult for Result<T, E> {
+    type Output = Result<T, E>;
+    fn into_args_write_result(self) -> Self::Output {
+        self
+    }
+}
+
+impl IntoArgsFlushResult for () {
+    type Output = Result<(), NeverError>;
+    fn into_args_flush_result(self) -> Self::Output {
+        Ok(())
+    }
+}
+
+impl<E: Debug> IntoArgsFlushResult for Result<(), E> {
+    type Output = Result<(), E>;
+    fn into_args_flush_result(self) -> Self::Output {
+        self
+    }
+}
+
+impl<R: ReturnConvention> IntoArgsWriteResult for R {
+    type Output = Result<(), R::Error>;
+    fn into_args_write_result(self) -> Self::Output {
+        self.into_return_result().map(|_| ())
+    }
+}
+
+impl<R: ReturnConvention> IntoArgsFlushResult for R {
+    type Output = Result<(), R::Error>;
+    fn into_args_flush_result(self) -> Self::Output {
+        self.into_return_result().map(|_| ())
+    }
+}
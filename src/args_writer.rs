@@ -0,0 +1,194 @@
+use core::fmt::{Arguments, Debug};
+
+use crate::{Flush, IntoFlushFn, IntoWriteFn, NeverError, ReturnConvention, WriteFmt};
+
+/// A writer that calls `write_fmt` with the formatted arguments as is,
+/// without intermediate chunking, buffering or allocations.
+///
+/// It is useful for sinks that do their own formatting,
+/// like deferred-formatting loggers or pre-locked terminals.
+///
+/// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
+///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
+/// # Panics
+///
+/// Writer panics if the write or flush function returns `Result::Err`.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::fmt::Arguments;
+/// use custom_print::ArgsWriter;
+///
+/// let mut written = 0;
+/// let mut writer = ArgsWriter::from_closure(|args: Arguments<'_>| {
+///     written += 1;
+///     assert_eq!(args.to_string(), "first second");
+/// });
+/// let second = "second";
+/// writer.write_fmt(format_args!("first {}", second)).unwrap();
+/// drop(writer);
+/// assert_eq!(written, 1);
+/// ```
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArgsWriter<F1, F2 = ()>(F1, F2);
+
+/// A helper trait used by [`ArgsWriter`]
+/// to convert wrapped function result to [`Result`]`<T, NeverError>` with error unwrapping.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait ExpectArgsWriteResult {
+    /// The resulting type after convertion.
+    type Output;
+
+    /// Performs the conversion with error unwrapping.
+    fn expect_args_write_result(self) -> Self::Output;
+}
+
+/// A helper trait used by [`ArgsWriter`] flush method
+/// to convert wrapped function result to [`Result`]`<(), NeverError>` with error unwrapping.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait ExpectArgsFlushResult {
+    /// Performs the conversion with error unwrapping.
+    fn expect_args_flush_result(self) -> Result<(), NeverError>;
+}
+
+impl<F1> ArgsWriter<F1, ()>
+where
+    F1: WriteFmt,
+{
+    /// Creates a new `ArgsWriter` from an object that implements [`WriteFmt`].
+    pub fn new(write: F1) -> Self {
+        Self(write, ())
+    }
+
+    /// Creates a new `ArgsWriter` with a [`WriteFmt`] wrapper
+    /// deduced with [`IntoWriteFn`] by the closure signature and constructed from it.
+    pub fn from_closure<F, Ts>(closure: F) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+    {
+        Self(closure.into_write_fn(), ())
+    }
+}
+
+impl<F1, F2> ArgsWriter<F1, F2>
+where
+    F1: WriteFmt,
+    F2: Flush,
+{
+    /// Creates a new `ArgsWriter` from an object that implements [`WriteFmt`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(write, flush)
+    }
+
+    /// Creates a new `ArgsWriter` with [`WriteFmt`] and [`Flush`] wrappers
+    /// deduced with [`IntoWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(write_closure.into_write_fn(), flush_closure.into_flush_fn())
+    }
+}
+
+impl<F1, F2> ArgsWriter<F1, F2>
+where
+    Self: WriteFmt,
+{
+    /// Writes a formatted string into this writer.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteFmt>::Output {
+        WriteFmt::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2> ArgsWriter<F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<F1, F2, Output> WriteFmt for ArgsWriter<F1, F2>
+where
+    F1: WriteFmt,
+    F1::Output: ExpectArgsWriteResult<Output = Output>,
+{
+    type Output = Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Output {
+        self.0.write_fmt(args).expect_args_write_result()
+    }
+}
+
+impl<F1, F2> Flush for ArgsWriter<F1, F2>
+where
+    F2: Flush,
+    F2::Output: ExpectArgsFlushResult,
+{
+    type Output = Result<(), NeverError>;
+
+    fn flush(&mut self) -> Self::Output {
+        self.1.flush().expect_args_flush_result()
+    }
+}
+
+impl ExpectArgsWriteResult for () {
+    type Output = Result<(), NeverError>;
+    fn expect_args_write_result(self) -> Self::Output {
+        Ok(())
+    }
+}
+
+impl<T, E: Debug> ExpectArgsWriteResult for Result<T, E> {
+    type Output = Result<T, NeverError>;
+    fn expect_args_write_result(self) -> Self::Output {
+        Ok(self.expect("failed writing"))
+    }
+}
+
+impl ExpectArgsFlushResult for () {
+    fn expect_args_flush_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<E: Debug> ExpectArgsFlushResult for Result<(), E> {
+    fn expect_args_flush_result(self) -> Result<(), NeverError> {
+        self.expect("failed flushing");
+        Ok(())
+    }
+}
+
+impl<R: ReturnConvention> ExpectArgsWriteResult for R {
+    type Output = Result<(), NeverError>;
+    fn expect_args_write_result(self) -> Self::Output {
+        let _ = self.into_return_result().expect("failed writing");
+        Ok(())
+    }
+}
+
+impl<R: ReturnConvention> ExpectArgsFlushResult for R {
+    fn expect_args_flush_result(self) -> Result<(), NeverError> {
+        let _ = self.into_return_result().expect("failed flushing");
+        Ok(())
+    }
+}
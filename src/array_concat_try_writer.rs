@@ -0,0 +1,186 @@
+use core::fmt::Arguments;
+
+use crate::array_concat_writer::{assert_array_len, write_fmt_to_array};
+use crate::{
+    ArrayConcatError, ArrayOverflow, Flush, IntoArrayConcatFlushResult, IntoArrayConcatWriteResult,
+    IntoFlushFn, IntoTryWriteFn, WriteBytes, WriteFmt, WriteStr,
+};
+
+/// A writer that calls `write_str` once with a combined string
+/// formatted into a stack buffer of `N` bytes, and do not require allocations.
+///
+/// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
+///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
+/// If the formatted message exceeds `N` bytes, the writer applies the specified
+/// [`ArrayOverflow`] policy, which truncates the message with the `"..."` marker by default.
+///
+/// Writer propagates error to the caller if the write or flush function returns `Result::Err`.
+/// Write errors are wrapped into [`ArrayConcatError::Write`],
+/// [`ArrayConcatError::Format`] is returned
+/// if a formatting trait implementation returns an error,
+/// and [`ArrayConcatError::Overflow`] is returned
+/// if the message exceeds the buffer with the [`ArrayOverflow::Error`] policy.
+///
+/// # Panics
+///
+/// Writer panics if `N` has no space for at least one 4-byte character.
+///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::{ArrayConcatError, ArrayConcatTryWriter, ArrayOverflow};
+///
+/// let mut writer = ArrayConcatTryWriter::<8, _>::from_closure(|_: &str| Ok::<(), ()>(()))
+///     .with_overflow(ArrayOverflow::Error);
+///
+/// assert_eq!(writer.write_fmt(format_args!("{}", "first")), Ok(()));
+/// assert_eq!(
+///     writer.write_fmt(format_args!("{}{}", "first", "second")),
+///     Err(ArrayConcatError::Overflow)
+/// );
+/// ```
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArrayConcatTryWriter<const N: usize, F1, F2 = ()>(F1, F2, ArrayOverflow);
+
+impl<const N: usize, F1> ArrayConcatTryWriter<N, F1, ()>
+where
+    F1: WriteStr,
+{
+    /// Creates a new `ArrayConcatTryWriter` from an object that implements [`WriteStr`].
+    pub fn new(write: F1) -> Self {
+        assert_array_len::<N>();
+        Self(write, (), ArrayOverflow::default())
+    }
+
+    /// Creates a new `ArrayConcatTryWriter` with a [`WriteStr`] wrapper
+    /// deduced with [`IntoTryWriteFn`] by the closure signature and constructed from it.
+    pub fn from_closure<F, Ts>(closure: F) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+    {
+        assert_array_len::<N>();
+        Self(closure.into_try_write_fn(), (), ArrayOverflow::default())
+    }
+}
+
+impl<const N: usize, F1, F2> ArrayConcatTryWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `ArrayConcatTryWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        assert_array_len::<N>();
+        Self(write, flush, ArrayOverflow::default())
+    }
+
+    /// Creates a new `ArrayConcatTryWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoTryWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        assert_array_len::<N>();
+        Self(
+            write_closure.into_try_write_fn(),
+            flush_closure.into_flush_fn(),
+            ArrayOverflow::default(),
+        )
+    }
+}
+
+impl<const N: usize, F1, F2> ArrayConcatTryWriter<N, F1, F2> {
+    /// Returns the writer with the specified buffer overflow policy.
+    pub fn with_overflow(self, overflow: ArrayOverflow) -> Self {
+        Self(self.0, self.1, overflow)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> ArrayConcatTryWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+{
+    /// Writes a formatted string into this writer, returning any error encountered.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> Result<T, ArrayConcatError<E>> {
+        write_fmt_to_array::<N, _, _, _>(&mut self.0, self.2, args)
+    }
+}
+
+impl<const N: usize, F1, F2> ArrayConcatTryWriter<N, F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function, returning any error encountered.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> WriteFmt for ArrayConcatTryWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+{
+    type Output = Result<T, ArrayConcatError<E>>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        ArrayConcatTryWriter::write_fmt(self, args)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> WriteStr for ArrayConcatTryWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+{
+    type Output = Result<T, ArrayConcatError<E>>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        self.0
+            .write_str(buf)
+            .into_array_concat_write_result()
+            .map_err(ArrayConcatError::Write)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> WriteBytes for ArrayConcatTryWriter<N, F1, F2>
+where
+    F1: WriteBytes,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+{
+    type Output = Result<T, ArrayConcatError<E>>;
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+        self.0
+            .write_bytes(buf)
+            .into_array_concat_write_result()
+            .map_err(ArrayConcatError::Write)
+    }
+}
+
+impl<const N: usize, F1, F2, E> Flush for ArrayConcatTryWriter<N, F1, F2>
+where
+    F2: Flush,
+    F2::Output: IntoArrayConcatFlushResult<Error = E>,
+{
+    type Output = Result<(), E>;
+
+    fn flush(&mut self) -> Self::Output {
+        self.1.flush().into_array_concat_flush_result()
+    }
+}
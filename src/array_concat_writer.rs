@@ -0,0 +1,394 @@
+use core::fmt::{self, Arguments, Debug};
+
+use crate::utf8::floor_char_boundary;
+use crate::{
+    ArrayConcatError, ArrayOverflow, Flush, IntoFlushFn, IntoWriteFn, NeverError, ReturnConvention,
+    WriteBytes, WriteFmt, WriteStr,
+};
+
+/// A writer that calls `write_str` once with a combined string
+/// formatted into a stack buffer of `N` bytes, and do not require allocations.
+///
+/// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
+///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
+/// If the formatted message exceeds `N` bytes, the writer applies the specified
+/// [`ArrayOverflow`] policy, which truncates the message with the `"..."` marker by default.
+///
+/// # Panics
+///
+/// Writer panics if `N` has no space for at least one 4-byte character.
+///
+/// Writer panics if the write or flush function returns `Result::Err`,
+/// if a formatting trait implementation returns an error
+/// or if the message exceeds the buffer with the [`ArrayOverflow::Error`] policy.
+///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::{ArrayConcatWriter, ArrayOverflow};
+///
+/// let mut chunks = Vec::new();
+/// let mut writer = ArrayConcatWriter::<8, _>::from_closure(|value: &str| {
+///     chunks.push(value.to_owned());
+/// });
+/// writer.write_fmt(format_args!("{}{}", "first", "second")).unwrap();
+///
+/// let mut writer = writer.with_overflow(ArrayOverflow::Split);
+/// writer.write_fmt(format_args!("{}{}", "first", "second")).unwrap();
+///
+/// assert_eq!(chunks, ["first...", "firstsec", "ond"]);
+/// ```
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArrayConcatWriter<const N: usize, F1, F2 = ()>(F1, F2, ArrayOverflow);
+
+/// A helper trait used by [`ArrayConcatWriter`] and [`ArrayConcatTryWriter`]
+/// to convert wrapped function result to [`Result`]`<T, E>`.
+///
+/// [`ArrayConcatTryWriter`]: struct.ArrayConcatTryWriter.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoArrayConcatWriteResult {
+    /// The resulting value type after convertion.
+    type Value;
+
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_array_concat_write_result(self) -> Result<Self::Value, Self::Error>;
+}
+
+/// A helper trait used by [`ArrayConcatWriter`] and [`ArrayConcatTryWriter`] flush methods
+/// to convert wrapped function result to [`Result`]`<(), E>`.
+///
+/// [`ArrayConcatTryWriter`]: struct.ArrayConcatTryWriter.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoArrayConcatFlushResult {
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_array_concat_flush_result(self) -> Result<(), Self::Error>;
+}
+
+impl<const N: usize, F1> ArrayConcatWriter<N, F1, ()>
+where
+    F1: WriteStr,
+{
+    /// Creates a new `ArrayConcatWriter` from an object that implements [`WriteStr`].
+    pub fn new(write: F1) -> Self {
+        assert_array_len::<N>();
+        Self(write, (), ArrayOverflow::default())
+    }
+
+    /// Creates a new `ArrayConcatWriter` with a [`WriteStr`] wrapper
+    /// deduced with [`IntoWriteFn`] by the closure signature and constructed from it.
+    pub fn from_closure<F, Ts>(closure: F) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+    {
+        assert_array_len::<N>();
+        Self(closure.into_write_fn(), (), ArrayOverflow::default())
+    }
+}
+
+impl<const N: usize, F1, F2> ArrayConcatWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `ArrayConcatWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        assert_array_len::<N>();
+        Self(write, flush, ArrayOverflow::default())
+    }
+
+    /// Creates a new `ArrayConcatWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        assert_array_len::<N>();
+        Self(
+            write_closure.into_write_fn(),
+            flush_closure.into_flush_fn(),
+            ArrayOverflow::default(),
+        )
+    }
+}
+
+impl<const N: usize, F1, F2> ArrayConcatWriter<N, F1, F2> {
+    /// Returns the writer with the specified buffer overflow policy.
+    pub fn with_overflow(self, overflow: ArrayOverflow) -> Self {
+        Self(self.0, self.1, overflow)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> ArrayConcatWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+    E: Debug,
+{
+    /// Writes a formatted string into this writer.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> Result<T, NeverError> {
+        Ok(write_fmt_to_array::<N, _, _, _>(&mut self.0, self.2, args).expect("failed writing"))
+    }
+}
+
+impl<const N: usize, F1, F2> ArrayConcatWriter<N, F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> WriteFmt for ArrayConcatWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+    E: Debug,
+{
+    type Output = Result<T, NeverError>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        ArrayConcatWriter::write_fmt(self, args)
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> WriteStr for ArrayConcatWriter<N, F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+    E: Debug,
+{
+    type Output = Result<T, NeverError>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        Ok(self
+            .0
+            .write_str(buf)
+            .into_array_concat_write_result()
+            .expect("failed writing"))
+    }
+}
+
+impl<const N: usize, F1, F2, T, E> WriteBytes for ArrayConcatWriter<N, F1, F2>
+where
+    F1: WriteBytes,
+    F1::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+    E: Debug,
+{
+    type Output = Result<T, NeverError>;
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+        Ok(self
+            .0
+            .write_bytes(buf)
+            .into_array_concat_write_result()
+            .expect("failed writing"))
+    }
+}
+
+impl<const N: usize, F1, F2, E> Flush for ArrayConcatWriter<N, F1, F2>
+where
+    F2: Flush,
+    F2::Output: IntoArrayConcatFlushResult<Error = E>,
+    E: Debug,
+{
+    type Output = Result<(), NeverError>;
+
+    fn flush(&mut self) -> Self::Output {
+        self.1
+            .flush()
+            .into_array_concat_flush_result()
+            .expect("failed flushing");
+        Ok(())
+    }
+}
+
+impl IntoArrayConcatWriteResult for () {
+    type Value = ();
+    type Error = NeverError;
+    fn into_array_concat_write_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<T, E> IntoArrayConcatWriteResult for Result<T, E> {
+    type Value = T;
+    type Error = E;
+    fn into_array_concat_write_result(self) -> Self {
+        self
+    }
+}
+
+impl IntoArrayConcatFlushResult for () {
+    type Error = NeverError;
+    fn into_array_concat_flush_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<E> IntoArrayConcatFlushResult for Result<(), E> {
+    type Error = E;
+    fn into_array_concat_flush_result(self) -> Self {
+        self
+    }
+}
+
+/// Formats the arguments into a stack buffer of `N` bytes
+/// and writes it with the specified writer applying the overflow policy.
+pub(crate) fn write_fmt_to_array<const N: usize, W, T, E>(
+    write: &mut W,
+    overflow: ArrayOverflow,
+    args: Arguments<'_>,
+) -> Result<T, ArrayConcatError<E>>
+where
+    W: WriteStr,
+    W::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+{
+    if let Some(buf) = args.as_str() {
+        if buf.len() <= N {
+            return write
+                .write_str(buf)
+                .into_array_concat_write_result()
+                .map_err(ArrayConcatError::Write);
+        }
+    }
+
+    let mut buffer = ArrayBuffer::<N, W, E> {
+        write,
+        overflow,
+        buf: [0; N],
+        len: 0,
+        truncated: false,
+        error: None,
+    };
+    let result = fmt::write(&mut buffer, args);
+    if let Some(err) = buffer.error {
+        return Err(err);
+    }
+    if result.is_err() && !buffer.truncated {
+        return Err(ArrayConcatError::Format);
+    }
+    buffer
+        .write
+        .write_str(buffer_str(&buffer.buf[..buffer.len]))
+        .into_array_concat_write_result()
+        .map_err(ArrayConcatError::Write)
+}
+
+pub(crate) fn assert_array_len<const N: usize>() {
+    assert!(
+        N >= 4,
+        "array buffer should have space for at least one 4-byte character"
+    );
+}
+
+struct ArrayBuffer<'a, const N: usize, W, E> {
+    write: &'a mut W,
+    overflow: ArrayOverflow,
+    buf: [u8; N],
+    len: usize,
+    truncated: bool,
+    error: Option<ArrayConcatError<E>>,
+}
+
+impl<const N: usize, W, E> ArrayBuffer<'_, N, W, E> {
+    fn push(&mut self, value: &str) {
+        self.buf[self.len..self.len + value.len()].copy_from_slice(value.as_bytes());
+        self.len += value.len();
+    }
+
+    fn truncate(&mut self, value: &str, marker: &str) {
+        let marker = &marker[..floor_char_boundary(marker.as_bytes(), N)];
+        let capacity = N - marker.len();
+        if self.len > capacity {
+            self.len = floor_char_boundary(&self.buf[..self.len], capacity);
+        } else {
+            let len = floor_char_boundary(value.as_bytes(), capacity - self.len);
+            self.push(&value[..len]);
+        }
+        self.push(marker);
+        self.truncated = true;
+    }
+}
+
+impl<const N: usize, W, T, E> fmt::Write for ArrayBuffer<'_, N, W, E>
+where
+    W: WriteStr,
+    W::Output: IntoArrayConcatWriteResult<Value = T, Error = E>,
+{
+    fn write_str(&mut self, mut value: &str) -> fmt::Result {
+        while value.len() > N - self.len {
+            match self.overflow {
+                ArrayOverflow::Truncate(marker) => {
+                    self.truncate(value, marker);
+                    return Err(fmt::Error);
+                }
+                ArrayOverflow::Split => {
+                    let len = floor_char_boundary(value.as_bytes(), N - self.len);
+                    if len == 0 && self.len == 0 {
+                        self.error = Some(ArrayConcatError::Overflow);
+                        return Err(fmt::Error);
+                    }
+                    self.push(&value[..len]);
+                    value = &value[len..];
+                    let result = self
+                        .write
+                        .write_str(buffer_str(&self.buf[..self.len]))
+                        .into_array_concat_write_result();
+                    self.len = 0;
+                    if let Err(err) = result {
+                        self.error = Some(ArrayConcatError::Write(err));
+                        return Err(fmt::Error);
+                    }
+                }
+                ArrayOverflow::Error => {
+                    self.error = Some(ArrayConcatError::Overflow);
+                    return Err(fmt::Error);
+                }
+            }
+        }
+        self.push(value);
+        Ok(())
+    }
+}
+
+fn buffer_str(buf: &[u8]) -> &str {
+    // The buffer is filled only with complete UTF-8 chars.
+    unsafe { core::str::from_utf8_unchecked(buf) }
+}
+
+impl<R: ReturnConvention> IntoArrayConcatWriteResult for R {
+    type Value = ();
+    type Error = R::Error;
+    fn into_array_concat_write_result(self) -> Result<(), R::Error> {
+        self.into_return_result().map(|_| ())
+    }
+}
+
+impl<R: ReturnConvention> IntoArrayConcatFlushResult for R {
+    type Error = R::Error;
+    fn into_array_concat_flush_result(self) -> Result<(), R::Error> {
+        self.into_return_result().map(|_| ())
+    }
+}
@@ -0,0 +1,73 @@
+use core::ffi::{c_char, CStr};
+
+use crate::{ArrayWriteCCharPtrFn, ArrayWriteCStrFn, IntoTryWriteFn, IntoWriteFn, NulPolicy};
+
+/// A write function wrapper for functions that take [`&CStr`] or [`*const c_char`]
+/// that builds nul-terminated strings in a stack buffer of `N` bytes,
+/// and do not require allocations.
+///
+/// Messages longer than `N - 1` bytes are written as several C strings
+/// split preferably at UTF-8 character boundaries.
+/// Interior nul bytes are handled with the specified [`NulPolicy`].
+///
+/// It can be used with both [`IntoWriteFn`] and [`IntoTryWriteFn`] traits,
+/// and can be specified with the `cstr_stack<N>:` or `cstr_stack<N>(policy):` prefix
+/// in [`define_writer_expr`].
+///
+/// # Panics
+///
+/// Writer panics if `N` is less than 2.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::ffi::CStr;
+/// use custom_print::{ArrayCStr, NulPolicy};
+///
+/// let mut lines = Vec::new();
+/// let mut write = |value: &CStr| lines.push(value.to_str().unwrap().to_owned());
+/// custom_print::define_macro!(
+///     cprint,
+///     fmt,
+///     ArrayCStr::<8, _>(&mut write, NulPolicy::ESCAPE)
+/// );
+///
+/// cprint!("first\0second");
+/// assert_eq!(lines, ["first\\0", "second"]);
+/// ```
+///
+/// [`&CStr`]: https://doc.rust-lang.org/std/ffi/struct.CStr.html
+/// [`*const c_char`]: https://doc.rust-lang.org/std/os/raw/type.c_char.html
+/// [`NulPolicy`]: enum.NulPolicy.html
+/// [`IntoWriteFn`]: trait.IntoWriteFn.html
+/// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ArrayCStr<const N: usize, F>(pub F, pub NulPolicy);
+
+macro_rules! def {
+    ( $func:ident, ($($ty:ty),*) ) => {
+        impl<const N: usize, F, R> IntoWriteFn<($($ty,)*)> for ArrayCStr<N, F>
+        where
+            F: FnMut($($ty),*) -> R,
+        {
+            type WriteFn = $func<N, F, R>;
+            fn into_write_fn(self) -> Self::WriteFn {
+                $func::new(self.0, self.1)
+            }
+        }
+
+        impl<const N: usize, F, R> IntoTryWriteFn<($($ty,)*)> for ArrayCStr<N, F>
+        where
+            F: FnMut($($ty),*) -> R,
+        {
+            type TryWriteFn = $func<N, F, R>;
+            fn into_try_write_fn(self) -> Self::TryWriteFn {
+                $func::new(self.0, self.1)
+            }
+        }
+    };
+}
+
+def!(ArrayWriteCStrFn, (&CStr));
+def!(ArrayWriteCCharPtrFn, (*const c_char));
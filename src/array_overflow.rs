@@ -0,0 +1,57 @@
+use core::fmt::{self, Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// A policy used by [`ArrayConcatWriter`] and [`ArrayConcatTryWriter`]
+/// when a formatted message exceeds the buffer capacity.
+///
+/// [`ArrayConcatWriter`]: struct.ArrayConcatWriter.html
+/// [`ArrayConcatTryWriter`]: struct.ArrayConcatTryWriter.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArrayOverflow {
+    /// Truncates the message on a char boundary and appends the specified marker,
+    /// so the written string including the marker fits into the buffer.
+    Truncate(&'static str),
+    /// Splits the message on char boundaries and writes it with several `write_str` calls.
+    Split,
+    /// Writes nothing and returns [`ArrayConcatError::Overflow`].
+    Error,
+}
+
+/// An error returned by [`ArrayConcatTryWriter`] write method.
+///
+/// [`ArrayConcatTryWriter`]: struct.ArrayConcatTryWriter.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArrayConcatError<E> {
+    /// The formatted message exceeds the buffer capacity
+    /// and the [`ArrayOverflow::Error`] policy is used.
+    Overflow,
+    /// A formatting trait implementation returned an error.
+    Format,
+    /// The wrapped write function returned an error.
+    Write(E),
+}
+
+impl ArrayOverflow {
+    /// The marker used by the default [`ArrayOverflow::Truncate`] policy.
+    pub const DEFAULT_MARKER: &'static str = "...";
+}
+
+impl Default for ArrayOverflow {
+    fn default() -> Self {
+        Self::Truncate(Self::DEFAULT_MARKER)
+    }
+}
+
+impl<E: Display> Display for ArrayConcatError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("formatted message exceeds the buffer capacity"),
+            Self::Format => f.write_str("a formatting trait implementation returned an error"),
+            Self::Write(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug + Display> Error for ArrayConcatError<E> {}
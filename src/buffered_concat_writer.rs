@@ -0,0 +1,225 @@
+use alloc::string::String;
+use core::fmt::{self, Arguments};
+
+use crate::{Flush, WriteFmt, WriteStr};
+
+/// The default upper bound of the buffer capacity kept by [`BufferedConcatWriter`] between calls.
+///
+/// [`BufferedConcatWriter`]: struct.BufferedConcatWriter.html
+pub const DEFAULT_MAX_BUFFER_CAPACITY: usize = 4096;
+
+/// A writer decorator that formats the message into a reused buffer
+/// and calls `write_str` of the wrapped writer once with the combined string.
+///
+/// Unlike [`ConcatWriter`] and [`ConcatTryWriter`] that allocate a new string
+/// for every formatted message, this writer keeps the buffer capacity between calls.
+/// The buffer is either a thread-local one if the `std` feature is enabled,
+/// a static one protected by a spin lock otherwise, or the caller-provided `&mut String`.
+/// If the shared buffer is already used, for example by a nested write
+/// from a [`Display`] implementation of a written value or from an interrupt,
+/// the message is formatted into a newly allocated string instead.
+/// After every write the buffer capacity is shrunk to the specified upper bound,
+/// which is [`DEFAULT_MAX_BUFFER_CAPACITY`] bytes by default.
+/// If the `std` feature is disabled and the shared buffer cannot be returned
+/// because the spin lock is held at that moment, the buffer is dropped,
+/// and the next write allocates a new shared buffer.
+///
+/// The `concat_buffered, args...` and `concat_buffered<N>, args...` modes of [`define_writer`]
+/// and [`define_try_writer`] wrap [`ConcatWriter`] or [`ConcatTryWriter`] into this writer.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::{BufferedConcatWriter, ConcatWriter};
+///
+/// let mut chunks = Vec::new();
+/// let mut buffer = String::new();
+/// let mut writer = BufferedConcatWriter::with_buffer(
+///     &mut buffer,
+///     ConcatWriter::from_closure(|value: &str| chunks.push(value.to_owned())),
+/// );
+/// let (first, second) = (1, 2);
+/// writer.write_fmt(format_args!("first{}", first)).unwrap();
+/// writer.write_fmt(format_args!("second{}", second)).unwrap();
+///
+/// assert_eq!(chunks, ["first1", "second2"]);
+/// assert!(buffer.capacity() >= "second2".len());
+/// ```
+///
+/// # Panics
+///
+/// Writer panics if a formatting trait implementation returns an error,
+/// the same way as [`ConcatWriter`] and [`ConcatTryWriter`] do.
+///
+/// [`ConcatWriter`]: struct.ConcatWriter.html
+/// [`ConcatTryWriter`]: struct.ConcatTryWriter.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`DEFAULT_MAX_BUFFER_CAPACITY`]: constant.DEFAULT_MAX_BUFFER_CAPACITY.html
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+#[derive(Debug)]
+pub struct BufferedConcatWriter<'a, W> {
+    writer: W,
+    buffer: Option<&'a mut String>,
+    max_capacity: usize,
+}
+
+impl<W> BufferedConcatWriter<'static, W>
+where
+    W: WriteStr,
+{
+    /// Creates a new `BufferedConcatWriter` that uses the shared buffer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: None,
+            max_capacity: DEFAULT_MAX_BUFFER_CAPACITY,
+        }
+    }
+}
+
+impl<'a, W> BufferedConcatWriter<'a, W>
+where
+    W: WriteStr,
+{
+    /// Creates a new `BufferedConcatWriter` that uses the specified buffer.
+    pub fn with_buffer(buffer: &'a mut String, writer: W) -> Self {
+        Self {
+            writer,
+            buffer: Some(buffer),
+            max_capacity: DEFAULT_MAX_BUFFER_CAPACITY,
+        }
+    }
+}
+
+impl<W> BufferedConcatWriter<'_, W> {
+    /// Returns the writer with the specified upper bound of the kept buffer capacity.
+    pub fn with_max_capacity(self, max_capacity: usize) -> Self {
+        Self {
+            max_capacity,
+            ..self
+        }
+    }
+
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> BufferedConcatWriter<'_, W>
+where
+    W: WriteStr,
+{
+    /// Writes a formatted string into the wrapped writer with a single `write_str` call.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> W::Output {
+        if let Some(buf) = args.as_str() {
+            return self.writer.write_str(buf);
+        }
+        match &mut self.buffer {
+            Some(buffer) => write_buffered(buffer, self.max_capacity, &mut self.writer, args),
+            None => {
+                let mut buffer = take_shared_buffer().unwrap_or_default();
+                let result = write_buffered(&mut buffer, self.max_capacity, &mut self.writer, args);
+                restore_shared_buffer(buffer);
+                result
+            }
+        }
+    }
+}
+
+impl<W> BufferedConcatWriter<'_, W>
+where
+    W: Flush,
+{
+    /// Flushes the wrapped writer.
+    pub fn flush(&mut self) -> W::Output {
+        self.writer.flush()
+    }
+}
+
+impl<W> WriteFmt for BufferedConcatWriter<'_, W>
+where
+    W: WriteStr,
+{
+    type Output = W::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        BufferedConcatWriter::write_fmt(self, args)
+    }
+}
+
+impl<W> WriteStr for BufferedConcatWriter<'_, W>
+where
+    W: WriteStr,
+{
+    type Output = W::Output;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        self.writer.write_str(buf)
+    }
+}
+
+impl<W> Flush for BufferedConcatWriter<'_, W>
+where
+    W: Flush,
+{
+    type Output = W::Output;
+
+    fn flush(&mut self) -> Self::Output {
+        self.writer.flush()
+    }
+}
+
+fn write_buffered<W: WriteStr>(
+    buffer: &mut String,
+    max_capacity: usize,
+    writer: &mut W,
+    args: Arguments<'_>,
+) -> W::Output {
+    buffer.clear();
+    fmt::write(buffer, args).expect(
+        "a formatting trait implementation returned an error when the underlying stream did not",
+    );
+    let result = writer.write_str(buffer);
+    buffer.clear();
+    buffer.shrink_to(max_capacity);
+    result
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static BUFFER: core::cell::Cell<Option<String>> = const { core::cell::Cell::new(None) };
+}
+
+#[cfg(not(feature = "std"))]
+static BUFFER: crate::spin_lock::SpinLock<Option<String>> = crate::spin_lock::SpinLock::new(None);
+
+/// Takes the shared buffer, or returns `None` if it is already used.
+fn take_shared_buffer() -> Option<String> {
+    #[cfg(feature = "std")]
+    return BUFFER.with(|buffer| buffer.take());
+    #[cfg(not(feature = "std"))]
+    return BUFFER.try_lock().and_then(|mut buffer| buffer.take());
+}
+
+/// Returns the buffer to the shared slot if it is free, otherwise drops it.
+///
+/// Without the `std` feature the buffer is dropped if the spin lock is held,
+/// and the shared slot stays empty until a later write returns its buffer.
+fn restore_shared_buffer(buf: String) {
+    #[cfg(feature = "std")]
+    BUFFER.with(|buffer| buffer.set(Some(buf)));
+    #[cfg(not(feature = "std"))]
+    if let Some(mut buffer) = BUFFER.try_lock() {
+        *buffer = Some(buf);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::utf8::floor_char_boundary;
+use crate::{IntoTryWriteFn, IntoWriteFn, NeverError, ReturnConvention, WriteBytes, WriteStr};
+
+/// A write function wrapper that splits every written message into chunks
+/// of at most `N` bytes for sinks with a maximum payload size.
+///
+/// Messages are split only at UTF-8 character boundaries,
+/// preferably right after the last newline that fits into the chunk.
+/// The [`ChunkOverflow`] policy specifies whether long messages are split
+/// with optional continuation markers, or truncated with the marker appended.
+///
+/// It implements [`WriteStr`] if the wrapped object implements [`WriteStr`]
+/// and [`WriteBytes`] if the wrapped object implements [`WriteBytes`],
+/// so it can be used with any writer, but it is mostly useful with [`ConcatWriter`]
+/// and [`ArrayConcatWriter`] that write the whole message at once.
+/// Chunks with markers are assembled in a stack buffer of `N` bytes.
+///
+/// It also implements [`IntoWriteFn`] and [`IntoTryWriteFn`] traits
+/// if the wrapped object is a closure, so it can be constructed
+/// with the `chunk<N>:` or `chunk<N>(overflow):` prefix in [`define_writer_expr`].
+///
+/// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
+/// The writing stops on the first chunk write error, which is returned as is.
+///
+/// # Panics
+///
+/// Writer panics if `N` has no space for the markers and at least one 4-byte character.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::{ChunkOverflow, ChunkingWriter, ConcatWriter};
+///
+/// let mut chunks = Vec::new();
+/// let mut write = |value: &str| chunks.push(value.to_owned());
+/// let mut writer = ConcatWriter::from_closure(ChunkingWriter::<8, _>::new(&mut write));
+/// writer.write_fmt(format_args!("ab\ncdefghijk")).unwrap();
+///
+/// let mut writer = ConcatWriter::from_closure(
+///     ChunkingWriter::<8, _>::new(&mut write).with_overflow(ChunkOverflow::ELLIPSIS),
+/// );
+/// writer.write_fmt(format_args!("abcdefghijk")).unwrap();
+///
+/// assert_eq!(chunks, ["ab\n", "cdefghij", "k", "abcde..."]);
+/// ```
+///
+/// [`ChunkOverflow`]: enum.ChunkOverflow.html
+/// [`WriteStr`]: trait.WriteStr.html
+/// [`WriteBytes`]: trait.WriteBytes.html
+/// [`ConcatWriter`]: struct.ConcatWriter.html
+/// [`ArrayConcatWriter`]: struct.ArrayConcatWriter.html
+/// [`IntoWriteFn`]: trait.IntoWriteFn.html
+/// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChunkingWriter<const N: usize, F>(F, ChunkOverflow);
+
+/// A policy used by [`ChunkingWriter`] when a message exceeds the chunk size.
+///
+/// [`ChunkingWriter`]: struct.ChunkingWriter.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChunkOverflow {
+    /// Splits the message into several chunks.
+    Split {
+        /// The marker appended to every chunk except the last one.
+        end: &'static str,
+        /// The marker prepended to every chunk except the first one.
+        start: &'static str,
+    },
+    /// Truncates the message and appends the specified marker,
+    /// so the written chunk including the marker fits into the chunk size.
+    Truncate(&'static str),
+}
+
+/// A helper trait used by [`ChunkingWriter`]
+/// to convert wrapped function result to [`Result`]`<(), E>` with error propagation.
+///
+/// [`ChunkingWriter`]: struct.ChunkingWriter.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoChunkingResult {
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_chunking_result(self) -> Result<(), Self::Error>;
+}
+
+impl ChunkOverflow {
+    /// Splits the message into several chunks without continuation markers.
+    pub const SPLIT: Self = Self::Split { end: "", start: "" };
+
+    /// Truncates the message and appends the `"..."` marker.
+    pub const ELLIPSIS: Self = Self::Truncate("...");
+}
+
+impl Default for ChunkOverflow {
+    fn default() -> Self {
+        Self::SPLIT
+    }
+}
+
+impl<const N: usize, F> ChunkingWriter<N, F> {
+    /// Creates a new `ChunkingWriter` from an object that implements [`WriteStr`]
+    /// or [`WriteBytes`], or from a closure that is converted
+    /// with [`IntoWriteFn`] or [`IntoTryWriteFn`] by the writer that uses it.
+    ///
+    /// [`WriteStr`]: trait.WriteStr.html
+    /// [`WriteBytes`]: trait.WriteBytes.html
+    /// [`IntoWriteFn`]: trait.IntoWriteFn.html
+    /// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+    pub fn new(write: F) -> Self {
+        Self(write, ChunkOverflow::default())
+    }
+
+    /// Returns the writer with the specified chunk overflow policy.
+    pub fn with_overflow(self, overflow: ChunkOverflow) -> Self {
+        Self(self.0, overflow)
+    }
+
+    /// Returns the wrapped object.
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<const N: usize, F, Ts> IntoWriteFn<Ts> for ChunkingWriter<N, F>
+where
+    F: IntoWriteFn<Ts>,
+{
+    type WriteFn = ChunkingWriter<N, F::WriteFn>;
+
+    fn into_write_fn(self) -> Self::WriteFn {
+        ChunkingWriter(self.0.into_write_fn(), self.1)
+    }
+}
+
+impl<const N: usize, F, Ts> IntoTryWriteFn<Ts> for ChunkingWriter<N, F>
+where
+    F: IntoTryWriteFn<Ts>,
+{
+    type TryWriteFn = ChunkingWriter<N, F::TryWriteFn>;
+
+    fn into_try_write_fn(self) -> Self::TryWriteFn {
+        ChunkingWriter(self.0.into_try_write_fn(), self.1)
+    }
+}
+
+impl<const N: usize, F> WriteStr for ChunkingWriter<N, F>
+where
+    F: WriteStr,
+    F::Output: IntoChunkingResult,
+{
+    type Output = Result<(), <F::Output as IntoChunkingResult>::Error>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        let write = &mut self.0;
+        write_chunks::<N, _>(buf.as_bytes(), self.1, |chunk| {
+            // Chunks are split only at char boundaries and markers are strings.
+            let chunk = unsafe { core::str::from_utf8_unchecked(chunk) };
+            write.write_str(chunk).into_chunking_result()
+        })
+    }
+}
+
+impl<const N: usize, F> WriteBytes for ChunkingWriter<N, F>
+where
+    F: WriteBytes,
+    F::Output: IntoChunkingResult,
+{
+    type Output = Result<(), <F::Output as IntoChunkingResult>::Error>;
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+        let write = &mut self.0;
+        write_chunks::<N, _>(buf, self.1, |chunk| {
+            write.write_bytes(chunk).into_chunking_result()
+        })
+    }
+}
+
+impl IntoChunkingResult for () {
+    type Error = NeverError;
+    fn into_chunking_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<T, E> IntoChunkingResult for Result<T, E> {
+    type Error = E;
+    fn into_chunking_result(self) -> Result<(), E> {
+        self.map(|_| ())
+    }
+}
+
+impl<R: ReturnConvention> IntoChunkingResult for R {
+    type Error = R::Error;
+    fn into_chunking_result(self) -> Result<(), R::Error> {
+        self.into_return_result().map(|_| ())
+    }
+}
+
+fn write_chunks<const N: usize, E>(
+    buf: &[u8],
+    overflow: ChunkOverflow,
+    mut write: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E> {
+    if buf.len() <= N {
+        return write(buf);
+    }
+    match overflow {
+        ChunkOverflow::Split { end, start } => {
+            assert_chunk_len::<N>(end.len() + start.len());
+            let mut chunk = Chunk::<N>::new();
+            let mut rest = buf;
+            let mut first = true;
+            loop {
+                let start = if first { "" } else { start };
+                if start.len() + rest.len() <= N {
+                    return write(chunk.join(start, rest, ""));
+                }
+                let len = split_position(rest, N - start.len() - end.len());
+                write(chunk.join(start, &rest[..len], end))?;
+                rest = &rest[len..];
+                first = false;
+            }
+        }
+        ChunkOverflow::Truncate(marker) => {
+            assert_chunk_len::<N>(marker.len());
+            let len = floor_char_boundary(buf, N - marker.len());
+            write(Chunk::<N>::new().join("", &buf[..len], marker))
+        }
+    }
+}
+
+fn assert_chunk_len<const N: usize>(markers_len: usize) {
+    assert!(
+        N >= markers_len + 4,
+        "chunk length should have space for markers and at least one character"
+    );
+}
+
+/// Returns the length of the next chunk that fits into the capacity,
+/// preferably ending with a newline.
+///
+/// The capacity is at least 4 bytes, so the chunk is never empty,
+/// even if the buffer is not valid UTF-8.
+fn split_position(buf: &[u8], capacity: usize) -> usize {
+    match buf[..capacity].iter().rposition(|&byte| byte == b'\n') {
+        Some(index) => index + 1,
+        None => floor_char_boundary(buf, capacity),
+    }
+}
+
+struct Chunk<const N: usize> {
+    buf: [u8; N],
+}
+
+impl<const N: usize> Chunk<N> {
+    fn new() -> Self {
+        Self { buf: [0; N] }
+    }
+
+    /// Returns the data with the markers, copying it into the buffer only if needed.
+    fn join<'a>(&'a mut self, start: &str, data: &'a [u8], end: &str) -> &'a [u8] {
+        if start.is_empty() && end.is_empty() {
+            return data;
+        }
+        let len = start.len() + data.len() + end.len();
+        self.buf[..start.len()].copy_from_slice(start.as_bytes());
+        self.buf[start.len()..start.len() + data.len()].copy_from_slice(data);
+        self.buf[start.len() + data.len()..len].copy_from_slice(end.as_bytes());
+        &self.buf[..len]
+    }
+}
@@ -1,16 +1,21 @@
 use core::fmt::{Arguments, Debug};
 
-use crate::{IntoTryWriteFn, NeverError, WriteBytes, WriteStr};
+use crate::{
+    Flush, IntoFlushFn, IntoTryWriteFn, NeverError, ReturnConvention, WriteBytes, WriteFmt,
+    WriteStr,
+};
 
 /// A writer that calls `write_str` once with a combined string.
 ///
 /// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
 ///
-/// Writer propagates error to the caller if the write function returns `Result::Err`.
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
+/// Writer propagates error to the caller if the write or flush function returns `Result::Err`.
 ///
 /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct ConcatTryWriter<F1>(F1);
+pub struct ConcatTryWriter<F1, F2 = ()>(F1, F2);
 
 /// A helper trait used by [`ConcatTryWriter`]
 /// to convert wrapped function result to [`Result`]`<T, E>`.
@@ -24,13 +29,25 @@ pub trait IntoConcatWriteResult {
     fn into_concat_write_result(self) -> Self::Output;
 }
 
-impl<F1> ConcatTryWriter<F1>
+/// A helper trait used by [`ConcatTryWriter`] flush method
+/// to convert wrapped function result to [`Result`]`<(), E>`.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoConcatFlushResult {
+    /// The resulting type after convertion.
+    type Output;
+
+    /// Performs the conversion with error propagation.
+    fn into_concat_flush_result(self) -> Self::Output;
+}
+
+impl<F1> ConcatTryWriter<F1, ()>
 where
     F1: WriteStr,
 {
     /// Creates a new `ConcatTryWriter` from an object that implements [`WriteStr`].
     pub fn new(write: F1) -> Self {
-        Self(write)
+        Self(write, ())
     }
 
     /// Creates a new `ConcatTryWriter` with a [`WriteStr`] wrapper
@@ -39,11 +56,37 @@ where
     where
         F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
     {
-        Self(closure.into_try_write_fn())
+        Self(closure.into_try_write_fn(), ())
+    }
+}
+
+impl<F1, F2> ConcatTryWriter<F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `ConcatTryWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(write, flush)
+    }
+
+    /// Creates a new `ConcatTryWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoTryWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(
+            write_closure.into_try_write_fn(),
+            flush_closure.into_flush_fn(),
+        )
     }
 }
 
-impl<F1> ConcatTryWriter<F1>
+impl<F1, F2> ConcatTryWriter<F1, F2>
 where
     Self: WriteStr,
 {
@@ -65,7 +108,28 @@ where
     }
 }
 
-impl<F1, Output> WriteStr for ConcatTryWriter<F1>
+impl<F1, F2> ConcatTryWriter<F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function, returning any error encountered.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<F1, F2> WriteFmt for ConcatTryWriter<F1, F2>
+where
+    Self: WriteStr,
+{
+    type Output = <Self as WriteStr>::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        ConcatTryWriter::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2, Output> WriteStr for ConcatTryWriter<F1, F2>
 where
     F1: WriteStr,
     F1::Output: IntoConcatWriteResult<Output = Output>,
@@ -77,7 +141,7 @@ where
     }
 }
 
-impl<F1, Output> WriteBytes for ConcatTryWriter<F1>
+impl<F1, F2, Output> WriteBytes for ConcatTryWriter<F1, F2>
 where
     F1: WriteBytes,
     F1::Output: IntoConcatWriteResult<Output = Output>,
@@ -89,6 +153,18 @@ where
     }
 }
 
+impl<F1, F2, Output> Flush for ConcatTryWriter<F1, F2>
+where
+    F2: Flush,
+    F2::Output: IntoConcatFlushResult<Output = Output>,
+{
+    type Output = Output;
+
+    fn flush(&mut self) -> Output {
+        self.1.flush().into_concat_flush_result()
+    }
+}
+
 impl IntoConcatWriteResult for () {
     type Output = Result<(), NeverError>;
     fn into_concat_write_result(self) -> Self::Output {
@@ -102,3 +178,31 @@ impl<T, E: Debug> IntoConcatWriteResult for Result<T, E> {
         self
     }
 }
+
+impl IntoConcatFlushResult for () {
+    type Output = Result<(), NeverError>;
+    fn into_concat_flush_result(self) -> Self::Output {
+        Ok(())
+    }
+}
+
+impl<E: Debug> IntoConcatFlushResult for Result<(), E> {
+    type Output = Result<(), E>;
+    fn into_concat_flush_result(self) -> Self::Output {
+        self
+    }
+}
+
+impl<R: ReturnConvention> IntoConcatWriteResult for R {
+    type Output = Result<(), R::Error>;
+    fn into_concat_write_result(self) -> Self::Output {
+        self.into_return_result().map(|_| ())
+    }
+}
+
+impl<R: ReturnConvention> IntoConcatFlushResult for R {
+    type Output = Result<(), R::Error>;
+    fn into_concat_flush_result(self) -> Self::Output {
+        self.into_return_result().map(|_| ())
+    }
+}
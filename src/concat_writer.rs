@@ -1,24 +1,27 @@
 use core::fmt::{Arguments, Debug};
 
-use crate::{IntoWriteFn, NeverError, WriteBytes, WriteStr};
+use crate::{
+    Flush, IntoFlushFn, IntoWriteFn, NeverError, ReturnConvention, WriteBytes, WriteFmt, WriteStr,
+};
 
 /// A writer that calls `write_str` once with a combined string.
 ///
 /// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
 ///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
 /// # Panics
 ///
-/// Writer panics if the write function returns `Result::Err`.
+/// Writer panics if the write or flush function returns `Result::Err`.
 ///
 /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct ConcatWriter<F1>(F1);
+pub struct ConcatWriter<F1, F2 = ()>(F1, F2);
 
 /// A helper trait used by [`ConcatWriter`]
 /// to convert wrapped function result to [`Result`]`<T, NeverError>` with error unwrapping.
 ///
 /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
-
 pub trait ExpectConcatWriteResult {
     /// The resulting type after convertion.
     type Output;
@@ -27,13 +30,22 @@ pub trait ExpectConcatWriteResult {
     fn expect_concat_write_result(self) -> Self::Output;
 }
 
-impl<F1> ConcatWriter<F1>
+/// A helper trait used by [`ConcatWriter`] flush method
+/// to convert wrapped function result to [`Result`]`<(), NeverError>` with error unwrapping.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait ExpectConcatFlushResult {
+    /// Performs the conversion with error unwrapping.
+    fn expect_concat_flush_result(self) -> Result<(), NeverError>;
+}
+
+impl<F1> ConcatWriter<F1, ()>
 where
     F1: WriteStr,
 {
     /// Creates a new `ConcatWriter` from an object that implements [`WriteStr`].
     pub fn new(write: F1) -> Self {
-        Self(write)
+        Self(write, ())
     }
 
     /// Creates a new `ConcatWriter` with a [`WriteStr`] wrapper
@@ -42,11 +54,34 @@ where
     where
         F: IntoWriteFn<Ts, WriteFn = F1>,
     {
-        Self(closure.into_write_fn())
+        Self(closure.into_write_fn(), ())
+    }
+}
+
+impl<F1, F2> ConcatWriter<F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `ConcatWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(write, flush)
+    }
+
+    /// Creates a new `ConcatWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(write_closure.into_write_fn(), flush_closure.into_flush_fn())
     }
 }
 
-impl<F1> ConcatWriter<F1>
+impl<F1, F2> ConcatWriter<F1, F2>
 where
     Self: WriteStr,
 {
@@ -68,7 +103,28 @@ where
     }
 }
 
-impl<F1, Output> WriteStr for ConcatWriter<F1>
+impl<F1, F2> ConcatWriter<F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<F1, F2> WriteFmt for ConcatWriter<F1, F2>
+where
+    Self: WriteStr,
+{
+    type Output = <Self as WriteStr>::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        ConcatWriter::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2, Output> WriteStr for ConcatWriter<F1, F2>
 where
     F1: WriteStr,
     F1::Output: ExpectConcatWriteResult<Output = Output>,
@@ -80,7 +136,7 @@ where
     }
 }
 
-impl<F1, Output> WriteBytes for ConcatWriter<F1>
+impl<F1, F2, Output> WriteBytes for ConcatWriter<F1, F2>
 where
     F1: WriteBytes,
     F1::Output: ExpectConcatWriteResult<Output = Output>,
@@ -92,6 +148,18 @@ where
     }
 }
 
+impl<F1, F2> Flush for ConcatWriter<F1, F2>
+where
+    F2: Flush,
+    F2::Output: ExpectConcatFlushResult,
+{
+    type Output = Result<(), NeverError>;
+
+    fn flush(&mut self) -> Self::Output {
+        self.1.flush().expect_concat_flush_result()
+    }
+}
+
 impl ExpectConcatWriteResult for () {
     type Output = Result<(), NeverError>;
     fn expect_concat_write_result(self) -> Self::Output {
@@ -105,3 +173,31 @@ impl<T, E: Debug> ExpectConcatWriteResult for Result<T, E> {
         Ok(self.expect("failed writing"))
     }
 }
+
+impl ExpectConcatFlushResult for () {
+    fn expect_concat_flush_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<E: Debug> ExpectConcatFlushResult for Result<(), E> {
+    fn expect_concat_flush_result(self) -> Result<(), NeverError> {
+        self.expect("failed flushing");
+        Ok(())
+    }
+}
+
+impl<R: ReturnConvention> ExpectConcatWriteResult for R {
+    type Output = Result<(), NeverError>;
+    fn expect_concat_write_result(self) -> Self::Output {
+        let _ = self.into_return_result().expect("failed writing");
+        Ok(())
+    }
+}
+
+impl<R: ReturnConvention> ExpectConcatFlushResult for R {
+    fn expect_concat_flush_result(self) -> Result<(), NeverError> {
+        let _ = self.into_return_result().expect("failed flushing");
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use alloc::borrow::Cow;
+use core::fmt::{Arguments, Debug};
+
+use crate::{Flush, NeverError, WriteFmt, WriteStr};
+
+/// A writer that writes a combined string into the primary writer,
+/// and if it fails, writes the same string into the secondary writer
+/// prefixed with a note of the original error.
+///
+/// It is useful when the primary sink can fail at runtime,
+/// like a closed socket, a full ring buffer or a throwing JS console,
+/// but the message should not be lost and should not cause a panic.
+///
+/// The message is formatted into a single string before writing,
+/// so it can be written again into the secondary writer.
+/// The primary writer should implement [`WriteStr`] and the secondary one [`WriteFmt`].
+/// Writer outputs can be either `()` or `for<T, E> `[`Result`]`<T, E>`.
+/// The primary writer error should implement [`Debug`] to be written into the note.
+///
+/// Writer returns the secondary writer error if both writers fail.
+/// The flush method flushes both writers and returns the secondary writer flush result.
+///
+/// The `fallback(args...; args...)` mode of [`define_writer`] and [`define_try_writer`]
+/// uses [`define_try_writer`] for the primary writer, so that it does not panic.
+///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::{ConcatTryWriter, ConcatWriter, FallbackWriter};
+///
+/// let mut fallback = Vec::new();
+/// let mut writer = FallbackWriter::new(
+///     ConcatTryWriter::from_closure(|_: &str| Err::<(), _>("closed")),
+///     ConcatWriter::from_closure(|value: &str| fallback.push(value.to_owned())),
+/// );
+/// let second = "second";
+/// writer.write_fmt(format_args!("first {}", second)).unwrap();
+/// drop(writer);
+///
+/// assert_eq!(fallback, ["[primary writer failed: \"closed\"] first second"]);
+/// ```
+///
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`WriteStr`]: trait.WriteStr.html
+/// [`WriteFmt`]: trait.WriteFmt.html
+/// [`Debug`]: https://doc.rust-lang.org/std/fmt/trait.Debug.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FallbackWriter<A, B>(A, B);
+
+/// A helper trait used by [`FallbackWriter`]
+/// to convert writer outputs to [`Result`]`<(), E>` with error propagation.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoFallbackResult {
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_fallback_result(self) -> Result<(), Self::Error>;
+}
+
+type FallbackResult<B> = Result<(), <B as IntoFallbackResult>::Error>;
+
+impl<A, B> FallbackWriter<A, B> {
+    /// Creates a new `FallbackWriter` with the specified primary and secondary writers.
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self(primary, secondary)
+    }
+
+    /// Returns the wrapped writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.0, self.1)
+    }
+}
+
+impl<A, B> FallbackWriter<A, B>
+where
+    Self: WriteStr,
+{
+    /// Writes a formatted string into the primary writer,
+    /// or into the secondary writer if the primary one fails.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteStr>::Output {
+        let buf = match args.as_str() {
+            Some(buf) => Cow::Borrowed(buf),
+            None => Cow::Owned(alloc::fmt::format(args)),
+        };
+        self.write_str(&buf)
+    }
+}
+
+impl<A, B> FallbackWriter<A, B>
+where
+    Self: Flush,
+{
+    /// Flushes both writers, returning the secondary writer flush result.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<A, B> WriteFmt for FallbackWriter<A, B>
+where
+    Self: WriteStr,
+{
+    type Output = <Self as WriteStr>::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        FallbackWriter::write_fmt(self, args)
+    }
+}
+
+impl<A, B> WriteStr for FallbackWriter<A, B>
+where
+    A: WriteStr,
+    A::Output: IntoFallbackResult,
+    <A::Output as IntoFallbackResult>::Error: Debug,
+    B: WriteFmt,
+    B::Output: IntoFallbackResult,
+{
+    type Output = FallbackResult<B::Output>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        match self.0.write_str(buf).into_fallback_result() {
+            Ok(()) => Ok(()),
+            Err(err) => self
+                .1
+                .write_fmt(format_args!("[primary writer failed: {:?}] {}", err, buf))
+                .into_fallback_result(),
+        }
+    }
+}
+
+impl<A, B> Flush for FallbackWriter<A, B>
+where
+    A: Flush,
+    B: Flush,
+    B::Output: IntoFallbackResult,
+{
+    type Output = FallbackResult<B::Output>;
+
+    fn flush(&mut self) -> Self::Output {
+        let _ = self.0.flush();
+        self.1.flush().into_fallback_result()
+    }
+}
+
+impl IntoFallbackResult for () {
+    type Error = NeverError;
+    fn into_fallback_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<T, E> IntoFallbackResult for Result<T, E> {
+    type Error = E;
+    fn into_fallback_result(self) -> Result<(), E> {
+        self.map(|_| ())
+    }
+}
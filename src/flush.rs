@@ -1,7 +1,10 @@
 /// A trait for objects which can flush written data on request.
 ///
-/// This trait is used by [`IoWriter`].
+/// This trait is used by [`FmtWriter`], [`ConcatWriter`], [`IoWriter`]
+/// and their fallible variants.
 ///
+/// [`FmtWriter`]: struct.FmtWriter.html
+/// [`ConcatWriter`]: struct.ConcatWriter.html
 /// [`IoWriter`]: struct.IoWriter.html
 pub trait Flush {
     /// The resulting type after flushing.
@@ -17,3 +20,14 @@ impl Flush for () {
     #[inline]
     fn flush(&mut self) -> Self::Output {}
 }
+
+impl<W> Flush for &mut W
+where
+    W: Flush + ?Sized,
+{
+    type Output = W::Output;
+
+    fn flush(&mut self) -> Self::Output {
+        (**self).flush()
+    }
+}
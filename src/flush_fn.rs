@@ -1,8 +1,11 @@
 use crate::Flush;
 
-/// A wrapper for flush function `for<R> FnMut(*const c_char) -> R`.
+/// A wrapper for flush function `for<R> FnMut() -> R`.
 ///
-/// It implements [`Flush`] trait and can be used in conjunction with IntoTryWriteFn trait to simplify type inference.
+/// It implements [`Flush`] trait and can be used in conjunction with [`IntoFlushFn`] trait
+/// to simplify type inference.
+///
+/// [`IntoFlushFn`]: trait.IntoFlushFn.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct FlushFn<F, R>(F)
 where
@@ -1,36 +1,87 @@
-use core::fmt::{self, Arguments, Debug};
+use core::fmt::{self, Arguments, Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
 
-use crate::{IntoTryWriteFn, WriteBytes, WriteStr};
+use crate::{
+    Flush, IntoFlushFn, IntoTryWriteFn, NeverError, ReturnConvention, WriteBytes, WriteFmt,
+    WriteStr,
+};
 
 /// A writer that calls `write_str` for each formatted chunk, but do not require allocations.
 ///
-/// Write function can return either `()` or [`fmt::Result`].
+/// Write function can return either `()` or `for<E> `[`Result`]`<(), E>`.
 ///
-/// Writer propagates error to the caller if the write function returns `Result::Err`.
-/// Note that the error context will be lost, because [`fmt::Error`]
-/// does not support transmission of an error other than that an error occurred.
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
 ///
-/// [`fmt::Error`]: https://doc.rust-lang.org/std/fmt/struct.Error.html
-/// [`fmt::Result`]: https://doc.rust-lang.org/std/fmt/type.Result.html
+/// Writer propagates error to the caller if the write or flush function returns `Result::Err`.
+/// The first error returned by the write function is stashed while formatting
+/// and returned wrapped into [`FmtWriteError::Write`],
+/// so the caller gets the actual error of the wrapped function.
+/// [`FmtWriteError::Format`] is returned
+/// if a formatting trait implementation returns an error while the write function does not.
+///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::{FmtTryWriter, FmtWriteError};
+///
+/// let mut writer = FmtTryWriter::from_closure(|_: &str| Err::<(), _>("closed"));
+/// assert_eq!(
+///     writer.write_fmt(format_args!("{}", "first")),
+///     Err(FmtWriteError::Write("closed"))
+/// );
+/// ```
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct FmtTryWriter<F1>(F1);
+pub struct FmtTryWriter<F1, F2 = ()>(F1, F2);
+
+/// An error returned by [`FmtTryWriter`] write method.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FmtWriteError<E> {
+    /// A formatting trait implementation returned an error.
+    Format,
+    /// The wrapped write function returned an error.
+    Write(E),
+}
 
 /// A helper trait used by [`FmtTryWriter`]
-/// to convert wrapped function result to [`fmt::Result`] with error propagation.
+/// to convert wrapped function result to [`Result`]`<(), E>` with error propagation.
 ///
-/// [`fmt::Result`]: https://doc.rust-lang.org/std/fmt/type.Result.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
 pub trait IntoFmtWriteResult {
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_fmt_write_result(self) -> Result<(), Self::Error>;
+}
+
+/// A helper trait used by [`FmtTryWriter`] flush method
+/// to convert wrapped function result to [`Result`]`<(), E>` with error propagation.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoFmtFlushResult {
+    /// The resulting error type after convertion.
+    type Error;
+
     /// Performs the conversion with error propagation.
-    fn into_fmt_write_result(self) -> fmt::Result;
+    fn into_fmt_flush_result(self) -> Result<(), Self::Error>;
+}
+
+/// A [`fmt::Write`] adapter that stashes the first error returned by the wrapped writer.
+struct StashError<'a, W, E> {
+    write: &'a mut W,
+    error: Option<E>,
 }
 
-impl<F1> FmtTryWriter<F1>
+impl<F1> FmtTryWriter<F1, ()>
 where
     F1: WriteStr,
 {
     /// Creates a new `FmtTryWriter` from an object that implements [`WriteStr`].
     pub fn new(write: F1) -> Self {
-        Self(write)
+        Self(write, ())
     }
 
     /// Creates a new `FmtTryWriter` with a [`WriteStr`] wrapper
@@ -39,13 +90,40 @@ where
     where
         F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
     {
-        Self(closure.into_try_write_fn())
+        Self(closure.into_try_write_fn(), ())
+    }
+}
+
+impl<F1, F2> FmtTryWriter<F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `FmtTryWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(write, flush)
+    }
+
+    /// Creates a new `FmtTryWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoTryWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(
+            write_closure.into_try_write_fn(),
+            flush_closure.into_flush_fn(),
+        )
     }
 }
 
-impl<F1> FmtTryWriter<F1>
+impl<F1, F2, E> FmtTryWriter<F1, F2>
 where
-    Self: fmt::Write,
+    F1: WriteStr,
+    F1::Output: IntoFmtWriteResult<Error = E>,
 {
     /// Writes a formatted string into this writer, returning any error encountered.
     ///
@@ -55,52 +133,155 @@ where
     ///
     /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
     /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
-    pub fn write_fmt(&mut self, args: Arguments<'_>) -> fmt::Result {
-        fmt::Write::write_fmt(self, args)
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> Result<(), FmtWriteError<E>> {
+        let mut adapter = StashError {
+            write: &mut self.0,
+            error: None,
+        };
+        match fmt::write(&mut adapter, args) {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => match adapter.error {
+                Some(err) => Err(FmtWriteError::Write(err)),
+                None => Err(FmtWriteError::Format),
+            },
+        }
     }
 }
 
-impl<F1> fmt::Write for FmtTryWriter<F1>
+impl<F1, F2> FmtTryWriter<F1, F2>
 where
-    Self: WriteStr<Output = fmt::Result>,
+    Self: Flush,
 {
-    fn write_str(&mut self, buf: &str) -> fmt::Result {
-        WriteStr::write_str(self, buf)
+    /// Flushes this writer using the wrapped flush function, returning any error encountered.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
     }
 }
 
-impl<F1> WriteStr for FmtTryWriter<F1>
+impl<F1, F2, E> WriteFmt for FmtTryWriter<F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoFmtWriteResult<Error = E>,
+{
+    type Output = Result<(), FmtWriteError<E>>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        FmtTryWriter::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2> fmt::Write for FmtTryWriter<F1, F2>
 where
     F1: WriteStr,
     F1::Output: IntoFmtWriteResult,
 {
-    type Output = fmt::Result;
+    fn write_str(&mut self, buf: &str) -> fmt::Result {
+        WriteStr::write_str(self, buf).map_err(|_| fmt::Error)
+    }
+}
+
+impl<F1, F2, E> WriteStr for FmtTryWriter<F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: IntoFmtWriteResult<Error = E>,
+{
+    type Output = Result<(), E>;
 
     fn write_str(&mut self, buf: &str) -> Self::Output {
         self.0.write_str(buf).into_fmt_write_result()
     }
 }
 
-impl<F1> WriteBytes for FmtTryWriter<F1>
+impl<F1, F2, E> WriteBytes for FmtTryWriter<F1, F2>
 where
     F1: WriteBytes,
-    F1::Output: IntoFmtWriteResult,
+    F1::Output: IntoFmtWriteResult<Error = E>,
 {
-    type Output = fmt::Result;
+    type Output = Result<(), E>;
 
     fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
         self.0.write_bytes(buf).into_fmt_write_result()
     }
 }
 
+impl<F1, F2, E> Flush for FmtTryWriter<F1, F2>
+where
+    F2: Flush,
+    F2::Output: IntoFmtFlushResult<Error = E>,
+{
+    type Output = Result<(), E>;
+
+    fn flush(&mut self) -> Self::Output {
+        self.1.flush().into_fmt_flush_result()
+    }
+}
+
+impl<W, E> fmt::Write for StashError<'_, W, E>
+where
+    W: WriteStr,
+    W::Output: IntoFmtWriteResult<Error = E>,
+{
+    fn write_str(&mut self, buf: &str) -> fmt::Result {
+        self.write
+            .write_str(buf)
+            .into_fmt_write_result()
+            .map_err(|err| {
+                self.error = Some(err);
+                fmt::Error
+            })
+    }
+}
+
+impl<E: Display> Display for FmtWriteError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format => f.write_str("a formatting trait implementation returned an error"),
+            Self::Write(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug + Display> Error for FmtWriteError<E> {}
+
 impl IntoFmtWriteResult for () {
-    fn into_fmt_write_result(self) -> fmt::Result {
+    type Error = NeverError;
+    fn into_fmt_write_result(self) -> Result<(), NeverError> {
         Ok(())
     }
 }
 
 impl<E: Debug> IntoFmtWriteResult for Result<(), E> {
-    fn into_fmt_write_result(self) -> fmt::Result {
-        self.map_err(|_| fmt::Error)
+    type Error = E;
+    fn into_fmt_write_result(self) -> Result<(), E> {
+        self
+    }
+}
+
+impl IntoFmtFlushResult for () {
+    type Error = NeverError;
+    fn into_fmt_flush_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<E: Debug> IntoFmtFlushResult for Result<(), E> {
+    type Error = E;
+    fn into_fmt_flush_result(self) -> Result<(), E> {
+        self
+    }
+}
+
+impl<R: ReturnConvention> IntoFmtWriteResult for R {
+    type Error = R::Error;
+    fn into_fmt_write_result(self) -> Result<(), R::Error> {
+        self.into_return_result().map(|_| ())
+    }
+}
+
+impl<R: ReturnConvention> IntoFmtFlushResult for R {
+    type Error = R::Error;
+    fn into_fmt_flush_result(self) -> Result<(), R::Error> {
+        self.into_return_result().map(|_| ())
     }
 }
@@ -1,18 +1,20 @@
 use core::fmt::{self, Arguments, Debug};
 
-use crate::{IntoWriteFn, WriteBytes, WriteStr};
+use crate::{Flush, IntoFlushFn, IntoWriteFn, ReturnConvention, WriteBytes, WriteFmt, WriteStr};
 
 /// A writer that calls `write_str` for each formatted chunk, but do not require allocations.
 ///
 /// Write function can return either `()` or `for<E> `[`Result`]`<(), E>`.
 ///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
 /// # Panics
 ///
-/// Writer panics if the write function returns `Result::Err`.
+/// Writer panics if the write or flush function returns `Result::Err`.
 ///
 /// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct FmtWriter<F1>(F1);
+pub struct FmtWriter<F1, F2 = ()>(F1, F2);
 
 /// A helper trait used by [`FmtWriter`]
 /// to convert wrapped function result to [`fmt::Result`] with error unwrapping.
@@ -23,13 +25,22 @@ pub trait ExpectFmtWriteResult {
     fn expect_fmt_write_result(self) -> fmt::Result;
 }
 
-impl<F1> FmtWriter<F1>
+/// A helper trait used by [`FmtWriter`] flush method
+/// to convert wrapped function result to [`fmt::Result`] with error unwrapping.
+///
+/// [`fmt::Result`]: https://doc.rust-lang.org/std/fmt/type.Result.html
+pub trait ExpectFmtFlushResult {
+    /// Performs the conversion with error unwrapping.
+    fn expect_fmt_flush_result(self) -> fmt::Result;
+}
+
+impl<F1> FmtWriter<F1, ()>
 where
     F1: WriteStr,
 {
     /// Creates a new `FmtWriter` from an object that implements [`WriteStr`].
     pub fn new(write: F1) -> Self {
-        Self(write)
+        Self(write, ())
     }
 
     /// Creates a new `FmtWriter` with a [`WriteStr`] wrapper
@@ -38,11 +49,34 @@ where
     where
         F: IntoWriteFn<Ts, WriteFn = F1>,
     {
-        Self(closure.into_write_fn())
+        Self(closure.into_write_fn(), ())
+    }
+}
+
+impl<F1, F2> FmtWriter<F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `FmtWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(write, flush)
+    }
+
+    /// Creates a new `FmtWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(write_closure.into_write_fn(), flush_closure.into_flush_fn())
     }
 }
 
-impl<F1> FmtWriter<F1>
+impl<F1, F2> FmtWriter<F1, F2>
 where
     Self: fmt::Write,
 {
@@ -59,7 +93,28 @@ where
     }
 }
 
-impl<F1> fmt::Write for FmtWriter<F1>
+impl<F1, F2> FmtWriter<F1, F2>
+where
+    Self: Flush,
+{
+    /// Flushes this writer using the wrapped flush function.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<F1, F2> WriteFmt for FmtWriter<F1, F2>
+where
+    Self: fmt::Write,
+{
+    type Output = fmt::Result;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        FmtWriter::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2> fmt::Write for FmtWriter<F1, F2>
 where
     Self: WriteStr<Output = fmt::Result>,
 {
@@ -68,7 +123,7 @@ where
     }
 }
 
-impl<F1> WriteStr for FmtWriter<F1>
+impl<F1, F2> WriteStr for FmtWriter<F1, F2>
 where
     F1: WriteStr,
     F1::Output: ExpectFmtWriteResult,
@@ -80,7 +135,7 @@ where
     }
 }
 
-impl<F1> WriteBytes for FmtWriter<F1>
+impl<F1, F2> WriteBytes for FmtWriter<F1, F2>
 where
     F1: WriteBytes,
     F1::Output: ExpectFmtWriteResult,
@@ -92,6 +147,18 @@ where
     }
 }
 
+impl<F1, F2> Flush for FmtWriter<F1, F2>
+where
+    F2: Flush,
+    F2::Output: ExpectFmtFlushResult,
+{
+    type Output = fmt::Result;
+
+    fn flush(&mut self) -> Self::Output {
+        self.1.flush().expect_fmt_flush_result()
+    }
+}
+
 impl ExpectFmtWriteResult for () {
     fn expect_fmt_write_result(self) -> fmt::Result {
         Ok(())
@@ -104,3 +171,30 @@ impl<E: Debug> ExpectFmtWriteResult for Result<(), E> {
         Ok(())
     }
 }
+
+impl ExpectFmtFlushResult for () {
+    fn expect_fmt_flush_result(self) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<E: Debug> ExpectFmtFlushResult for Result<(), E> {
+    fn expect_fmt_flush_result(self) -> fmt::Result {
+        self.expect("failed flushing");
+        Ok(())
+    }
+}
+
+impl<R: ReturnConvention> ExpectFmtWriteResult for R {
+    fn expect_fmt_write_result(self) -> fmt::Result {
+        let _ = self.into_return_result().expect("failed writing");
+        Ok(())
+    }
+}
+
+impl<R: ReturnConvention> ExpectFmtFlushResult for R {
+    fn expect_fmt_flush_result(self) -> fmt::Result {
+        let _ = self.into_return_result().expect("failed flushing");
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::fmt::{self, Debug, Formatter};
+
+use crate::{IntoTryWriteFn, IntoWriteFn, WriteStr};
+
+/// A sink stored in [`GlobalSink`].
+///
+/// It is a boxed [`WriteStr`] object if the `alloc` feature is enabled,
+/// or a function pointer otherwise.
+///
+/// [`GlobalSink`]: struct.GlobalSink.html
+/// [`WriteStr`]: trait.WriteStr.html
+#[cfg(feature = "alloc")]
+pub type Sink = Box<dyn WriteStr<Output = ()> + Send>;
+
+/// A sink stored in [`GlobalSink`].
+///
+/// It is a boxed [`WriteStr`] object if the `alloc` feature is enabled,
+/// or a function pointer otherwise.
+///
+/// [`GlobalSink`]: struct.GlobalSink.html
+/// [`WriteStr`]: trait.WriteStr.html
+#[cfg(not(feature = "alloc"))]
+pub type Sink = fn(&str);
+
+/// A slot for a sink that can be replaced at runtime,
+/// so the output of generated macros can be redirected after they are defined,
+/// for example to capture it in tests, to silence it in benchmarks or to reroute it after init.
+///
+/// The `global` and `global(sink)` modes of [`define_writer`] and [`define_try_writer`]
+/// write into [`GLOBAL_SINK`] or into the specified sink.
+/// References to the sink also implement [`IntoWriteFn`] and [`IntoTryWriteFn`],
+/// so they can be used as write functions with other writers,
+/// like `concat, &custom_print::GLOBAL_SINK`.
+/// If no sink is set, the written data is discarded.
+///
+/// The sink is protected by [`std::sync::Mutex`] if the `std` feature is enabled,
+/// or by a spin lock otherwise, and the boxed sink is called while the lock is held.
+/// Writes into the same `GlobalSink` made while its sink is called are discarded
+/// instead of deadlocking, like a sink that logs through a macro defined with the `global` mode.
+/// If the `std` feature is enabled, only the writes of the thread that calls the sink
+/// are discarded, and other threads wait for the sink to return.
+/// Otherwise, the writes from interrupt handlers and other cores are discarded too.
+/// The sink must not replace the sink of the same `GlobalSink`.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use std::sync::{Arc, Mutex};
+///
+/// custom_print::define_macro!(cprintln, concat, &custom_print::GLOBAL_SINK);
+///
+/// fn main() {
+///     let lines = Arc::new(Mutex::new(Vec::new()));
+///     let captured = Arc::clone(&lines);
+///     custom_print::GLOBAL_SINK.with_sink(
+///         move |value: &str| captured.lock().unwrap().push(value.to_owned()),
+///         || cprintln!("captured"),
+///     );
+///     cprintln!("discarded");
+///
+///     assert_eq!(*lines.lock().unwrap(), ["captured\n"]);
+/// }
+/// ```
+///
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`GLOBAL_SINK`]: static.GLOBAL_SINK.html
+/// [`IntoWriteFn`]: trait.IntoWriteFn.html
+/// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+/// [`std::sync::Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+pub struct GlobalSink(Lock<Option<Sink>>);
+
+/// The global sink used by the `global` mode of [`define_writer`] and [`define_try_writer`].
+///
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+pub static GLOBAL_SINK: GlobalSink = GlobalSink::new();
+
+#[cfg(feature = "std")]
+type Lock<T> = std::sync::Mutex<T>;
+#[cfg(not(feature = "std"))]
+type Lock<T> = crate::spin_lock::SpinLock<T>;
+
+impl GlobalSink {
+    /// Creates a new `GlobalSink` without a sink.
+    pub const fn new() -> Self {
+        Self(Lock::new(None))
+    }
+
+    fn with_slot<R>(&self, f: impl FnOnce(&mut Option<Sink>) -> R) -> R {
+        #[cfg(feature = "std")]
+        return f(&mut self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner));
+        #[cfg(not(feature = "std"))]
+        return f(&mut self.0.lock());
+    }
+
+    /// Sets the sink and returns the previous one.
+    ///
+    /// The sink can be a closure with any signature supported by [`IntoWriteFn`]
+    /// that returns `()`, or any [`WriteStr`] object with `()` output.
+    ///
+    /// [`IntoWriteFn`]: trait.IntoWriteFn.html
+    /// [`WriteStr`]: trait.WriteStr.html
+    #[cfg(feature = "alloc")]
+    pub fn set_sink<F, Ts>(&self, sink: F) -> Option<Sink>
+    where
+        F: IntoWriteFn<Ts>,
+        F::WriteFn: WriteStr<Output = ()> + Send + 'static,
+    {
+        self.replace_sink(Some(Box::new(sink.into_write_fn())))
+    }
+
+    /// Sets the sink function and returns the previous one.
+    #[cfg(not(feature = "alloc"))]
+    pub fn set_sink(&self, sink: fn(&str)) -> Option<Sink> {
+        self.replace_sink(Some(sink))
+    }
+
+    /// Removes the sink and returns it, so the written data is discarded.
+    pub fn take_sink(&self) -> Option<Sink> {
+        self.replace_sink(None)
+    }
+
+    /// Replaces the sink with the specified one and returns the previous one.
+    pub fn replace_sink(&self, sink: Option<Sink>) -> Option<Sink> {
+        self.with_slot(|slot| core::mem::replace(slot, sink))
+    }
+
+    /// Sets the sink, calls the function and restores the previous sink,
+    /// even if the function panics.
+    ///
+    /// The sink accepts the same values as in [`set_sink`].
+    ///
+    /// [`set_sink`]: #method.set_sink
+    #[cfg(feature = "alloc")]
+    pub fn with_sink<F, Ts, R>(&self, sink: F, f: impl FnOnce() -> R) -> R
+    where
+        F: IntoWriteFn<Ts>,
+        F::WriteFn: WriteStr<Output = ()> + Send + 'static,
+    {
+        let _restore = Restore(self, self.set_sink(sink));
+        f()
+    }
+
+    /// Sets the sink function, calls the function and restores the previous sink,
+    /// even if the function panics.
+    #[cfg(not(feature = "alloc"))]
+    pub fn with_sink<R>(&self, sink: fn(&str), f: impl FnOnce() -> R) -> R {
+        let _restore = Restore(self, self.set_sink(sink));
+        f()
+    }
+
+    /// Writes the string into the sink if it is set.
+    ///
+    /// The string is discarded if it is written while the sink is called.
+    /// If the `testing` feature is enabled, the string is recorded instead
+    /// while a [`capture`] is active on the current thread.
+    ///
+    /// [`capture`]: testing/fn.capture.html
+    pub fn write_str(&self, buf: &str) {
+        #[cfg(feature = "testing")]
+        if crate::testing::write_captured(buf) {
+            return;
+        }
+        #[cfg(feature = "std")]
+        if let Some(_writing) = Writing::enter(self) {
+            self.with_slot(|slot| {
+                if let Some(sink) = slot {
+                    sink.write_str(buf);
+                }
+            });
+        }
+        #[cfg(all(feature = "alloc", not(feature = "std")))]
+        if let Some(mut slot) = self.0.try_lock() {
+            if let Some(sink) = &mut *slot {
+                sink.write_str(buf);
+            }
+        }
+        #[cfg(not(feature = "alloc"))]
+        if let Some(sink) = self.with_slot(|slot| *slot) {
+            sink(buf);
+        }
+    }
+}
+
+impl Default for GlobalSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for GlobalSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let is_set = self.with_slot(|slot| slot.is_some());
+        f.debug_struct("GlobalSink")
+            .field("is_set", &is_set)
+            .finish()
+    }
+}
+
+impl WriteStr for &GlobalSink {
+    type Output = ();
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        GlobalSink::write_str(self, buf);
+    }
+}
+
+impl<'a> IntoWriteFn<(&'a GlobalSink,)> for &'a GlobalSink {
+    type WriteFn = Self;
+
+    fn into_write_fn(self) -> Self::WriteFn {
+        self
+    }
+}
+
+impl<'a> IntoTryWriteFn<(&'a GlobalSink,)> for &'a GlobalSink {
+    type TryWriteFn = Self;
+
+    fn into_try_write_fn(self) -> Self::TryWriteFn {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static WRITING: core::cell::RefCell<alloc::vec::Vec<usize>> =
+        const { core::cell::RefCell::new(alloc::vec::Vec::new()) };
+}
+
+/// Marks the sink as being written by the current thread until dropped.
+#[cfg(feature = "std")]
+struct Writing(usize);
+
+#[cfg(feature = "std")]
+impl Writing {
+    /// Returns `None` if the sink is already being written by the current thread.
+    fn enter(sink: &GlobalSink) -> Option<Self> {
+        let address: *const GlobalSink = sink;
+        let address = address as usize;
+        WRITING.with(|writing| {
+            let mut writing = writing.borrow_mut();
+            if writing.contains(&address) {
+                None
+            } else {
+                writing.push(address);
+                Some(Self(address))
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Writing {
+    fn drop(&mut self) {
+        let _ = WRITING.try_with(|writing| writing.borrow_mut().retain(|&item| item != self.0));
+    }
+}
+
+/// Restores the previous sink when dropped.
+struct Restore<'a>(&'a GlobalSink, Option<Sink>);
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.replace_sink(self.1.take());
+    }
+}
@@ -0,0 +1,323 @@
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::fmt::{self, Arguments, Write};
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{Flush, NeverError, WriteFmt};
+
+/// A writer decorator that detects nested writes made while the wrapped writer is writing
+/// and handles them with the specified [`NestedWrite`] policy.
+///
+/// Nested writes happen if a [`Debug`] or [`Display`] implementation of a written value
+/// or the write function itself calls a macro that uses a guarded writer.
+/// Without the guard such calls recurse until the stack overflows,
+/// or deadlock if the writer is locked.
+///
+/// Whether a guarded writer is writing is stored in a thread-local flag
+/// if the `std` feature is enabled, or in a static atomic flag otherwise,
+/// so without `std` writes from other threads or interrupts are also treated as nested.
+/// The flag is shared by all guarded writers, so the nested output is queued
+/// or dropped even if it is written by another guarded writer.
+/// The guard should be specified before the `locked` modifier,
+/// like `guarded, locked, fmt, args...`, so nested writes do not wait for the lock.
+/// In this order the `dbg`-like macros take the lock for every write inside the guard
+/// instead of for the whole macro invocation.
+///
+/// Writer output can be either `()` or `for<T, E> `[`Result`]`<T, E>`.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use core::fmt::{self, Display, Formatter};
+/// use std::sync::Mutex;
+///
+/// static CHUNKS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// fn write(value: &str) {
+///     CHUNKS.lock().unwrap().push(value.to_owned());
+/// }
+///
+/// custom_print::define_macro!(cprintln, guarded, concat, crate::write);
+///
+/// struct Value;
+///
+/// impl Display for Value {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+///         cprintln!("nested");
+///         f.write_str("value")
+///     }
+/// }
+///
+/// fn main() {
+///     cprintln!("outer {}", Value);
+///     assert_eq!(*CHUNKS.lock().unwrap(), ["outer value\n", "nested\n"]);
+/// }
+/// ```
+///
+/// [`NestedWrite`]: enum.NestedWrite.html
+/// [`Debug`]: https://doc.rust-lang.org/std/fmt/trait.Debug.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug)]
+pub struct GuardedWriter<W>(W, NestedWrite);
+
+/// A policy used by [`GuardedWriter`] for writes made while another guarded write is in progress.
+///
+/// [`GuardedWriter`]: struct.GuardedWriter.html
+#[derive(Clone, Copy, Debug, Default)]
+pub enum NestedWrite {
+    /// Discards the nested output.
+    Drop,
+    /// Writes the nested output with the specified function.
+    ///
+    /// The function should not use guarded writers itself.
+    Fallback(fn(&str)),
+    /// Stores the nested output and writes it with the outermost guarded writer
+    /// after its write is finished.
+    ///
+    /// Without the `alloc` feature at most 256 bytes are queued, the rest are discarded.
+    #[default]
+    Queue,
+}
+
+/// A helper trait used by [`GuardedWriter`]
+/// to convert wrapped writer outputs to [`Result`]`<(), E>` with error propagation.
+///
+/// [`GuardedWriter`]: struct.GuardedWriter.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoGuardedResult {
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_guarded_result(self) -> Result<(), Self::Error>;
+}
+
+impl<W> GuardedWriter<W> {
+    /// Creates a new `GuardedWriter` that queues nested writes.
+    pub fn new(writer: W) -> Self {
+        Self(writer, NestedWrite::default())
+    }
+
+    /// Returns the writer with the specified nested write policy.
+    pub fn with_policy(self, policy: NestedWrite) -> Self {
+        Self(self.0, policy)
+    }
+
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+impl<W> GuardedWriter<W>
+where
+    Self: WriteFmt,
+{
+    /// Writes a formatted string into the wrapped writer
+    /// or handles it with the nested write policy.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteFmt>::Output {
+        WriteFmt::write_fmt(self, args)
+    }
+}
+
+impl<W> GuardedWriter<W>
+where
+    Self: Flush,
+{
+    /// Flushes the wrapped writer.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<W> WriteFmt for GuardedWriter<W>
+where
+    W: WriteFmt,
+    W::Output: IntoGuardedResult,
+{
+    type Output = Result<(), <W::Output as IntoGuardedResult>::Error>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        let _guard = match WritingGuard::enter() {
+            Some(guard) => guard,
+            None => {
+                write_nested(self.1, args);
+                return Ok(());
+            }
+        };
+        let result = self.0.write_fmt(args).into_guarded_result();
+        let mut queued = Ok(());
+        while let Some(message) = take_queued() {
+            let message = message.as_str();
+            queued = queued.and(
+                self.0
+                    .write_fmt(format_args!("{}", message))
+                    .into_guarded_result(),
+            );
+        }
+        result.and(queued)
+    }
+}
+
+impl<W> Flush for GuardedWriter<W>
+where
+    W: Flush,
+{
+    type Output = W::Output;
+
+    fn flush(&mut self) -> Self::Output {
+        self.0.flush()
+    }
+}
+
+impl IntoGuardedResult for () {
+    type Error = NeverError;
+    fn into_guarded_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<T, E> IntoGuardedResult for Result<T, E> {
+    type Error = E;
+    fn into_guarded_result(self) -> Result<(), E> {
+        self.map(|_| ())
+    }
+}
+
+fn write_nested(policy: NestedWrite, args: Arguments<'_>) {
+    match policy {
+        NestedWrite::Drop => {}
+        NestedWrite::Fallback(write) => {
+            let _ = fmt::write(&mut FallbackFn(write), args);
+        }
+        NestedWrite::Queue => {
+            // Format the message before locking the queue,
+            // since formatting may write nested messages itself.
+            let mut message = Queue::new();
+            let _ = message.write_fmt(args);
+            let _ = with_queue(|queue| queue.write_str(message.as_str()));
+        }
+    }
+}
+
+struct FallbackFn(fn(&str));
+
+impl Write for FallbackFn {
+    fn write_str(&mut self, buf: &str) -> fmt::Result {
+        (self.0)(buf);
+        Ok(())
+    }
+}
+
+/// Clears the writing flag when dropped, even if the wrapped writer panics.
+struct WritingGuard;
+
+impl WritingGuard {
+    fn enter() -> Option<Self> {
+        #[cfg(feature = "std")]
+        let is_writing = WRITING.with(|writing| writing.replace(true));
+        #[cfg(not(feature = "std"))]
+        let is_writing = WRITING.swap(true, Ordering::Acquire);
+        if is_writing {
+            None
+        } else {
+            Some(Self)
+        }
+    }
+}
+
+impl Drop for WritingGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        WRITING.with(|writing| writing.set(false));
+        #[cfg(not(feature = "std"))]
+        WRITING.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static WRITING: Cell<bool> = const { Cell::new(false) };
+    static QUEUE: core::cell::RefCell<Queue> = const { core::cell::RefCell::new(Queue::new()) };
+}
+
+#[cfg(not(feature = "std"))]
+static WRITING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(not(feature = "std"))]
+static QUEUE: crate::spin_lock::SpinLock<Queue> = crate::spin_lock::SpinLock::new(Queue::new());
+
+fn with_queue<R>(f: impl FnOnce(&mut Queue) -> R) -> R {
+    #[cfg(feature = "std")]
+    return QUEUE.with(|queue| f(&mut queue.borrow_mut()));
+    #[cfg(not(feature = "std"))]
+    return f(&mut QUEUE.lock());
+}
+
+fn take_queued() -> Option<Queue> {
+    with_queue(|queue| {
+        if queue.as_str().is_empty() {
+            None
+        } else {
+            Some(core::mem::take(queue))
+        }
+    })
+}
+
+#[cfg(feature = "alloc")]
+type Queue = alloc::string::String;
+
+#[cfg(not(feature = "alloc"))]
+const QUEUE_CAPACITY: usize = 256;
+
+/// A fixed-size string buffer that discards the data that does not fit.
+#[cfg(not(feature = "alloc"))]
+struct Queue {
+    buf: [u8; QUEUE_CAPACITY],
+    len: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Queue {
+    const fn new() -> Self {
+        Self {
+            buf: [0; QUEUE_CAPACITY],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole characters are written into the buffer.
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Default for Queue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Write for Queue {
+    fn write_str(&mut self, buf: &str) -> fmt::Result {
+        let mut len = buf.len().min(QUEUE_CAPACITY - self.len);
+        while !buf.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.buf[self.len..self.len + len].copy_from_slice(&buf.as_bytes()[..len]);
+        self.len += len;
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::FlushFn;
+
+/// A trait used to inference type of flush closure wrapper.
+///
+/// This trait used by [`FmtWriter`], [`ConcatWriter`], [`IoWriter`]
+/// and their fallible variants to wrap closures without arguments into [`FlushFn`].
+///
+/// [`FmtWriter`]: struct.FmtWriter.html
+/// [`ConcatWriter`]: struct.ConcatWriter.html
+/// [`IoWriter`]: struct.IoWriter.html
+pub trait IntoFlushFn {
+    /// The corresponding flush function wrapper.
+    type FlushFn;
+
+    /// Returns the wrapped function.
+    fn into_flush_fn(self) -> Self::FlushFn;
+}
+
+impl<F, R> IntoFlushFn for F
+where
+    F: FnMut() -> R,
+{
+    type FlushFn = FlushFn<F, R>;
+    fn into_flush_fn(self) -> Self::FlushFn {
+        Self::FlushFn::new(self)
+    }
+}
@@ -1,27 +1,37 @@
 #[cfg(feature = "alloc")]
+use alloc::ffi::CString;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
-#[cfg(feature = "std")]
-use std::ffi::{CStr, CString};
-#[cfg(feature = "std")]
-use std::os::raw::c_char;
+#[cfg(feature = "alloc")]
+use core::ffi::{c_char, CStr};
+use core::fmt::Arguments;
 
 #[cfg(feature = "alloc")]
 use crate::WriteStringFn;
-#[cfg(feature = "std")]
+use crate::{
+    Record, TryWriteUtf16PtrFn, WriteArgsFn, WriteBytesFn, WriteLenPtrFn, WritePtrLenFn,
+    WriteRecordFn, WriteStrFn, WriteUtf16Fn, WriteUtf16PtrLenFn,
+};
+#[cfg(feature = "alloc")]
 use crate::{TryWriteCCharPtrFn, TryWriteCStrFn, TryWriteCStringFn};
-use crate::{WriteBytesFn, WriteLenPtrFn, WritePtrLenFn, WriteStrFn};
 
 /// A trait used to inference type of fallible write closure wrapper.
 ///
 /// This trait used by [`FmtTryWriter`], [`ConcatTryWriter`] and [`IoTryWriter`].
 ///
 /// Both [`IntoWriteFn`] and `IntoTryWriteFn` traits provides the same wrappers for
-/// closures with `*const u8`, `usize`, `&[u8]`, [`&str`] and [`String`] arguments.
+/// closures with `*const u8`, `usize`, `&[u8]`, [`&str`], [`&Record`], [`Arguments`] and [`String`] arguments,
+/// and for closures with `&[u16]` and `*const u16, usize` arguments
+/// that receive the message transcoded into UTF-16.
+/// Without the `alloc` feature, messages longer than 256 bytes are transcoded
+/// and written in several chunks split at character boundaries.
 /// This variant uses non-panicking versions for
-/// closures with [`&CStr`], [`CString`], and [`*const c_char`] arguments.
+/// closures with [`&CStr`], [`CString`], [`*const c_char`] and nul-terminated `*const u16` arguments.
 ///
 /// [`&str`]: https://doc.rust-lang.org/std/str/index.html
 /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+/// [`&Record`]: struct.Record.html
+/// [`Arguments`]: https://doc.rust-lang.org/std/fmt/struct.Arguments.html
 /// [`&CStr`]: https://doc.rust-lang.org/std/ffi/struct.CStr.html
 /// [`CString`]: https://doc.rust-lang.org/std/ffi/struct.CString.html
 /// [`*const c_char`]: https://doc.rust-lang.org/std/os/raw/type.c_char.html
@@ -57,12 +67,18 @@ def!((F, R), WriteLenPtrFn<F, R>, (usize, *const u8));
 def!((F, R), WriteBytesFn<F, R>, (&[u8]));
 
 def!((F, R), WriteStrFn<F, R>, (&str));
+def!((F, R), WriteRecordFn<F, R>, (&Record<'_>));
+def!((F, R), WriteArgsFn<F, R>, (Arguments<'_>));
+
+def!((F, R), WriteUtf16Fn<F, R>, (&[u16]));
+def!((F, R), WriteUtf16PtrLenFn<F, R>, (*const u16, usize));
+def!((F, R), TryWriteUtf16PtrFn<F, R>, (*const u16));
 #[cfg(feature = "alloc")]
 def!((F, R), WriteStringFn<F, R>, (String));
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 def!((F, R), TryWriteCStrFn<F, R>, (&CStr));
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 def!((F, R), TryWriteCStringFn<F, R>, (CString));
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 def!((F, R), TryWriteCCharPtrFn<F, R>, (*const c_char));
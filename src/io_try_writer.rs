@@ -1,26 +1,58 @@
 use core::fmt::{Arguments, Debug};
+use std::boxed::Box;
+use std::error::Error;
 use std::ffi;
 use std::io;
 
-use crate::{Flush, IntoTryWriteFn, WriteBytes, WriteStr};
+use crate::{Flush, IntoFlushFn, IntoTryWriteFn, ReturnConvention, WriteBytes, WriteFmt, WriteStr};
 
 /// A writer that uses `write_bytes` and has both `write` and `flush` methods.
 ///
 /// It calls `write_bytes` for each formatted chunk like the [`FmtTryWriter`],
 /// but provides write and flush methods that allows you to use [`BufWriter`], [`LineWriter`] etc.
 ///
-/// Write function can return either `()`, `usize`, [`io::Result`]`<()>`, [`io::Result`]`<usize>`,
-/// [`Result`]`<(), `[`ffi::NulError`]`>` or [`Result`]`<usize, `[`ffi::NulError`]`>`.
+/// Write function can return either `()`, `usize`,
+/// `for<E> `[`Result`]`<(), E>` or `for<E> `[`Result`]`<usize, E>`
+/// where `E: Into<Box<dyn Error + Send + Sync>>`,
+/// like [`io::Error`], [`ffi::NulError`], any other [`Error`] or a string.
+/// [`io::Error`] is returned as is, [`ffi::NulError`] is converted
+/// to [`ErrorKind::InvalidData`] and the other errors to [`ErrorKind::Other`].
+/// Use [`map_err`] or [`with_error_kind`] methods to customize the conversion.
 ///
 /// The `usize` itself or in `Result` indicates how many bytes were written.
 /// `write_fmt` method that is used by [`write!`] and [`writeln!`]
 /// will continuously call write until there is no more data to be written
 /// or a non-[`ErrorKind::Interrupted`] kind is returned.
 ///
-/// Flush function can return either `()` or [`io::Result`]`<()>`.
+/// Flush function can return either `()` or `for<E> `[`Result`]`<(), E>`
+/// with the same error conversion.
 ///
 /// Writer propagates error to the caller if the write function returns `Result::Err`.
 ///
+/// # Examples
+///
+/// ```rust
+/// use std::io::{ErrorKind, LineWriter, Write};
+/// use custom_print::IoTryWriter;
+///
+/// #[derive(Debug)]
+/// struct Closed;
+///
+/// impl std::fmt::Display for Closed {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("closed")
+///     }
+/// }
+///
+/// impl std::error::Error for Closed {}
+///
+/// let writer = IoTryWriter::from_closure(|_: &[u8]| Err::<(), _>(Closed));
+/// let mut writer = LineWriter::new(writer.with_error_kind(ErrorKind::BrokenPipe));
+/// let err = writeln!(writer, "first").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+/// assert_eq!(err.to_string(), "closed");
+/// ```
+///
 /// [`FmtTryWriter`]: struct.FmtTryWriter.html
 /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
 /// [`writeln!`]: https://doc.rust-lang.org/std/macro.writeln.html
@@ -30,9 +62,22 @@ use crate::{Flush, IntoTryWriteFn, WriteBytes, WriteStr};
 /// [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
 /// [`LineWriter`]: https://doc.rust-lang.org/std/io/struct.LineWriter.html
 /// [`ErrorKind::Interrupted`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Interrupted
+/// [`ErrorKind::InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+/// [`ErrorKind::Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other
+/// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+/// [`Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
+/// [`map_err`]: #method.map_err
+/// [`with_error_kind`]: #method.with_error_kind
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct IoTryWriter<F1, F2>(F1, F2);
 
+/// A write function wrapper used by [`IoTryWriter::map_err`]
+/// that converts write function errors to [`io::Error`] with the specified mapping function.
+///
+/// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MapIoErrorFn<F, M>(F, M);
+
 /// A helper trait used by [`IoTryWriter`] write method
 /// to convert wrapped function result to [`io::Result`] with error propagation.
 ///
@@ -61,6 +106,20 @@ where
     pub fn new(write: F1, flush: F2) -> Self {
         Self(write, flush)
     }
+
+    /// Creates a new `IoTryWriter` with [`WriteBytes`] and [`Flush`] wrappers
+    /// deduced with [`IntoTryWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoTryWriteFn<Ts, TryWriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(
+            write_closure.into_try_write_fn(),
+            flush_closure.into_flush_fn(),
+        )
+    }
 }
 
 impl<F1> IoTryWriter<F1, ()>
@@ -77,7 +136,33 @@ where
     }
 }
 
-impl<F1> IoTryWriter<F1, ()>
+impl<F1, F2> IoTryWriter<F1, F2> {
+    /// Converts the write function errors to [`io::Error`] with the specified mapping function.
+    ///
+    /// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+    pub fn map_err<M, E>(self, map: M) -> IoTryWriter<MapIoErrorFn<F1, M>, F2>
+    where
+        M: FnMut(E) -> io::Error,
+    {
+        IoTryWriter(MapIoErrorFn(self.0, map), self.1)
+    }
+
+    /// Converts the write function errors to [`io::Error`] with the specified [`ErrorKind`].
+    ///
+    /// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+    /// [`ErrorKind`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html
+    pub fn with_error_kind<E>(
+        self,
+        kind: io::ErrorKind,
+    ) -> IoTryWriter<MapIoErrorFn<F1, impl FnMut(E) -> io::Error>, F2>
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.map_err(move |err: E| io::Error::new(kind, err))
+    }
+}
+
+impl<F1, F2> IoTryWriter<F1, F2>
 where
     Self: io::Write,
 {
@@ -92,6 +177,22 @@ where
     pub fn write_fmt(&mut self, args: Arguments<'_>) -> io::Result<()> {
         io::Write::write_fmt(self, args)
     }
+
+    /// Flushes this writer using the wrapped flush function, returning any error encountered.
+    pub fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(self)
+    }
+}
+
+impl<F1, F2> WriteFmt for IoTryWriter<F1, F2>
+where
+    Self: io::Write,
+{
+    type Output = io::Result<()>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        IoTryWriter::write_fmt(self, args)
+    }
 }
 
 impl<F1, F2> io::Write for IoTryWriter<F1, F2>
@@ -131,45 +232,71 @@ where
     }
 }
 
-impl IntoIoWriteResult for () {
-    fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        Ok(buf.len())
+impl<F, M, T, E> WriteStr for MapIoErrorFn<F, M>
+where
+    F: WriteStr<Output = Result<T, E>>,
+    M: FnMut(E) -> io::Error,
+{
+    type Output = io::Result<T>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        self.0.write_str(buf).map_err(&mut self.1)
     }
 }
 
-impl IntoIoWriteResult for usize {
-    fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        let _ = buf;
-        Ok(self)
+impl<F, M, T, E> WriteBytes for MapIoErrorFn<F, M>
+where
+    F: WriteBytes<Output = Result<T, E>>,
+    M: FnMut(E) -> io::Error,
+{
+    type Output = io::Result<T>;
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+        self.0.write_bytes(buf).map_err(&mut self.1)
     }
 }
 
-impl IntoIoWriteResult for Result<(), ffi::NulError> {
+fn into_io_error<E>(err: E) -> io::Error
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    let err = match err.into().downcast::<io::Error>() {
+        Ok(err) => return *err,
+        Err(err) => err,
+    };
+    if err.is::<ffi::NulError>() {
+        return io::Error::new(io::ErrorKind::InvalidData, err);
+    }
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+impl IntoIoWriteResult for () {
     fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        self.map_or_else(
-            |err| Err(io::Error::new(io::ErrorKind::InvalidData, err)),
-            |_| Ok(buf.len()),
-        )
+        Ok(buf.len())
     }
 }
 
-impl IntoIoWriteResult for Result<usize, ffi::NulError> {
+impl IntoIoWriteResult for usize {
     fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        let _ = buf;
-        self.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        Ok(self.min(buf.len()))
     }
 }
 
-impl IntoIoWriteResult for io::Result<()> {
+impl<E> IntoIoWriteResult for Result<(), E>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
     fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        self.map(|_| buf.len())
+        self.map_or_else(|err| Err(into_io_error(err)), |_| Ok(buf.len()))
     }
 }
 
-impl IntoIoWriteResult for io::Result<usize> {
+impl<E> IntoIoWriteResult for Result<usize, E>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
     fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        let _ = buf;
-        self
+        self.map(|len| len.min(buf.len())).map_err(into_io_error)
     }
 }
 
@@ -179,8 +306,34 @@ impl IntoIoFlushResult for () {
     }
 }
 
-impl IntoIoFlushResult for io::Result<()> {
+impl<E> IntoIoFlushResult for Result<(), E>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn into_io_flush_result(self) -> io::Result<()> {
+        self.map_err(into_io_error)
+    }
+}
+
+impl<R> IntoIoWriteResult for R
+where
+    R: ReturnConvention,
+    R::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn into_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
+        match self.into_return_result() {
+            Ok(written) => Ok(written.map_or(buf.len(), |len| len.min(buf.len()))),
+            Err(err) => Err(into_io_error(err)),
+        }
+    }
+}
+
+impl<R> IntoIoFlushResult for R
+where
+    R: ReturnConvention,
+    R::Error: Into<Box<dyn Error + Send + Sync>>,
+{
     fn into_io_flush_result(self) -> io::Result<()> {
-        self
+        self.into_return_result().map(|_| ()).map_err(into_io_error)
     }
 }
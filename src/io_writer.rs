@@ -1,7 +1,7 @@
 use core::fmt::{Arguments, Debug};
 use std::io;
 
-use crate::{Flush, IntoWriteFn, WriteBytes, WriteStr};
+use crate::{Flush, IntoFlushFn, IntoWriteFn, ReturnConvention, WriteBytes, WriteFmt, WriteStr};
 
 /// A writer that uses `write_bytes` and has both `write` and `flush` methods.
 ///
@@ -60,6 +60,17 @@ where
     pub fn new(write: F1, flush: F2) -> Self {
         Self(write, flush)
     }
+
+    /// Creates a new `IoWriter` with [`WriteBytes`] and [`Flush`] wrappers
+    /// deduced with [`IntoWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(write_closure.into_write_fn(), flush_closure.into_flush_fn())
+    }
 }
 
 impl<F1> IoWriter<F1, ()>
@@ -76,7 +87,7 @@ where
     }
 }
 
-impl<F1> IoWriter<F1, ()>
+impl<F1, F2> IoWriter<F1, F2>
 where
     Self: io::Write,
 {
@@ -91,6 +102,22 @@ where
     pub fn write_fmt(&mut self, args: Arguments<'_>) -> io::Result<()> {
         io::Write::write_fmt(self, args)
     }
+
+    /// Flushes this writer using the wrapped flush function.
+    pub fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(self)
+    }
+}
+
+impl<F1, F2> WriteFmt for IoWriter<F1, F2>
+where
+    Self: io::Write,
+{
+    type Output = io::Result<()>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        IoWriter::write_fmt(self, args)
+    }
 }
 
 impl<F1, F2> io::Write for IoWriter<F1, F2>
@@ -138,8 +165,7 @@ impl ExpectIoWriteResult for () {
 
 impl ExpectIoWriteResult for usize {
     fn expect_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        let _ = buf;
-        Ok(self)
+        Ok(self.min(buf.len()))
     }
 }
 
@@ -152,8 +178,7 @@ impl<E: Debug> ExpectIoWriteResult for Result<(), E> {
 
 impl<E: Debug> ExpectIoWriteResult for Result<usize, E> {
     fn expect_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
-        let _ = buf;
-        Ok(self.expect("failed writing"))
+        Ok(self.expect("failed writing").min(buf.len()))
     }
 }
 
@@ -169,3 +194,17 @@ impl<E: Debug> ExpectIoFlushResult for Result<(), E> {
         Ok(())
     }
 }
+
+impl<R: ReturnConvention> ExpectIoWriteResult for R {
+    fn expect_io_write_result(self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.into_return_result().expect("failed writing");
+        Ok(written.map_or(buf.len(), |len| len.min(buf.len())))
+    }
+}
+
+impl<R: ReturnConvention> ExpectIoFlushResult for R {
+    fn expect_io_flush_result(self) -> io::Result<()> {
+        let _ = self.into_return_result().expect("failed flushing");
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use core::fmt::{self, Display, Formatter};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Trace as usize);
+
+/// A logging level used by `error`, `warn`, `info`, `debug` and `trace` macros
+/// defined with [`define_log`] or [`define_macros`].
+///
+/// [`define_log`]: macro.define_log.html
+/// [`define_macros`]: macro.define_macros.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// The "error" level.
+    Error = 1,
+    /// The "warn" level.
+    Warn,
+    /// The "info" level.
+    Info,
+    /// The "debug" level.
+    Debug,
+    /// The "trace" level.
+    Trace,
+}
+
+/// A runtime maximum logging level filter set with [`set_max_level`].
+///
+/// [`set_max_level`]: fn.set_max_level.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum LevelFilter {
+    /// Disables all logging macros.
+    Off,
+    /// Enables only the [`Level::Error`] macros.
+    Error,
+    /// Enables the [`Level::Warn`] macros and above.
+    Warn,
+    /// Enables the [`Level::Info`] macros and above.
+    Info,
+    /// Enables the [`Level::Debug`] macros and above.
+    Debug,
+    /// Enables all logging macros.
+    Trace,
+}
+
+/// Sets the runtime maximum logging level.
+///
+/// Logging macros with a more verbose level do nothing and do not evaluate their arguments.
+/// All levels are enabled by default.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::{set_max_level, LevelFilter};
+///
+/// let mut string = String::new();
+/// custom_print::define_macros!({ warn, info }, concat, |value: &str| string += value);
+///
+/// set_max_level(LevelFilter::Warn);
+/// warn!("first");
+/// info!("second");
+/// set_max_level(LevelFilter::Trace);
+/// assert_eq!(string, "first\n");
+/// ```
+pub fn set_max_level(level: LevelFilter) {
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+/// Returns the runtime maximum logging level.
+pub fn max_level() -> LevelFilter {
+    match MAX_LEVEL.load(Ordering::Relaxed) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+impl Level {
+    /// Returns `true` if the level is enabled by the runtime maximum logging level.
+    pub fn is_enabled(self) -> bool {
+        self as usize <= MAX_LEVEL.load(Ordering::Relaxed)
+    }
+
+    /// Returns the lowercase level name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Binds the level to the function that takes the level and the written string.
+#[doc(hidden)]
+pub fn _with_level<F, R>(level: Level, mut write: F) -> impl FnMut(&str) -> R
+where
+    F: FnMut(Level, &str) -> R,
+{
+    move |value| write(level, value)
+}
@@ -14,7 +14,9 @@
 //! [`define_macros`], [`define_macro`] or [`define_init_panic_hook`].
 //! These macros define macros or functions with the specified names that use
 //! [`FmtWriter`], [`FmtTryWriter`], [`ConcatWriter`], [`ConcatTryWriter`],
-//! [`IoWriter`] or [`IoTryWriter`] with the specified closure, unsafe function or extern function.
+//! [`ArrayConcatWriter`], [`ArrayConcatTryWriter`], [`ArgsWriter`], [`ArgsTryWriter`],
+//! [`IoWriter`] or [`IoTryWriter`]
+//! with the specified closure, unsafe function or extern function.
 //!
 //! # Usage
 //!
@@ -86,8 +88,8 @@
 //!
 //! An example with a function that takes a [`c_char`] pointer and overriding
 //! [`std::print`] and [`std::println`] functions:
-#![cfg_attr(feature = "std", doc = " ```rust")]
-#![cfg_attr(not(feature = "std"), doc = " ```rust,compile_fail")]
+#![cfg_attr(feature = "alloc", doc = " ```rust")]
+#![cfg_attr(not(feature = "alloc"), doc = " ```rust,compile_fail")]
 //! fn write(_value: *const std::os::raw::c_char) { /* ... */ }
 //!
 //! custom_print::define_macros!({ cprint, cprintln }, concat, crate::write);
@@ -226,10 +228,19 @@
 //! # Feature Flags
 //!
 //! - `alloc` (implied by `std` so enabled by default):
-//!   Enables [`WriteStringFn`] and [`ConcatWriter`] types.
+//!   Enables [`WriteStringFn`], [`ConcatWriter`], [`LineBufWriter`] and [`OnceWriter`] types,
+//!   `{Try|Lossy}Write{CStr|CString|CCharPtr}Fn`, `LossyCStr`, `BufferedConcatWriter`
+//!   the `line`, `concat_buffered` and `once:` writer modes and the `lossy:` prefix.
+//!   Without it, functions that take C strings can be used with `ArrayCStr`
+//!   and the `cstr_stack<N>:` prefix.
+//!   `GlobalSink` stores a boxed sink with it and a `fn(&str)` sink without it.
 //! - `std` (enabled by default):
-//!   Enables [`IoWriter`], `{Try}Write{CStr|CString|CCharPtr}Fn`,
-//!   [`define_panic_hook`] and [`define_init_panic_hook`].
+//!   Enables [`IoWriter`], [`define_panic_hook`] and [`define_init_panic_hook`].
+//!   The `locked` writer modifier uses a reentrant `MutexWriterLock` with it
+//!   and a non-reentrant `SpinWriterLock` without it, so nested locked writes deadlock.
+//! - `testing`:
+//!   Enables the `testing` module with a recording sink, the `capture` writer mode
+//!   and `GlobalSink` output capturing, and the `assert_printed` and `assert_chunks` macros.
 //!
 //! # Similar crates
 //!
@@ -332,8 +343,14 @@
 //! [`FmtTryWriter`]: struct.FmtTryWriter.html
 //! [`ConcatWriter`]: struct.ConcatWriter.html
 //! [`ConcatTryWriter`]: struct.ConcatTryWriter.html
+//! [`ArrayConcatWriter`]: struct.ArrayConcatWriter.html
+//! [`ArrayConcatTryWriter`]: struct.ArrayConcatTryWriter.html
+//! [`ArgsWriter`]: struct.ArgsWriter.html
+//! [`ArgsTryWriter`]: struct.ArgsTryWriter.html
 //! [`IoWriter`]: struct.IoWriter.html
 //! [`IoTryWriter`]: struct.IoTryWriter.html
+//! [`LineBufWriter`]: struct.LineBufWriter.html
+//! [`OnceWriter`]: struct.OnceWriter.html
 //! [`web-log`]: https://crates.io/crates/web-log
 //! [`wasm-rs-dbg`]: https://crates.io/crates/wasm-rs-dbg
 //! [`console_log`]: https://crates.io/crates/console_log
@@ -362,48 +379,127 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+mod args_try_writer;
+mod args_writer;
+mod array_concat_try_writer;
+mod array_concat_writer;
+mod array_cstr;
+mod array_overflow;
+#[cfg(feature = "alloc")]
+mod buffered_concat_writer;
+mod chunking_writer;
 #[cfg(feature = "alloc")]
 mod concat_try_writer;
 #[cfg(feature = "alloc")]
 mod concat_writer;
+#[cfg(feature = "alloc")]
+mod fallback_writer;
 mod flush;
 mod flush_fn;
 mod fmt_try_writer;
 mod fmt_writer;
+mod global_sink;
+mod guarded_writer;
+mod into_flush_fn;
 mod into_try_write_fn;
 mod into_write_fn;
 #[cfg(feature = "std")]
 mod io_try_writer;
 #[cfg(feature = "std")]
 mod io_writer;
+mod level;
+#[cfg(feature = "alloc")]
+mod line_buf_writer;
+mod locked_writer;
+#[cfg(feature = "alloc")]
+mod lossy_cstr;
 mod macros;
+mod multi_write_result;
 mod never_error;
+mod nul_policy;
+#[cfg(feature = "alloc")]
+mod once_writer;
+mod prefix_writer;
+mod record;
+mod return_convention;
+#[cfg(not(feature = "std"))]
+mod spin_lock;
+mod tee_writer;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod utf16;
+mod utf8;
 mod write_bytes;
+mod write_fmt;
 mod write_fns;
 mod write_str;
+mod writer_lock;
 
+pub use args_try_writer::{ArgsTryWriter, IntoArgsFlushResult, IntoArgsWriteResult};
+pub use args_writer::{ArgsWriter, ExpectArgsFlushResult, ExpectArgsWriteResult};
+pub use array_concat_try_writer::ArrayConcatTryWriter;
+pub use array_concat_writer::{
+    ArrayConcatWriter, IntoArrayConcatFlushResult, IntoArrayConcatWriteResult,
+};
+pub use array_cstr::ArrayCStr;
+pub use array_overflow::{ArrayConcatError, ArrayOverflow};
+#[cfg(feature = "alloc")]
+pub use buffered_concat_writer::{BufferedConcatWriter, DEFAULT_MAX_BUFFER_CAPACITY};
+pub use chunking_writer::{ChunkOverflow, ChunkingWriter, IntoChunkingResult};
 #[cfg(feature = "alloc")]
-pub use concat_try_writer::{ConcatTryWriter, IntoConcatWriteResult};
+pub use concat_try_writer::{ConcatTryWriter, IntoConcatFlushResult, IntoConcatWriteResult};
 #[cfg(feature = "alloc")]
-pub use concat_writer::{ConcatWriter, ExpectConcatWriteResult};
+pub use concat_writer::{ConcatWriter, ExpectConcatFlushResult, ExpectConcatWriteResult};
+#[cfg(feature = "alloc")]
+pub use fallback_writer::{FallbackWriter, IntoFallbackResult};
 pub use flush::Flush;
 pub use flush_fn::FlushFn;
-pub use fmt_try_writer::{FmtTryWriter, IntoFmtWriteResult};
-pub use fmt_writer::{ExpectFmtWriteResult, FmtWriter};
+pub use fmt_try_writer::{FmtTryWriter, FmtWriteError, IntoFmtFlushResult, IntoFmtWriteResult};
+pub use fmt_writer::{ExpectFmtFlushResult, ExpectFmtWriteResult, FmtWriter};
+pub use global_sink::{GlobalSink, Sink, GLOBAL_SINK};
+pub use guarded_writer::{GuardedWriter, IntoGuardedResult, NestedWrite};
+pub use into_flush_fn::IntoFlushFn;
 pub use into_try_write_fn::IntoTryWriteFn;
 pub use into_write_fn::IntoWriteFn;
 #[cfg(feature = "std")]
-pub use io_try_writer::{IntoIoFlushResult, IntoIoWriteResult, IoTryWriter};
+pub use io_try_writer::{IntoIoFlushResult, IntoIoWriteResult, IoTryWriter, MapIoErrorFn};
 #[cfg(feature = "std")]
 pub use io_writer::{ExpectIoFlushResult, ExpectIoWriteResult, IoWriter};
+pub use level::{_with_level, max_level, set_max_level, Level, LevelFilter};
+#[cfg(feature = "alloc")]
+pub use line_buf_writer::{flush_lines, LineBufWriter};
+pub use locked_writer::LockedWriter;
+#[cfg(feature = "alloc")]
+pub use lossy_cstr::LossyCStr;
+pub use multi_write_result::MultiWriteResult;
 pub use never_error::NeverError;
+pub use nul_policy::NulPolicy;
+#[cfg(feature = "alloc")]
+pub use once_writer::{_OnceWriterSlot, OnceWriter, OnceWriterGuard};
+pub use prefix_writer::{LinePrefix, PrefixWriter};
+pub use record::{_RecordMetadata, _RecordScope, Record, RecordKind};
+pub use return_convention::{
+    Errno, ErrnoCount, Flag, Ignored, Presence, ReturnConvention, ReturnError,
+};
+pub use tee_writer::{IntoTeeResult, TeeError, TeeWriter};
+pub use utf16::Utf16NulError;
 pub use write_bytes::WriteBytes;
+pub use write_fmt::WriteFmt;
 #[cfg(feature = "alloc")]
 pub use write_fns::WriteStringFn;
-#[cfg(feature = "std")]
 pub use write_fns::{
-    TryWriteCCharPtrFn, TryWriteCStrFn, TryWriteCStringFn, WriteCCharPtrFn, WriteCStrFn,
-    WriteCStringFn,
+    ArrayWriteCCharPtrFn, ArrayWriteCStrFn, TryWriteUtf16PtrFn, WriteArgsFn, WriteBytesFn,
+    WriteLenPtrFn, WritePtrLenFn, WriteRecordFn, WriteStrFn, WriteUtf16Fn, WriteUtf16PtrFn,
+    WriteUtf16PtrLenFn,
+};
+#[cfg(feature = "alloc")]
+pub use write_fns::{
+    LossyWriteCCharPtrFn, LossyWriteCStrFn, LossyWriteCStringFn, TryWriteCCharPtrFn,
+    TryWriteCStrFn, TryWriteCStringFn, WriteCCharPtrFn, WriteCStrFn, WriteCStringFn,
 };
-pub use write_fns::{WriteBytesFn, WriteLenPtrFn, WritePtrLenFn, WriteStrFn};
 pub use write_str::{WriteStr, WriteStrAsBytes};
+#[cfg(feature = "std")]
+pub use writer_lock::MutexWriterLock;
+pub use writer_lock::{
+    _WriterLockScope, DefaultWriterLock, SpinWriterLock, WriterLock, WRITER_LOCK,
+};
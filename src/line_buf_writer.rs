@@ -0,0 +1,357 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Arguments;
+use core::mem::take;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    ExpectConcatFlushResult, ExpectConcatWriteResult, Flush, IntoFlushFn, IntoWriteFn, NeverError,
+    WriteFmt, WriteStr,
+};
+
+/// A writer that accumulates partial lines and calls `write_str` only with complete lines
+/// with the trailing newline removed.
+///
+/// It is useful for sinks like `console.log` or `syslog` that add their own newline,
+/// so that `print!("a"); print!("b"); println!("c")` produces a single `"abc"` line.
+/// Both `\n` and `\r\n` line endings are removed.
+///
+/// The pending partial line is stored in a buffer identified by the writer key
+/// set with [`with_key`], so writers with the same key share it
+/// even if they are constructed separately.
+/// A writer created without a key uses a buffer of its own
+/// and writes its pending partial line when it is dropped.
+/// The buffer is thread-local if the `std` feature is enabled,
+/// or a static buffer protected by a spin lock otherwise.
+/// The `line` mode of [`define_writer`] uses the module path of the call site
+/// and the stringified writer arguments as the key,
+/// because the arguments are resolved in the module of the call site.
+///
+/// The pending partial line is written when the writer is flushed,
+/// or when [`flush_lines`] is called, which is done by the panic hooks defined by this crate.
+///
+/// Write function can return either `()` or `for<T, E> `[`Result`]`<T, E>`.
+/// Because [`flush_lines`] may write the pending partial line after the writer is gone,
+/// a copy of the write function is stored with it,
+/// so the write function should be `Clone + Send + 'static`.
+/// Use [`ConcatWriter`] for write closures that borrow their environment.
+///
+/// Optional flush function can return either `()` or `for<E> `[`Result`]`<(), E>`.
+///
+/// # Panics
+///
+/// Writer panics if the write or flush function returns `Result::Err`.
+///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::LineBufWriter;
+/// use once_cell::sync::Lazy;
+/// use std::sync::Mutex;
+///
+/// static LINES: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+///
+/// fn write(value: &str) {
+///     LINES.lock().unwrap().push(value.to_owned());
+/// }
+///
+/// let mut writer = LineBufWriter::from_closure(write).with_key("example");
+/// writer.write_fmt(format_args!("a")).unwrap();
+/// writer.write_fmt(format_args!("b")).unwrap();
+/// writer.write_fmt(format_args!("c\nd")).unwrap();
+/// assert_eq!(*LINES.lock().unwrap(), ["abc"]);
+/// writer.flush().unwrap();
+/// assert_eq!(*LINES.lock().unwrap(), ["abc", "d"]);
+/// ```
+///
+/// [`with_key`]: struct.LineBufWriter.html#method.with_key
+/// [`define_writer`]: macro.define_writer.html
+/// [`flush_lines`]: fn.flush_lines.html
+/// [`ConcatWriter`]: struct.ConcatWriter.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Debug)]
+pub struct LineBufWriter<F1, F2 = ()>(LineKey, F1, F2);
+
+/// The key of the buffer used by a [`LineBufWriter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LineKey {
+    Shared(&'static str),
+    Instance(usize),
+}
+
+impl LineKey {
+    fn instance() -> Self {
+        static NEXT_INSTANCE: AtomicUsize = AtomicUsize::new(0);
+        Self::Instance(NEXT_INSTANCE.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+type LineWriteFn = Box<dyn FnMut(&str) + Send>;
+
+struct LineBuffer {
+    key: LineKey,
+    line: String,
+    write: Option<LineWriteFn>,
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static BUFFERS: core::cell::RefCell<Vec<LineBuffer>> =
+        const { core::cell::RefCell::new(Vec::new()) };
+}
+
+#[cfg(not(feature = "std"))]
+static BUFFERS: crate::spin_lock::SpinLock<Vec<LineBuffer>> =
+    crate::spin_lock::SpinLock::new(Vec::new());
+
+fn with_buffers<R>(f: impl FnOnce(&mut Vec<LineBuffer>) -> R) -> R {
+    #[cfg(feature = "std")]
+    return BUFFERS.with(|buffers| f(&mut buffers.borrow_mut()));
+    #[cfg(not(feature = "std"))]
+    return f(&mut BUFFERS.lock());
+}
+
+/// Calls the function with the buffer with the specified key
+/// and removes the buffer if it has no pending partial line afterwards.
+fn with_buffer<R>(key: LineKey, f: impl FnOnce(&mut LineBuffer) -> R) -> R {
+    with_buffers(|buffers| {
+        let index = match buffers.iter().position(|buffer| buffer.key == key) {
+            Some(index) => index,
+            None => {
+                buffers.push(LineBuffer {
+                    key,
+                    line: String::new(),
+                    write: None,
+                });
+                buffers.len() - 1
+            }
+        };
+        let result = f(&mut buffers[index]);
+        if buffers[index].line.is_empty() {
+            let _ = buffers.swap_remove(index);
+        }
+        result
+    })
+}
+
+/// Writes the pending partial line of the writer instance buffer and removes the buffer.
+fn release_instance_buffer(key: LineKey) {
+    if let LineKey::Shared(_) = key {
+        return;
+    }
+    let take_pending = |buffers: &mut Vec<LineBuffer>| {
+        let index = buffers.iter().position(|buffer| buffer.key == key)?;
+        let buffer = buffers.swap_remove(index);
+        Some((buffer.line, buffer.write?))
+    };
+    #[cfg(feature = "std")]
+    let pending = BUFFERS
+        .try_with(|buffers| take_pending(&mut buffers.borrow_mut()))
+        .ok()
+        .flatten();
+    #[cfg(not(feature = "std"))]
+    let pending = take_pending(&mut BUFFERS.lock());
+    if let Some((line, mut write)) = pending {
+        write(&line);
+    }
+}
+
+/// Writes all pending partial lines of [`LineBufWriter`] writers
+/// that are buffered for the current thread.
+///
+/// Write errors are ignored, because this function is supposed to be called from panic hooks.
+/// It is called by the panic hooks defined by this crate before writing the panic message.
+pub fn flush_lines() {
+    let pending: Vec<_> = with_buffers(|buffers| {
+        buffers
+            .drain(..)
+            .filter_map(|buffer| Some((buffer.line, buffer.write?)))
+            .collect()
+    });
+    for (line, mut write) in pending {
+        if !line.is_empty() {
+            write(&line);
+        }
+    }
+}
+
+impl<F1> LineBufWriter<F1, ()>
+where
+    F1: WriteStr,
+{
+    /// Creates a new `LineBufWriter` from an object that implements [`WriteStr`].
+    pub fn new(write: F1) -> Self {
+        Self(LineKey::instance(), write, ())
+    }
+
+    /// Creates a new `LineBufWriter` with a [`WriteStr`] wrapper
+    /// deduced with [`IntoWriteFn`] by the closure signature and constructed from it.
+    pub fn from_closure<F, Ts>(closure: F) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+    {
+        Self(LineKey::instance(), closure.into_write_fn(), ())
+    }
+}
+
+impl<F1, F2> LineBufWriter<F1, F2>
+where
+    F1: WriteStr,
+    F2: Flush,
+{
+    /// Creates a new `LineBufWriter` from an object that implements [`WriteStr`]
+    /// and object that implements [`Flush`].
+    pub fn with_flush(write: F1, flush: F2) -> Self {
+        Self(LineKey::instance(), write, flush)
+    }
+
+    /// Creates a new `LineBufWriter` with [`WriteStr`] and [`Flush`] wrappers
+    /// deduced with [`IntoWriteFn`] and [`IntoFlushFn`] by the closure signatures
+    /// and constructed from them.
+    pub fn from_closures<F, Ts, G>(write_closure: F, flush_closure: G) -> Self
+    where
+        F: IntoWriteFn<Ts, WriteFn = F1>,
+        G: IntoFlushFn<FlushFn = F2>,
+    {
+        Self(
+            LineKey::instance(),
+            write_closure.into_write_fn(),
+            flush_closure.into_flush_fn(),
+        )
+    }
+}
+
+impl<F1, F2> LineBufWriter<F1, F2> {
+    /// Returns the writer that uses the buffer with the specified key.
+    ///
+    /// Writers created without a key use a buffer of their own.
+    pub fn with_key(mut self, key: &'static str) -> Self {
+        release_instance_buffer(self.0);
+        self.0 = LineKey::Shared(key);
+        self
+    }
+}
+
+impl<F1, F2> Clone for LineBufWriter<F1, F2>
+where
+    F1: Clone,
+    F2: Clone,
+{
+    /// Returns a copy of the writer.
+    ///
+    /// A copy of the writer created without a key uses a new buffer of its own.
+    fn clone(&self) -> Self {
+        let key = match self.0 {
+            LineKey::Shared(key) => LineKey::Shared(key),
+            LineKey::Instance(_) => LineKey::instance(),
+        };
+        Self(key, self.1.clone(), self.2.clone())
+    }
+}
+
+impl<F1, F2> Drop for LineBufWriter<F1, F2> {
+    fn drop(&mut self) {
+        release_instance_buffer(self.0);
+    }
+}
+
+impl<F1, F2> LineBufWriter<F1, F2>
+where
+    Self: WriteStr,
+{
+    /// Writes a formatted string into this writer.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteStr>::Output {
+        let buf = match args.as_str() {
+            Some(buf) => Cow::Borrowed(buf),
+            None => Cow::Owned(alloc::fmt::format(args)),
+        };
+        self.write_str(&buf)
+    }
+}
+
+impl<F1, F2> LineBufWriter<F1, F2>
+where
+    Self: Flush,
+{
+    /// Writes the pending partial line and flushes this writer using the wrapped flush function.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<F1, F2> WriteFmt for LineBufWriter<F1, F2>
+where
+    Self: WriteStr,
+{
+    type Output = <Self as WriteStr>::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        LineBufWriter::write_fmt(self, args)
+    }
+}
+
+impl<F1, F2> WriteStr for LineBufWriter<F1, F2>
+where
+    F1: WriteStr + Clone + Send + 'static,
+    F1::Output: ExpectConcatWriteResult,
+{
+    type Output = Result<(), NeverError>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        let lines = with_buffer(self.0, |buffer| {
+            let lines = match buf.rfind('\n') {
+                Some(index) => {
+                    let mut lines = take(&mut buffer.line);
+                    lines.push_str(&buf[..index]);
+                    buffer.line.push_str(&buf[index + 1..]);
+                    Some(lines)
+                }
+                None => {
+                    buffer.line.push_str(buf);
+                    None
+                }
+            };
+            if !buffer.line.is_empty() && buffer.write.is_none() {
+                let mut write = self.1.clone();
+                buffer.write = Some(Box::new(move |line: &str| {
+                    let _ = write.write_str(line);
+                }));
+            }
+            lines
+        });
+        if let Some(lines) = lines {
+            for line in lines.split('\n') {
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                let _ = self.1.write_str(line).expect_concat_write_result();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F1, F2> Flush for LineBufWriter<F1, F2>
+where
+    F1: WriteStr,
+    F1::Output: ExpectConcatWriteResult,
+    F2: Flush,
+    F2::Output: ExpectConcatFlushResult,
+{
+    type Output = Result<(), NeverError>;
+
+    fn flush(&mut self) -> Self::Output {
+        let line = with_buffer(self.0, |buffer| take(&mut buffer.line));
+        if !line.is_empty() {
+            let _ = self.1.write_str(&line).expect_concat_write_result();
+        }
+        self.2.flush().expect_concat_flush_result()
+    }
+}
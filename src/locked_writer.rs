@@ -0,0 +1,142 @@
+use core::fmt::Arguments;
+
+use crate::{Flush, WriteFmt, WriterLock};
+
+/// A writer decorator that holds a [`WriterLock`] while writing into the wrapped writer,
+/// so output of different threads does not interleave.
+///
+/// The lock is held during the whole [`write_fmt`] call, so writers like [`FmtWriter`]
+/// that call the write function several times for a single message
+/// write the whole message before other threads can write theirs.
+///
+/// The `locked, args...` and `locked(lock), args...` modes of [`define_writer`]
+/// and [`define_try_writer`] wrap the writer into `LockedWriter`
+/// with the global [`WRITER_LOCK`] or the specified lock.
+/// The `dbg`-like macros defined with these modes hold the lock
+/// during the whole macro invocation, so all values are written together.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Mutex;
+/// use std::thread;
+///
+/// static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// fn write(value: &str) {
+///     let mut lines = LINES.lock().unwrap();
+///     match lines.last_mut() {
+///         Some(line) if !line.ends_with('\n') => *line += value,
+///         _ => lines.push(value.to_owned()),
+///     }
+/// }
+///
+/// custom_print::define_macro!(cprintln, locked, fmt, crate::write);
+///
+/// fn main() {
+///     let threads: Vec<_> = (0..4)
+///         .map(|index| thread::spawn(move || cprintln!("{} {} {}", index, index, index)))
+///         .collect();
+///     threads.into_iter().for_each(|thread| thread.join().unwrap());
+///
+///     let mut lines = LINES.lock().unwrap().clone();
+///     lines.sort();
+///     assert_eq!(lines, ["0 0 0\n", "1 1 1\n", "2 2 2\n", "3 3 3\n"]);
+/// }
+/// ```
+///
+/// [`WriterLock`]: trait.WriterLock.html
+/// [`write_fmt`]: #method.write_fmt
+/// [`FmtWriter`]: struct.FmtWriter.html
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`WRITER_LOCK`]: static.WRITER_LOCK.html
+#[derive(Clone, Copy, Debug)]
+pub struct LockedWriter<'a, L: ?Sized, W>(&'a L, W);
+
+impl<'a, L, W> LockedWriter<'a, L, W>
+where
+    L: WriterLock + ?Sized,
+{
+    /// Creates a new `LockedWriter` with the specified lock and wrapped writer.
+    pub fn new(lock: &'a L, writer: W) -> Self {
+        Self(lock, writer)
+    }
+}
+
+impl<L: ?Sized, W> LockedWriter<'_, L, W> {
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.1
+    }
+}
+
+impl<L: ?Sized, W> LockedWriter<'_, L, W>
+where
+    Self: WriteFmt,
+{
+    /// Writes a formatted string into the wrapped writer while holding the lock.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteFmt>::Output {
+        WriteFmt::write_fmt(self, args)
+    }
+}
+
+impl<L: ?Sized, W> LockedWriter<'_, L, W>
+where
+    Self: Flush,
+{
+    /// Flushes the wrapped writer while holding the lock.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<L, W> WriteFmt for LockedWriter<'_, L, W>
+where
+    L: WriterLock + ?Sized,
+    W: WriteFmt,
+{
+    type Output = W::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        let _guard = Guard::new(self.0);
+        self.1.write_fmt(args)
+    }
+}
+
+impl<L, W> Flush for LockedWriter<'_, L, W>
+where
+    L: WriterLock + ?Sized,
+    W: Flush,
+{
+    type Output = W::Output;
+
+    fn flush(&mut self) -> Self::Output {
+        let _guard = Guard::new(self.0);
+        self.1.flush()
+    }
+}
+
+/// Releases the lock when dropped, even if the wrapped writer panics.
+struct Guard<'a, L: WriterLock + ?Sized>(&'a L);
+
+impl<'a, L: WriterLock + ?Sized> Guard<'a, L> {
+    fn new(lock: &'a L) -> Self {
+        lock.lock();
+        Self(lock)
+    }
+}
+
+impl<L: WriterLock + ?Sized> Drop for Guard<'_, L> {
+    fn drop(&mut self) {
+        // The lock was acquired in `new` and is released only once.
+        unsafe { self.0.unlock() };
+    }
+}
@@ -0,0 +1,70 @@
+use alloc::ffi::CString;
+use core::ffi::{c_char, CStr};
+
+use crate::{
+    IntoTryWriteFn, IntoWriteFn, LossyWriteCCharPtrFn, LossyWriteCStrFn, LossyWriteCStringFn,
+    NulPolicy,
+};
+
+/// A write function wrapper for functions that take [`&CStr`], [`CString`] or [`*const c_char`]
+/// that handles interior nul bytes with the specified [`NulPolicy`] instead of
+/// panicking or returning [`NulError`].
+///
+/// Note that the write function is called with the converted C strings,
+/// so a written bytes count returned by it may not match the message length.
+///
+/// It can be used with both [`IntoWriteFn`] and [`IntoTryWriteFn`] traits,
+/// and can be specified with the `lossy:` or `lossy(policy):` prefix in [`define_writer_expr`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::ffi::CStr;
+/// use custom_print::{LossyCStr, NulPolicy};
+///
+/// let mut lines = Vec::new();
+/// let mut write = |value: &CStr| lines.push(value.to_str().unwrap().to_owned());
+/// custom_print::define_macro!(cprintln, fmt, LossyCStr(&mut write, NulPolicy::ESCAPE));
+///
+/// cprintln!("first\0second");
+/// assert_eq!(lines, ["first\\0second\n"]);
+/// ```
+///
+/// [`&CStr`]: https://doc.rust-lang.org/std/ffi/struct.CStr.html
+/// [`CString`]: https://doc.rust-lang.org/std/ffi/struct.CString.html
+/// [`*const c_char`]: https://doc.rust-lang.org/std/os/raw/type.c_char.html
+/// [`NulError`]: https://doc.rust-lang.org/std/ffi/struct.NulError.html
+/// [`NulPolicy`]: enum.NulPolicy.html
+/// [`IntoWriteFn`]: trait.IntoWriteFn.html
+/// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LossyCStr<F>(pub F, pub NulPolicy);
+
+macro_rules! def {
+    ( $func:ident, ($($ty:ty),*) ) => {
+        impl<F, R> IntoWriteFn<($($ty,)*)> for LossyCStr<F>
+        where
+            F: FnMut($($ty),*) -> R,
+        {
+            type WriteFn = $func<F, R>;
+            fn into_write_fn(self) -> Self::WriteFn {
+                $func::new(self.0, self.1)
+            }
+        }
+
+        impl<F, R> IntoTryWriteFn<($($ty,)*)> for LossyCStr<F>
+        where
+            F: FnMut($($ty),*) -> R,
+        {
+            type TryWriteFn = $func<F, R>;
+            fn into_try_write_fn(self) -> Self::TryWriteFn {
+                $func::new(self.0, self.1)
+            }
+        }
+    };
+}
+
+def!(LossyWriteCStrFn, (&CStr));
+def!(LossyWriteCStringFn, (CString));
+def!(LossyWriteCCharPtrFn, (*const c_char));
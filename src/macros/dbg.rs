@@ -26,11 +26,11 @@
 /// [`define_try_dbg`]: macro.define_try_dbg.html
 #[macro_export]
 macro_rules! define_dbg {
-    ( $( #[$meta:meta] )* $name:ident, $($args:tt)* ) => {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $($args:tt)* ) => {
         $crate::define_dbglike!(
             $( #[$meta] )*
             $name,
-            ::core::writeln,
+            ::core::writeln $([ $($affix)* ])?,
             expect,
             ":#?",
             $($args)*
@@ -67,14 +67,52 @@ macro_rules! define_dbg {
 /// [`define_dbg`]: macro.define_dbg.html
 #[macro_export]
 macro_rules! define_try_dbg {
-    ( $( #[$meta:meta] )* $name:ident, $($args:tt)* ) => {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $($args:tt)* ) => {
         $crate::define_dbglike!(
             $( #[$meta] )*
             $name,
-            ::core::writeln,
+            ::core::writeln $([ $($affix)* ])?,
             try,
             ":#?",
             $($args)*
         );
     };
 }
+
+/// Defines `quiet_dbg` macro that prints and returns the value
+/// of a given expression for quick and dirty debugging
+/// and silently ignores write errors.
+///
+/// The generated macro returns the values unchanged even if writing fails.
+///
+/// The first argument specifies the generated macro name.
+/// The writer itself is specified by the rest arguments with the [`define_try_writer`] macros.
+///
+/// See also [`define_dbg`] and [`define_try_dbg`] macros.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::fmt;
+///
+/// custom_print::define_quiet_dbg!(quiet_dbg, fmt, |_: &str| Err(fmt::Error));
+///
+/// assert_eq!(quiet_dbg!("value"), "value");
+/// ```
+///
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`define_dbg`]: macro.define_dbg.html
+/// [`define_try_dbg`]: macro.define_try_dbg.html
+#[macro_export]
+macro_rules! define_quiet_dbg {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $($args:tt)* ) => {
+        $crate::define_dbglike!(
+            $( #[$meta] )*
+            $name,
+            ::core::writeln $([ $($affix)* ])?,
+            ignore,
+            ":#?",
+            $($args)*
+        );
+    };
+}
@@ -8,6 +8,7 @@
 ///
 /// If the `try` policy is used, it propagates write error and
 /// returns values wrapper into `Result`.
+/// If the `ignore` policy is used, it discards write error and returns values unchanged.
 ///
 /// The writer itself is specified by the rest arguments with the [`define_writer`] macros.
 ///
@@ -27,10 +28,19 @@
 /// [`define_writer`]: macro.define_writer.html
 #[macro_export]
 macro_rules! define_dbglike {
+    ( $( $args:tt )* ) => {
+        $crate::_define_dbglike!(Dbg, $( $args )*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_dbglike {
     (
+        $kind:ident,
         $( #[$meta:meta] )*
         $name:ident,
-        $macro:path,
+        $macro:path $([ $($affix:tt)* ])?,
         expect,
         $format:literal,
         $($args:tt)*
@@ -39,16 +49,19 @@ macro_rules! define_dbglike {
             ($),
             $( #[$meta] )*,
             $name,
-            $macro,
+            $macro $([ $($affix)* ])?,
             expect,
             $format,
-            $crate::define_writer!($($args)*)
+            $kind,
+            [ $crate::_lock_scope!($($args)*) ],
+            $crate::_define_unlocked_writer!(define_writer, [] $($args)*)
         );
     };
     (
+        $kind:ident,
         $( #[$meta:meta] )*
         $name:ident,
-        $macro:path,
+        $macro:path $([ $($affix:tt)* ])?,
         try,
         $format:literal,
         $($args:tt)*
@@ -57,10 +70,33 @@ macro_rules! define_dbglike {
             ($),
             $( #[$meta] )*,
             $name,
-            $macro,
+            $macro $([ $($affix)* ])?,
             try,
             $format,
-            $crate::define_try_writer!($($args)*)
+            $kind,
+            [ $crate::_lock_scope!($($args)*) ],
+            $crate::_define_unlocked_writer!(define_try_writer, [] $($args)*)
+        );
+    };
+    (
+        $kind:ident,
+        $( #[$meta:meta] )*
+        $name:ident,
+        $macro:path $([ $($affix:tt)* ])?,
+        ignore,
+        $format:literal,
+        $($args:tt)*
+    ) => {
+        $crate::_define_dbglike_impl!(
+            ($),
+            $( #[$meta] )*,
+            $name,
+            $macro $([ $($affix)* ])?,
+            ignore,
+            $format,
+            $kind,
+            [ $crate::_lock_scope!($($args)*) ],
+            $crate::_define_unlocked_writer!(define_try_writer, [] $($args)*)
         );
     };
 }
@@ -72,9 +108,11 @@ macro_rules! _define_dbglike_impl {
         ($d:tt),
         $( #[$meta:meta] )*,
         $name:ident,
-        $macro:path,
+        $macro:path $([ $($affix:tt)* ])?,
         $handler:tt,
         $format:literal,
+        $kind:ident,
+        [ $( $lock:tt )* ],
         $( $writer:tt )*
     ) => {
         $( #[$meta] )*
@@ -83,12 +121,16 @@ macro_rules! _define_dbglike_impl {
             // Dummy comment below is used to avoid rustfmt formatting bug.
             // Issue: https://github.com/rust-lang/rustfmt/issues/4609
             /* ================================================================================== */
-            () => {
-                $crate::dbgwrite!($macro, $( $writer )*, $handler, $format)
-            };
-            ($d ($d args:tt)+) => {
-                $crate::dbgwrite!($macro, $( $writer )*, $handler, $format, $d ($d args)+)
-            };
+            () => {{
+                let _record = $crate::_record_scope!($crate::RecordKind::$kind);
+                let _lock = $( $lock )*;
+                $crate::dbgwrite!($macro $([ $($affix)* ])?, $( $writer )*, $handler, $format)
+            }};
+            ($d ($d args:tt)+) => {{
+                let _record = $crate::_record_scope!($crate::RecordKind::$kind);
+                let _lock = $( $lock )*;
+                $crate::dbgwrite!($macro $([ $($affix)* ])?, $( $writer )*, $handler, $format, $d ($d args)+)
+            }};
         }
     };
 }
@@ -7,6 +7,7 @@
 ///
 /// If the `try` policy is used, it propagates write error and
 /// returns values wrapper into `Result`.
+/// If the `ignore` policy is used, it discards write error and returns values unchanged.
 ///
 /// # Panics
 ///
@@ -24,32 +25,37 @@
 /// assert!(string.contains("\"first\""));
 /// assert_eq!(custom_print::dbgwrite!(writeln, &mut string, try, ":?", "second"), Ok(("second")));
 /// assert!(string.contains("\"second\""));
+/// assert_eq!(custom_print::dbgwrite!(writeln, &mut string, ignore, ":?", "third"), ("third"));
+/// assert!(string.contains("\"third\""));
 /// ```
 #[macro_export]
 macro_rules! dbgwrite {
-    ( $macro:path, $writer:expr, expect, $format:literal $(, $($args:tt)+)? ) => {
-        $crate::_dbgwrite_impl!( $macro, $writer, $format $(, $($args)+)? )
+    ( $macro:path $([ $($affix:tt)* ])?, $writer:expr, expect, $format:literal $(, $($args:tt)+)? ) => {
+        $crate::_dbgwrite_impl!( expect, [$($($affix)*)?], $macro, $writer, $format $(, $($args)+)? )
     };
-    ( $macro:path, $writer:expr, try, $format:literal $(, $($args:tt)+)? ) => {
-        $crate::_try_dbgwrite_impl!( $macro, $writer, $format $(, $($args)+)? )
+    ( $macro:path $([ $($affix:tt)* ])?, $writer:expr, ignore, $format:literal $(, $($args:tt)+)? ) => {
+        $crate::_dbgwrite_impl!( ignore, [$($($affix)*)?], $macro, $writer, $format $(, $($args)+)? )
+    };
+    ( $macro:path $([ $($affix:tt)* ])?, $writer:expr, try, $format:literal $(, $($args:tt)+)? ) => {
+        $crate::_try_dbgwrite_impl!( [$($($affix)*)?], $macro, $writer, $format $(, $($args)+)? )
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! _dbgwrite_impl {
-    ( $macro:path, $writer:expr, $format:literal ) => {
+    ( $handler:tt, $affix:tt, $macro:path, $writer:expr, $format:literal ) => {
         $crate::write!(
-            $macro, $writer, expect, "[{}:{}]", ::core::file!(), ::core::line!()
+            $macro $affix, $writer, $handler, "[{}:{}]", ::core::file!(), ::core::line!()
         );
     };
-    ( $macro:path, $writer:expr, $format:literal, $val:expr $(,)? ) => {
+    ( $handler:tt, $affix:tt, $macro:path, $writer:expr, $format:literal, $val:expr $(,)? ) => {
         // Use of `match` here is intentional because it affects the lifetimes
         // of temporaries - https://stackoverflow.com/a/48732525/1063961
         match $val {
             tmp => {
                 $crate::write!(
-                    $macro, $writer, expect,
+                    $macro $affix, $writer, $handler,
                     ::core::concat!("[{}:{}] {} = {", $format, "}"),
                     ::core::file!(), ::core::line!(), ::core::stringify!($val), &tmp
                 );
@@ -57,12 +63,12 @@ macro_rules! _dbgwrite_impl {
             }
         }
     };
-    ( $macro:path, $writer:expr, $format:literal, $($val:expr),+ $(,)? ) => {
+    ( $handler:tt, $affix:tt, $macro:path, $writer:expr, $format:literal, $($val:expr),+ $(,)? ) => {
         ($(
             match $val {
                 tmp => {
                     $crate::write!(
-                        $macro, $writer, expect,
+                        $macro $affix, $writer, $handler,
                         ::core::concat!("[{}:{}] {} = {", $format, "}"),
                         ::core::file!(), ::core::line!(), ::core::stringify!($val), &tmp
                     );
@@ -76,31 +82,31 @@ macro_rules! _dbgwrite_impl {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! _try_dbgwrite_impl {
-    ( $macro:path, $writer:expr, $format:literal ) => {
+    ( $affix:tt, $macro:path, $writer:expr, $format:literal ) => {
         $crate::write!(
-            $macro, $writer, try, "[{}:{}]", ::core::file!(), ::core::line!()
+            $macro $affix, $writer, try, "[{}:{}]", ::core::file!(), ::core::line!()
         ).map(|_| ());
     };
-    ( $macro:path, $writer:expr, $format:literal, $val:expr $(,)? ) => {
+    ( $affix:tt, $macro:path, $writer:expr, $format:literal, $val:expr $(,)? ) => {
         // Use of `match` here is intentional because it affects the lifetimes
         // of temporaries - https://stackoverflow.com/a/48732525/1063961
         match $val {
             tmp => {
                 $crate::write!(
-                    $macro, $writer, try,
+                    $macro $affix, $writer, try,
                     ::core::concat!("[{}:{}] {} = {", $format, "}"),
                     ::core::file!(), ::core::line!(), ::core::stringify!($val), &tmp
                 ).map(|_| tmp)
             }
         }
     };
-    ( $macro:path, $writer:expr, $format:literal, $($val:expr),+ $(,)? ) => {
+    ( $affix:tt, $macro:path, $writer:expr, $format:literal, $($val:expr),+ $(,)? ) => {
         (|| {
             Ok(($(
                 match $val {
                     tmp => {
                         match $crate::write!(
-                            $macro, $writer, try,
+                            $macro $affix, $writer, try,
                             ::core::concat!("[{}:{}] {} = {", $format, "}"),
                             ::core::file!(), ::core::line!(), ::core::stringify!($val), &tmp
                         ) {
@@ -7,10 +7,12 @@
 ///
 /// Depending on the specified templates, the macro uses
 /// [`define_print`], [`define_println`], [`define_dbg`], [`define_flush`],
-/// [`define_try_print`], [`define_try_println`], [`define_try_dbg`] or [`define_try_flush`]
-/// for each generated macro.
+/// [`define_try_print`], [`define_try_println`], [`define_try_dbg`], [`define_try_flush`],
+/// [`define_quiet_print`], [`define_quiet_println`], [`define_quiet_dbg`], [`define_quiet_flush`],
+/// [`define_log`] or [`define_try_log`] for each generated macro.
 ///
 /// If you need to define a single `print`-like or `dbg`-like macro, use [`define_macro`].
+/// Template options like `println(prefix = "[gpu] ")` are passed to [`define_macro`] as is.
 ///
 /// # Macro ambiguity
 ///
@@ -94,8 +96,8 @@
 ///
 /// An example with a function that takes a [`c_char`] pointer and overriding
 /// [`std::print`] and [`std::println`] functions:
-#[cfg_attr(feature = "std", doc = "```rust")]
-#[cfg_attr(not(feature = "std"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
 /// fn write(_value: *const std::os::raw::c_char) { /* ... */ }
 ///
 /// custom_print::define_macros!({ cprint, cprintln }, concat, crate::write);
@@ -156,16 +158,27 @@
 /// [`define_try_println`]: macro.define_try_println.html
 /// [`define_try_dbg`]: macro.define_try_dbg.html
 /// [`define_try_flush`]: macro.define_try_flush.html
+/// [`define_quiet_print`]: macro.define_quiet_print.html
+/// [`define_quiet_println`]: macro.define_quiet_println.html
+/// [`define_quiet_dbg`]: macro.define_quiet_dbg.html
+/// [`define_quiet_flush`]: macro.define_quiet_flush.html
+/// [`define_log`]: macro.define_log.html
+/// [`define_try_log`]: macro.define_try_log.html
 #[macro_export]
 macro_rules! define_macros {
     (
         $( #[$meta1:meta] )*
-        { $( $( #[$meta2:meta] )* $template:ident $(as $name:ident)? ),* $(,)? },
+        {
+            $(
+                $( #[$meta2:meta] )*
+                $template:ident $( ( $( $opts:tt )* ) )? $(as $name:ident)?
+            ),* $(,)?
+        },
         $( $args:tt )*
     ) => {
         $crate::_define_macros_impl!(
             $( #[$meta1] )*
-            { $( $( #[$meta2] )* $template $( as $name )? ),* },
+            { $( $( #[$meta2] )* $template $( ( $( $opts )* ) )? $( as $name )? ),* },
             $( $args )*
         );
     };
@@ -176,13 +189,17 @@ macro_rules! define_macros {
 macro_rules! _define_macros_impl {
     (
         $( #[$meta1:meta] )*
-        { $( #[$meta2:meta] )* $template:ident $(as $name:ident)? $(, $($rest:tt)* )? },
+        {
+            $( #[$meta2:meta] )*
+            $template:ident $( ( $( $opts:tt )* ) )? $(as $name:ident)?
+            $(, $($rest:tt)* )?
+        },
         $( $args:tt )*
     ) => {
         $crate::define_macro!(
             $( #[$meta1] )*
             $( #[$meta2] )*
-            $template $(as $name)?, $($args)*
+            $template $( ( $( $opts )* ) )? $(as $name)?, $($args)*
         );
         $crate::_define_macros_impl!(
             $( #[$meta1] )*
@@ -200,17 +217,45 @@ macro_rules! _define_macros_impl {
 ///
 /// Depending on the specified template, the macro uses
 /// [`define_print`], [`define_println`], [`define_dbg`], [`define_flush`],
-/// [`define_try_print`], [`define_try_println`], [`define_try_dbg`] or [`define_try_flush`].
+/// [`define_try_print`], [`define_try_println`], [`define_try_dbg`], [`define_try_flush`],
+/// [`define_quiet_print`], [`define_quiet_println`], [`define_quiet_dbg`], [`define_quiet_flush`],
+/// [`define_log`] or [`define_try_log`].
 ///
 /// If you need to define multiple `print`-like and `dbg`-like macros, use [`define_macros`].
 ///
 /// # Naming
 ///
 /// The macros with the `try_` prefix are producing fallible write expressions.
+/// The macros with the `quiet_` prefix are silently ignoring write errors.
 /// The macros with the `e` prefix are proposed to be used with `stderr`-like writers.
 /// The macros with the `c` prefix are proposed to be used
 /// instead of the standard macros
 /// or to shadow the standard macros in the following lines of code.
+/// The `error`, `warn`, `info`, `debug` and `trace` macros write a line
+/// only if their [`Level`] is enabled by [`set_max_level`].
+///
+/// # Options
+///
+/// The `print`-like, `dbg`-like and log templates accept options in parentheses
+/// after the template name, like `println(prefix = "[gpu] ") as gpu_println`:
+/// - `prefix = "..."` is a string literal inserted at the start of the format string,
+/// - `suffix = "..."` is a string literal inserted at the end of the format string,
+///   before the newline if the macro writes one.
+///
+/// The options are concatenated with the format string at compile time,
+/// so they have no runtime cost and work with any writer, including `fmt` writer without `alloc`.
+/// Braces in the options are part of the format string and should be escaped as `{{` and `}}`.
+/// Note that for `dbg`-like macros the prefix is inserted only before the first line
+/// of the value, use the `prefix(...)` mode of [`define_writer`] to prefix every line.
+///
+/// ```rust
+/// use core::fmt::Write;
+/// let mut string = String::new();
+/// custom_print::define_macro!(println(prefix = "[gpu] ", suffix = ";") as gpu_println, &mut string);
+///
+/// gpu_println!("ready");
+/// assert_eq!(string, "[gpu] ready;\n");
+/// ```
 ///
 /// # Macro ambiguity
 ///
@@ -286,8 +331,8 @@ macro_rules! _define_macros_impl {
 ///
 /// An example with a function that takes a [`c_char`] pointer and overriding
 /// [`std::println`] function:
-#[cfg_attr(feature = "std", doc = "```rust")]
-#[cfg_attr(not(feature = "std"), doc = "```rust,compile_fail")]
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
 /// fn write(_value: *const std::os::raw::c_char) { /* ... */ }
 ///
 /// custom_print::define_macro!(cprintln, concat, crate::write);
@@ -346,65 +391,133 @@ macro_rules! _define_macros_impl {
 /// [`define_try_println`]: macro.define_try_println.html
 /// [`define_try_dbg`]: macro.define_try_dbg.html
 /// [`define_try_flush`]: macro.define_try_flush.html
+/// [`define_quiet_print`]: macro.define_quiet_print.html
+/// [`define_quiet_println`]: macro.define_quiet_println.html
+/// [`define_quiet_dbg`]: macro.define_quiet_dbg.html
+/// [`define_quiet_flush`]: macro.define_quiet_flush.html
+/// [`define_log`]: macro.define_log.html
+/// [`define_try_log`]: macro.define_try_log.html
+/// [`Level`]: enum.Level.html
+/// [`set_max_level`]: fn.set_max_level.html
 #[macro_export]
 macro_rules! define_macro {
-    ( $( #[$meta:meta] )* print       as $name:ident, $( $args:tt )* ) => {
-        $crate::define_print!      ( $( #[$meta] )* $name, $( $args )* )
+    (
+        $( #[$meta:meta] )*
+        $template:ident ( $( $opts:tt )* ) $(as $name:ident)?, $( $args:tt )*
+    ) => {
+        $crate::_define_macro_options!(
+            [ $( #[$meta] )* $template $(as $name)? ] [""] [""] ( $( $opts )* ) $( $args )*
+        );
+    };
+    ( $( #[$meta:meta] )* print       as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_print!      ( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* println     as $name:ident, $( $args:tt )* ) => {
-        $crate::define_println!    ( $( #[$meta] )* $name, $( $args )* )
+    ( $( #[$meta:meta] )* println     as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_println!    ( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* dbg         as $name:ident, $( $args:tt )* ) => {
-        $crate::define_dbg!        ( $( #[$meta] )* $name, $( $args )* )
+    ( $( #[$meta:meta] )* dbg         as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_dbg!        ( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
     };
     ( $( #[$meta:meta] )* flush       as $name:ident, $( $args:tt )* ) => {
-        $crate::define_flush!      ( $( #[$meta] )* $name, $( $args )* )
+        $crate::define_flush!      ( $( #[$meta] )* $name, $( $args )* );
     };
-    ( $( #[$meta:meta] )* try_print   as $name:ident, $( $args:tt )* ) => {
-        $crate::define_try_print!  ( $( #[$meta] )* $name, $( $args )* )
+    ( $( #[$meta:meta] )* try_print   as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_print!  ( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* try_println as $name:ident, $( $args:tt )* ) => {
-        $crate::define_try_println!( $( #[$meta] )* $name, $( $args )* )
+    ( $( #[$meta:meta] )* try_println as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_println!( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* try_dbg     as $name:ident, $( $args:tt )* ) => {
-        $crate::define_try_dbg!    ( $( #[$meta] )* $name, $( $args )* )
+    ( $( #[$meta:meta] )* try_dbg     as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_dbg!    ( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
     };
     ( $( #[$meta:meta] )* try_flush   as $name:ident, $( $args:tt )* ) => {
-        $crate::define_try_flush!  ( $( #[$meta] )* $name, $( $args )* )
+        $crate::define_try_flush!  ( $( #[$meta] )* $name, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* quiet_print as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_quiet_print!( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* quiet_println as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_quiet_println!( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* quiet_dbg as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_quiet_dbg!( $( #[$meta] )* $name $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* quiet_flush as $name:ident, $( $args:tt )* ) => {
+        $crate::define_quiet_flush!( $( #[$meta] )* $name, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* error       as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!        ( $( #[$meta] )* $name $([ $($affix)* ])?, Error, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* warn        as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!        ( $( #[$meta] )* $name $([ $($affix)* ])?, Warn, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* info        as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!        ( $( #[$meta] )* $name $([ $($affix)* ])?, Info, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* debug       as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!        ( $( #[$meta] )* $name $([ $($affix)* ])?, Debug, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* trace       as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!        ( $( #[$meta] )* $name $([ $($affix)* ])?, Trace, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_error   as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!    ( $( #[$meta] )* $name $([ $($affix)* ])?, Error, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_warn    as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!    ( $( #[$meta] )* $name $([ $($affix)* ])?, Warn, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_info    as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!    ( $( #[$meta] )* $name $([ $($affix)* ])?, Info, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_debug   as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!    ( $( #[$meta] )* $name $([ $($affix)* ])?, Debug, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_trace   as $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!    ( $( #[$meta] )* $name $([ $($affix)* ])?, Trace, $( $args )* );
     };
 
-    ( $( #[$meta:meta] )* print,        $( $args:tt )* ) => {
-        $crate::define_print!  ( $( #[$meta] )* print,        $( $args )* );
+    ( $( #[$meta:meta] )* print $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_print!  ( $( #[$meta] )* print $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* eprint,       $( $args:tt )* ) => {
-        $crate::define_print!  ( $( #[$meta] )* eprint,       $( $args )* );
+    ( $( #[$meta:meta] )* eprint $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* eprint, ::core::write $([ $($affix)* ])?, expect, $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* cprint,       $( $args:tt )* ) => {
-        $crate::define_print!  ( $( #[$meta] )* cprint,       $( $args )* );
+    ( $( #[$meta:meta] )* cprint $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_print!  ( $( #[$meta] )* cprint $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* ceprint,      $( $args:tt )* ) => {
-        $crate::define_print!  ( $( #[$meta] )* ceprint,      $( $args )* );
+    ( $( #[$meta:meta] )* ceprint $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* ceprint, ::core::write $([ $($affix)* ])?, expect, $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* println,      $( $args:tt )* ) => {
-        $crate::define_println!( $( #[$meta] )* println,      $( $args )* );
+    ( $( #[$meta:meta] )* println $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_println!( $( #[$meta] )* println $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* eprintln,     $( $args:tt )* ) => {
-         $crate::define_println!( $( #[$meta] )* eprintln,     $( $args )* );
+    ( $( #[$meta:meta] )* eprintln $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* eprintln, ::core::writeln $([ $($affix)* ])?, expect, $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* cprintln,     $( $args:tt )* ) => {
-        $crate::define_println!( $( #[$meta] )* cprintln,     $( $args )* );
+    ( $( #[$meta:meta] )* cprintln $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_println!( $( #[$meta] )* cprintln $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* ceprintln,    $( $args:tt )* ) => {
-        $crate::define_println!( $( #[$meta] )* ceprintln,    $( $args )* );
+    ( $( #[$meta:meta] )* ceprintln $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* ceprintln, ::core::writeln $([ $($affix)* ])?, expect, $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* dbg,          $( $args:tt )* ) => {
-        $crate::define_dbg!    ( $( #[$meta] )* dbg,          $( $args )* );
+    ( $( #[$meta:meta] )* dbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_dbg!    ( $( #[$meta] )* dbg $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* edbg,         $( $args:tt )* ) => {
-        $crate::define_dbg!    ( $( #[$meta] )* edbg,         $( $args )* );
+    ( $( #[$meta:meta] )* edbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_dbglike!(
+            Edbg, $( #[$meta] )* edbg, ::core::writeln $([ $($affix)* ])?, expect, ":#?", $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* cdbg,         $( $args:tt )* ) => {
-        $crate::define_dbg!    ( $( #[$meta] )* cdbg,         $( $args )* );
+    ( $( #[$meta:meta] )* cdbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_dbg!    ( $( #[$meta] )* cdbg $([ $($affix)* ])?, $( $args )* );
     };
     ( $( #[$meta:meta] )* flush,        $( $args:tt )* ) => {
         $crate::define_flush!  ( $( #[$meta] )* flush,        $( $args )* );
@@ -412,24 +525,76 @@ macro_rules! define_macro {
     ( $( #[$meta:meta] )* eflush,       $( $args:tt )* ) => {
         $crate::define_flush!  ( $( #[$meta] )* eflush,       $( $args )* );
     };
+    ( $( #[$meta:meta] )* error $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!    ( $( #[$meta] )* error $([ $($affix)* ])?, Error, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* warn $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!    ( $( #[$meta] )* warn $([ $($affix)* ])?, Warn, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* info $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!    ( $( #[$meta] )* info $([ $($affix)* ])?, Info, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* debug $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!    ( $( #[$meta] )* debug $([ $($affix)* ])?, Debug, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* trace $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_log!    ( $( #[$meta] )* trace $([ $($affix)* ])?, Trace, $( $args )* );
+    };
 
-    ( $( #[$meta:meta] )* try_print,    $( $args:tt )* ) => {
-        $crate::define_try_print!  ( $( #[$meta] )* try_print,    $( $args )* );
+    ( $( #[$meta:meta] )* quiet_print $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_quiet_print!( $( #[$meta] )* quiet_print $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* quiet_eprint $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* quiet_eprint, ::core::write $([ $($affix)* ])?, ignore, $( $args )*
+        );
+    };
+    ( $( #[$meta:meta] )* quiet_println $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_quiet_println!( $( #[$meta] )* quiet_println $([ $($affix)* ])?, $( $args )* );
     };
-    ( $( #[$meta:meta] )* try_eprint,   $( $args:tt )* ) => {
-        $crate::define_try_print!  ( $( #[$meta] )* try_eprint,   $( $args )* );
+    ( $( #[$meta:meta] )* quiet_eprintln $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* quiet_eprintln, ::core::writeln $([ $($affix)* ])?, ignore, $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* try_println,  $( $args:tt )* ) => {
-        $crate::define_try_println!( $( #[$meta] )* try_println,  $( $args )* );
+    ( $( #[$meta:meta] )* quiet_dbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_quiet_dbg!( $( #[$meta] )* quiet_dbg $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* quiet_edbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_dbglike!(
+            Edbg, $( #[$meta] )* quiet_edbg, ::core::writeln $([ $($affix)* ])?, ignore, ":#?", $( $args )*
+        );
     };
-    ( $( #[$meta:meta] )* try_eprintln, $( $args:tt )* ) => {
-        $crate::define_try_println!( $( #[$meta] )* try_eprintln, $( $args )* );
+    ( $( #[$meta:meta] )* quiet_flush,  $( $args:tt )* ) => {
+        $crate::define_quiet_flush!( $( #[$meta] )* quiet_flush, $( $args )* );
     };
-    ( $( #[$meta:meta] )* try_dbg,      $( $args:tt )* ) => {
-        $crate::define_try_dbg!    ( $( #[$meta] )* try_dbg,      $( $args )* );
+    ( $( #[$meta:meta] )* quiet_eflush, $( $args:tt )* ) => {
+        $crate::define_quiet_flush!( $( #[$meta] )* quiet_eflush, $( $args )* );
     };
-    ( $( #[$meta:meta] )* try_edbg,     $( $args:tt )* ) => {
-        $crate::define_try_dbg!    ( $( #[$meta] )* try_edbg,     $( $args )* );
+
+    ( $( #[$meta:meta] )* try_print $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_print!  ( $( #[$meta] )* try_print $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_eprint $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* try_eprint, ::core::write $([ $($affix)* ])?, try, $( $args )*
+        );
+    };
+    ( $( #[$meta:meta] )* try_println $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_println!( $( #[$meta] )* try_println $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_eprintln $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_printlike!(
+            Eprint, $( #[$meta] )* try_eprintln, ::core::writeln $([ $($affix)* ])?, try, $( $args )*
+        );
+    };
+    ( $( #[$meta:meta] )* try_dbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_dbg!    ( $( #[$meta] )* try_dbg $([ $($affix)* ])?, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_edbg $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::_define_dbglike!(
+            Edbg, $( #[$meta] )* try_edbg, ::core::writeln $([ $($affix)* ])?, try, ":#?", $( $args )*
+        );
     };
     ( $( #[$meta:meta] )* try_flush,    $( $args:tt )* ) => {
         $crate::define_try_flush!  ( $( #[$meta] )* try_flush,    $( $args )* );
@@ -437,4 +602,43 @@ macro_rules! define_macro {
     ( $( #[$meta:meta] )* try_eflush,   $( $args:tt )* ) => {
         $crate::define_try_flush!  ( $( #[$meta] )* try_eflush,   $( $args )* );
     };
+    ( $( #[$meta:meta] )* try_error $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!( $( #[$meta] )* try_error $([ $($affix)* ])?, Error, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_warn $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!( $( #[$meta] )* try_warn $([ $($affix)* ])?, Warn, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_info $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!( $( #[$meta] )* try_info $([ $($affix)* ])?, Info, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_debug $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!( $( #[$meta] )* try_debug $([ $($affix)* ])?, Debug, $( $args )* );
+    };
+    ( $( #[$meta:meta] )* try_trace $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_try_log!( $( #[$meta] )* try_trace $([ $($affix)* ])?, Trace, $( $args )* );
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_macro_options {
+    (
+        [ $( $head:tt )* ] [ $prefix:expr ] [ $suffix:expr ]
+        ( prefix = $value:expr $(, $( $opts:tt )* )? ) $( $args:tt )*
+    ) => {
+        $crate::_define_macro_options!(
+            [ $( $head )* ] [ $value ] [ $suffix ] ( $( $( $opts )* )? ) $( $args )*
+        );
+    };
+    (
+        [ $( $head:tt )* ] [ $prefix:expr ] [ $suffix:expr ]
+        ( suffix = $value:expr $(, $( $opts:tt )* )? ) $( $args:tt )*
+    ) => {
+        $crate::_define_macro_options!(
+            [ $( $head )* ] [ $prefix ] [ $value ] ( $( $( $opts )* )? ) $( $args )*
+        );
+    };
+    ( [ $( $head:tt )* ] [ $prefix:expr ] [ $suffix:expr ] () $( $args:tt )* ) => {
+        $crate::define_macro!( $( $head )* [ $prefix, $suffix ], $( $args )* );
+    };
 }
@@ -111,3 +111,38 @@ macro_rules! define_try_flush {
         }
     };
 }
+
+/// Defines `quiet_flush` macro that calls `flush` method of the specified writer
+/// and silently ignores flush errors.
+///
+/// See also [`define_flush`] and [`define_try_flush`] macros.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::fmt;
+///
+/// custom_print::define_quiet_flush!(
+///     quiet_flush,
+///     fmt,
+///     |_: &str| Ok::<(), fmt::Error>(()),
+///     flush: || Err::<(), _>(fmt::Error)
+/// );
+///
+/// assert_eq!(quiet_flush!(), ());
+/// ```
+///
+/// [`define_flush`]: macro.define_flush.html
+/// [`define_try_flush`]: macro.define_try_flush.html
+#[macro_export]
+macro_rules! define_quiet_flush {
+    ( $( #[$meta:meta] )* $name:ident, $($args:tt)* ) => {
+        $( #[$meta] )*
+        #[allow(unused_macros)]
+        macro_rules! $name {
+            () => {{
+                let _ = $crate::define_try_writer!($($args)*).flush();
+            }};
+        }
+    };
+}
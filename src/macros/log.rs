@@ -0,0 +1,188 @@
+/// Defines a logging macro like `info` that writes a line with the specified [`Level`]
+/// if the level is enabled by the runtime maximum logging level.
+///
+/// The first argument specifies the generated macro name.
+/// The second argument specifies the [`Level`] variant name: `Error`, `Warn`, `Info`,
+/// `Debug` or `Trace`.
+/// The writer itself is specified by the rest arguments with the [`define_writer`] macros.
+///
+/// If the writer arguments are prefixed with `level,`, like `level, concat, args...`,
+/// then the writer function takes the [`Level`] as the first argument
+/// followed by [`&str`], so the same function can be used for all levels.
+/// Otherwise, the level is only used for filtering,
+/// so level-specific writers can be used for each level.
+///
+/// The generated macro does nothing and does not evaluate its arguments
+/// if the level is disabled with [`set_max_level`].
+///
+/// Use [`define_try_log`] if you need to define a fallible logging macros.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::Level;
+///
+/// let mut lines = Vec::new();
+/// custom_print::define_log!(warn, Warn, level, concat, |level: Level, value: &str| {
+///     lines.push(format!("{}: {}", level, value));
+/// });
+///
+/// assert_eq!(warn!("value"), ());
+/// assert_eq!(lines, ["warn: value\n"]);
+/// ```
+///
+/// [`Level`]: enum.Level.html
+/// [`&str`]: https://doc.rust-lang.org/std/primitive.str.html
+/// [`define_writer`]: macro.define_writer.html
+/// [`set_max_level`]: fn.set_max_level.html
+/// [`define_try_log`]: macro.define_try_log.html
+#[macro_export]
+macro_rules! define_log {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $level:ident, level, $kind:ident, $($args:tt)* ) => {
+        $crate::_define_log_impl!(
+            ($),
+            $( #[$meta] )*,
+            $name,
+            $level $([ $($affix)* ])?,
+            expect,
+            $crate::define_writer!(
+                $kind,
+                $crate::_with_level($crate::Level::$level, $crate::define_writer_expr!($($args)*))
+            )
+        );
+    };
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $level:ident, $($args:tt)* ) => {
+        $crate::_define_log_impl!(
+            ($),
+            $( #[$meta] )*,
+            $name,
+            $level $([ $($affix)* ])?,
+            expect,
+            $crate::define_writer!($($args)*)
+        );
+    };
+}
+
+/// Defines a fallible logging macro like `try_info` that writes a line
+/// with the specified [`Level`] if the level is enabled by the runtime maximum logging level.
+///
+/// The generated macro returns `Ok(())` if the level is disabled,
+/// or the writing result with the discarded value otherwise.
+///
+/// The macro arguments are the same as in [`define_log`].
+///
+/// Use [`define_log`] if you need to define a non-fallible logging macros.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::fmt;
+/// use custom_print::FmtWriteError;
+///
+/// custom_print::define_try_log!(try_error, Error, fmt, |_: &str| Err(fmt::Error));
+///
+/// assert_eq!(try_error!("value"), Err(FmtWriteError::Write(fmt::Error)));
+/// ```
+///
+/// [`Level`]: enum.Level.html
+/// [`define_log`]: macro.define_log.html
+#[macro_export]
+macro_rules! define_try_log {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $level:ident, level, $kind:ident, $($args:tt)* ) => {
+        $crate::_define_log_impl!(
+            ($),
+            $( #[$meta] )*,
+            $name,
+            $level $([ $($affix)* ])?,
+            try,
+            $crate::define_try_writer!(
+                $kind,
+                $crate::_with_level($crate::Level::$level, $crate::define_writer_expr!($($args)*))
+            )
+        );
+    };
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $level:ident, $($args:tt)* ) => {
+        $crate::_define_log_impl!(
+            ($),
+            $( #[$meta] )*,
+            $name,
+            $level $([ $($affix)* ])?,
+            try,
+            $crate::define_try_writer!($($args)*)
+        );
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_log_impl {
+    (
+        ($d:tt),
+        $( #[$meta:meta] )*,
+        $name:ident,
+        $level:ident $([ $($affix:tt)* ])?,
+        expect,
+        $( $writer:tt )*
+    ) => {
+        $( #[$meta] )*
+        #[allow(unused_macros)]
+        macro_rules! $name {
+            // Dummy comment below is used to avoid rustfmt formatting bug.
+            // Issue: https://github.com/rust-lang/rustfmt/issues/4609
+            /* ================================================================================== */
+            () => {
+                if $crate::Level::$level.is_enabled() {
+                    let _record = $crate::_record_scope!(
+                        $crate::RecordKind::Log($crate::Level::$level)
+                    );
+                    let _ = $crate::write!(::core::writeln $([ $($affix)* ])?, $( $writer )*, expect);
+                }
+            };
+            ($d ($d args:tt)+) => {
+                if $crate::Level::$level.is_enabled() {
+                    let _record = $crate::_record_scope!(
+                        $crate::RecordKind::Log($crate::Level::$level)
+                    );
+                    let _ = $crate::write!(::core::writeln $([ $($affix)* ])?, $( $writer )*, expect, $d ($d args)+);
+                }
+            };
+        }
+    };
+    (
+        ($d:tt),
+        $( #[$meta:meta] )*,
+        $name:ident,
+        $level:ident $([ $($affix:tt)* ])?,
+        try,
+        $( $writer:tt )*
+    ) => {
+        $( #[$meta] )*
+        #[allow(unused_macros)]
+        macro_rules! $name {
+            // Dummy comment below is used to avoid rustfmt formatting bug.
+            // Issue: https://github.com/rust-lang/rustfmt/issues/4609
+            /* ================================================================================== */
+            () => {
+                if $crate::Level::$level.is_enabled() {
+                    let _record = $crate::_record_scope!(
+                        $crate::RecordKind::Log($crate::Level::$level)
+                    );
+                    $crate::write!(::core::writeln $([ $($affix)* ])?, $( $writer )*, try).map(|_| ())
+                } else {
+                    ::core::result::Result::Ok(())
+                }
+            };
+            ($d ($d args:tt)+) => {
+                if $crate::Level::$level.is_enabled() {
+                    let _record = $crate::_record_scope!(
+                        $crate::RecordKind::Log($crate::Level::$level)
+                    );
+                    $crate::write!(::core::writeln $([ $($affix)* ])?, $( $writer )*, try, $d ($d args)+).map(|_| ())
+                } else {
+                    ::core::result::Result::Ok(())
+                }
+            };
+        }
+    };
+}
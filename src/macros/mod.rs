@@ -3,9 +3,12 @@ mod dbglike;
 mod dbgwrite;
 mod define;
 mod flush;
+mod log;
 mod panic_hook;
 mod print;
 mod printlike;
+mod record;
+mod testing;
 mod write;
 mod writer;
 mod writer_expr;
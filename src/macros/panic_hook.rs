@@ -1,5 +1,8 @@
 /// Defines `panic_hook` function that can be used as panic hook that uses the specified writer.
 ///
+/// The panic hook writes pending partial lines of [`LineBufWriter`] writers
+/// with [`flush_lines`] before writing the panic message.
+///
 /// The first argument specify function name in the format `fn FUNC_NAME(...)`
 /// and can be omitted to use the default name `panic_hook`.
 ///
@@ -29,16 +32,21 @@
 ///     let message = MESSAGE.lock().unwrap();
 ///
 ///     assert!(message.contains("panicked"));
-///     assert!(message.contains("assertion failed"));
+///     assert!(message.contains("assertion") && message.contains("failed"));
 ///     assert!(message.contains("\"foo\""));
 ///     assert!(message.contains("\"bar\""));
 /// }
 /// ```
+///
+/// [`LineBufWriter`]: struct.LineBufWriter.html
+/// [`flush_lines`]: fn.flush_lines.html
 #[macro_export]
 macro_rules! define_panic_hook {
     ( $(#[$extern_meta:meta])* $vis:vis fn $name:ident(...), $($args:tt)* ) => {
         $(#[$extern_meta])*
+        #[allow(deprecated)]
         $vis fn $name(info: &::std::panic::PanicInfo<'_>) {
+            $crate::flush_lines();
             ::core::writeln!($crate::define_writer!($($args)*), "{}", info)
                 .expect("failed writing panic info");
         }
@@ -53,6 +61,9 @@ macro_rules! define_panic_hook {
 /// The first argument specify function name in the format `fn FUNC_NAME()`
 /// and can be omitted to use the default name `init_panic_hook`.
 ///
+/// Pending partial lines are written before the panic message
+/// the same way as in [`define_panic_hook`].
+///
 #[cfg_attr(feature = "alloc", doc = "```rust")]
 #[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
 /// use once_cell::sync::Lazy;
@@ -77,18 +88,22 @@ macro_rules! define_panic_hook {
 ///     let message = MESSAGE.lock().unwrap();
 ///
 ///     assert!(message.contains("panicked"));
-///     assert!(message.contains("assertion failed"));
+///     assert!(message.contains("assertion") && message.contains("failed"));
 ///     assert!(message.contains("\"foo\""));
 ///     assert!(message.contains("\"bar\""));
 /// }
 /// ```
+///
+/// [`define_panic_hook`]: macro.define_panic_hook.html
 #[macro_export]
 macro_rules! define_init_panic_hook {
     ( $(#[$extern_meta:meta])* $vis:vis fn $name:ident(), $($args:tt)* ) => {
         $(#[$extern_meta])*
+        #[allow(deprecated)]
         $vis fn $name() {
             ::std::panic::set_hook(::std::boxed::Box::new(
                 |info: &::std::panic::PanicInfo<'_>| {
+                    $crate::flush_lines();
                     ::core::writeln!($crate::define_writer!($($args)*), "{}", info)
                         .expect("failed writing panic info");
                 }
@@ -102,6 +117,9 @@ macro_rules! define_init_panic_hook {
 
 /// Sets `panic_hook` that uses the specified writer.
 ///
+/// Pending partial lines are written before the panic message
+/// the same way as in [`define_panic_hook`].
+///
 /// # Examples
 ///
 #[cfg_attr(feature = "alloc", doc = "```rust")]
@@ -127,19 +145,22 @@ macro_rules! define_init_panic_hook {
 ///     let message = MESSAGE.lock().unwrap();
 ///
 ///     assert!(message.contains("panicked"));
-///     assert!(message.contains("assertion failed"));
+///     assert!(message.contains("assertion") && message.contains("failed"));
 ///     assert!(message.contains("\"foo\""));
 ///     assert!(message.contains("\"bar\""));
 /// }
 /// ```
+///
+/// [`define_panic_hook`]: macro.define_panic_hook.html
 #[macro_export]
 macro_rules! init_panic_hook {
-    ( $($args:tt)* ) => {
-        ::std::panic::set_hook(::std::boxed::Box::new(
-            |info: &::std::panic::PanicInfo<'_>| {
-                ::core::writeln!($crate::define_writer!($($args)*), "{}", info)
-                    .expect("failed writing panic info");
-            }
-        ))
-    };
+    ( $($args:tt)* ) => {{
+        #[allow(deprecated)]
+        let panic_hook = |info: &::std::panic::PanicInfo<'_>| {
+            $crate::flush_lines();
+            ::core::writeln!($crate::define_writer!($($args)*), "{}", info)
+                .expect("failed writing panic info");
+        };
+        ::std::panic::set_hook(::std::boxed::Box::new(panic_hook))
+    }};
 }
@@ -20,11 +20,11 @@
 /// [`define_try_print`]: macro.define_try_print.html
 #[macro_export]
 macro_rules! define_print {
-    ( $( #[$meta:meta] )* $name:ident, $( $args:tt )* ) => {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
         $crate::define_printlike!(
             $( #[$meta] )*
             $name,
-            ::core::write,
+            ::core::write $([ $($affix)* ])?,
             expect,
             $($args)*
         );
@@ -53,11 +53,11 @@ macro_rules! define_print {
 /// [`define_try_println`]: macro.define_try_println.html
 #[macro_export]
 macro_rules! define_println {
-    ( $( #[$meta:meta] )* $name:ident, $( $args:tt )* ) => {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
         $crate::define_printlike!(
             $( #[$meta] )*
             $name,
-            ::core::writeln,
+            ::core::writeln $([ $($affix)* ])?,
             expect,
             $($args)*
         );
@@ -86,11 +86,11 @@ macro_rules! define_println {
 /// [`define_try_println`]: macro.define_try_println.html
 #[macro_export]
 macro_rules! define_try_print {
-    ( $( #[$meta:meta] )* $name:ident, $( $args:tt )* ) => {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
         $crate::define_printlike!(
             $( #[$meta] )*
             $name,
-            ::core::write, try,
+            ::core::write $([ $($affix)* ])?, try,
             $($args)*
         );
     };
@@ -119,13 +119,81 @@ macro_rules! define_try_print {
 /// [`define_try_print`]: macro.define_try_print.html
 #[macro_export]
 macro_rules! define_try_println {
-    ( $( #[$meta:meta] )* $name:ident, $( $args:tt )* ) => {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
         $crate::define_printlike!(
             $( #[$meta] )*
             $name,
-            ::core::writeln,
+            ::core::writeln $([ $($affix)* ])?,
             try,
             $($args)*
         );
     };
 }
+
+/// Defines `quiet_print` macro that defines a print macro
+/// that uses the specified writer and silently ignores write errors.
+///
+/// The first argument specifies the generated macro name.
+/// The writer itself is specified by the rest arguments with the [`define_try_writer`] macros.
+///
+/// See also [`define_print`] and [`define_quiet_println`] macros.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::fmt;
+///
+/// custom_print::define_quiet_print!(quiet_print, fmt, |_: &str| Err(fmt::Error));
+///
+/// assert_eq!(quiet_print!("value"), ());
+/// ```
+///
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`define_print`]: macro.define_print.html
+/// [`define_quiet_println`]: macro.define_quiet_println.html
+#[macro_export]
+macro_rules! define_quiet_print {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_printlike!(
+            $( #[$meta] )*
+            $name,
+            ::core::write $([ $($affix)* ])?,
+            ignore,
+            $($args)*
+        );
+    };
+}
+
+/// Defines `quiet_println` macro that defines a println macro
+/// that uses the specified writer and silently ignores write errors.
+///
+/// The first argument specifies the generated macro name.
+/// The writer itself is specified by the rest arguments with the [`define_try_writer`] macros.
+///
+/// See also [`define_println`] and [`define_quiet_print`] macros.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut string = String::new();
+/// custom_print::define_quiet_println!(quiet_println, fmt, |value: &str| string += value);
+///
+/// assert_eq!(quiet_println!("value"), ());
+/// assert_eq!(string, "value\n");
+/// ```
+///
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`define_println`]: macro.define_println.html
+/// [`define_quiet_print`]: macro.define_quiet_print.html
+#[macro_export]
+macro_rules! define_quiet_println {
+    ( $( #[$meta:meta] )* $name:ident $([ $($affix:tt)* ])?, $( $args:tt )* ) => {
+        $crate::define_printlike!(
+            $( #[$meta] )*
+            $name,
+            ::core::writeln $([ $($affix)* ])?,
+            ignore,
+            $($args)*
+        );
+    };
+}
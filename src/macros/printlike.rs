@@ -1,6 +1,10 @@
 /// Defines a `print`-like macro with a given name that uses
 /// specified write macro, error-handling policy and writer.
 ///
+/// The error-handling policy is one of `expect`, `try` or `ignore`.
+/// The `try` and `ignore` policies use a writer defined with [`define_try_writer`],
+/// so that the `ignore` policy silently discards write errors.
+///
 /// The writer itself is specified by the rest arguments with the [`define_writer`] macros.
 ///
 /// # Examples
@@ -17,12 +21,22 @@
 /// ```
 ///
 /// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
 #[macro_export]
 macro_rules! define_printlike {
+    ( $( $args:tt )* ) => {
+        $crate::_define_printlike!(Print, $( $args )*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_printlike {
     (
+        $kind:ident,
         $( #[$meta:meta] )*
         $name:ident,
-        $macro:path,
+        $macro:path $([ $($affix:tt)* ])?,
         expect,
         $( $args:tt )*
     ) => {
@@ -30,15 +44,17 @@ macro_rules! define_printlike {
             ($),
             $( #[$meta] )*,
             $name,
-            $macro,
+            $macro $([ $($affix)* ])?,
             expect,
+            $kind,
             $crate::define_writer!($($args)*)
         );
     };
     (
+        $kind:ident,
         $( #[$meta:meta] )*
         $name:ident,
-        $macro:path,
+        $macro:path $([ $($affix:tt)* ])?,
         try,
         $( $args:tt )*
     ) => {
@@ -46,8 +62,27 @@ macro_rules! define_printlike {
             ($),
             $( #[$meta] )*,
             $name,
-            $macro,
+            $macro $([ $($affix)* ])?,
             try,
+            $kind,
+            $crate::define_try_writer!($($args)*)
+        );
+    };
+    (
+        $kind:ident,
+        $( #[$meta:meta] )*
+        $name:ident,
+        $macro:path $([ $($affix:tt)* ])?,
+        ignore,
+        $( $args:tt )*
+    ) => {
+        $crate::_define_printlike_impl!(
+            ($),
+            $( #[$meta] )*,
+            $name,
+            $macro $([ $($affix)* ])?,
+            ignore,
+            $kind,
             $crate::define_try_writer!($($args)*)
         );
     };
@@ -60,8 +95,9 @@ macro_rules! _define_printlike_impl {
         ($d:tt),
         $( #[$meta:meta] )*,
         $name:ident,
-        $macro:path,
+        $macro:path $([ $($affix:tt)* ])?,
         $handler:tt,
+        $kind:ident,
         $( $writer:tt )*
     ) => {
         $( #[$meta] )*
@@ -70,12 +106,14 @@ macro_rules! _define_printlike_impl {
             // Dummy comment below is used to avoid rustfmt formatting bug.
             // Issue: https://github.com/rust-lang/rustfmt/issues/4609
             /* ================================================================================== */
-            () => {
-                $crate::write!($macro, $( $writer )*, $handler)
-            };
-            ($d ($d args:tt)+) => {
-                $crate::write!($macro, $( $writer )*, $handler, $d ($d args)+)
-            };
+            () => {{
+                let _record = $crate::_record_scope!($crate::RecordKind::$kind);
+                $crate::write!($macro $([ $($affix)* ])?, $( $writer )*, $handler)
+            }};
+            ($d ($d args:tt)+) => {{
+                let _record = $crate::_record_scope!($crate::RecordKind::$kind);
+                $crate::write!($macro $([ $($affix)* ])?, $( $writer )*, $handler, $d ($d args)+)
+            }};
         }
     };
 }
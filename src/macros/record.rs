@@ -0,0 +1,14 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _record_scope {
+    ( $kind:expr ) => {{
+        static METADATA: $crate::_RecordMetadata = $crate::_RecordMetadata::new(
+            ::core::file!(),
+            ::core::line!(),
+            ::core::column!(),
+            ::core::module_path!(),
+            $kind,
+        );
+        $crate::_RecordScope::enter(&METADATA)
+    }};
+}
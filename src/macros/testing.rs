@@ -0,0 +1,55 @@
+/// Asserts that the text recorded by the [`Recorder`] is equal to the formatted string.
+///
+/// This macro requires the `testing` feature.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "testing", doc = "```rust")]
+#[cfg_attr(not(feature = "testing"), doc = "```rust,compile_fail")]
+/// use custom_print::assert_printed;
+/// use custom_print::testing::capture;
+///
+/// custom_print::define_macro!(cprintln, capture);
+///
+/// fn main() {
+///     let recorder = capture(|| cprintln!("value: {}", 1));
+///     assert_printed!(recorder, "value: {}\n", 1);
+/// }
+/// ```
+///
+/// [`Recorder`]: testing/struct.Recorder.html
+#[macro_export]
+macro_rules! assert_printed {
+    ( $recorder:expr, $($args:tt)+ ) => {
+        $crate::testing::_assert_printed(&$recorder, ::core::format_args!($($args)+))
+    };
+}
+
+/// Asserts that the strings of the write calls recorded by the [`Recorder`]
+/// are equal to the specified ones.
+///
+/// This macro requires the `testing` feature.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "testing", doc = "```rust")]
+#[cfg_attr(not(feature = "testing"), doc = "```rust,compile_fail")]
+/// use custom_print::assert_chunks;
+/// use custom_print::testing::capture;
+///
+/// custom_print::define_macro!(cprintln, capture);
+///
+/// fn main() {
+///     let value = 1;
+///     let recorder = capture(|| cprintln!("value: {}", value));
+///     assert_chunks!(recorder, ["value: ", "1", "\n"]);
+/// }
+/// ```
+///
+/// [`Recorder`]: testing/struct.Recorder.html
+#[macro_export]
+macro_rules! assert_chunks {
+    ( $recorder:expr, $expected:expr $(,)? ) => {
+        ::core::assert_eq!($recorder.chunks(), $expected)
+    };
+}
@@ -1,6 +1,7 @@
 /// Calls another write macro with the specified writer, arguments and error-handling policy.
 ///
-/// It propagates errors if the `try` policy is used.
+/// It propagates errors if the `try` policy is used,
+/// and silently discards them returning `()` if the `ignore` policy is used.
 ///
 /// # Panics
 ///
@@ -16,13 +17,33 @@
 /// assert_eq!(string, "first\n");
 /// assert_eq!(custom_print::write!(writeln, &mut string, try, "second"), Ok(()));
 /// assert_eq!(string, "first\nsecond\n");
+/// assert_eq!(custom_print::write!(writeln, &mut string, ignore, "third"), ());
+/// assert_eq!(string, "first\nsecond\nthird\n");
 /// ```
 #[macro_export]
 macro_rules! write {
+    ( $macro:path [], $( $args:tt )* ) => {
+        $crate::write!($macro, $( $args )*)
+    };
+    ( $macro:path [ $prefix:expr, $suffix:expr ], $writer:expr, $handler:tt $(,)? ) => {
+        $crate::write!($macro [$prefix, $suffix], $writer, $handler, "")
+    };
+    (
+        $macro:path [ $prefix:expr, $suffix:expr ], $writer:expr, $handler:tt,
+        $format:expr $(, $($args:tt)*)?
+    ) => {
+        $crate::write!(
+            $macro, $writer, $handler,
+            ::core::concat!($prefix, $format, $suffix) $(, $($args)*)?
+        )
+    };
     ( $macro:path, $writer:expr, expect $(, $($args:tt)*)? ) => {
         { $macro!($writer $(, $($args)*)?) }.expect("failed writing")
     };
     ( $macro:path, $writer:expr, try $(, $($args:tt)*)? ) => {
         { $macro!($writer $(, $($args)*)?) }
     };
+    ( $macro:path, $writer:expr, ignore $(, $($args:tt)*)? ) => {
+        { let _ = { $macro!($writer $(, $($args)*)?) }; }
+    };
 }
@@ -3,11 +3,68 @@
 /// If more than one argument is used, the first argument specifies the writer type,
 /// and the others are used to define the expression:
 /// - `concat, args...`: [`ConcatWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
+/// - `concat_stack<N>, args...`:
+///   [`ArrayConcatWriter`]`::<N, _, _>::from_closure(`[`define_writer_expr`]`!(args...))`
+/// - `concat_buffered, args...`:
+///   [`BufferedConcatWriter`]`::new(`[`ConcatWriter`]`::from_closure(`[`define_writer_expr`]`!(args...)))`
+///   that formats messages into a reused buffer
+/// - `concat_buffered<N>, args...`: the same writer with `.with_max_capacity(N)`
+///   that keeps at most `N` bytes of the buffer capacity between calls
+/// - `args, args...`: [`ArgsWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
 /// - `fmt, args...`: [`FmtWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
 /// - `io, args...`: [`IoWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
+/// - `line, args...`: [`LineBufWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
+///   with the module path of the call site and the stringified arguments
+///   used as the line buffer key
+/// - `tee(args...; args...; ...)`: [`TeeWriter`] that writes into all writers
+///   defined by `define_writer!(args...)` for each `;`-separated arguments group
+/// - `fallback(primary_args...; secondary_args...)`: [`FallbackWriter`] that writes
+///   into `define_try_writer!(primary_args...)` and if it fails,
+///   into `define_writer!(secondary_args...)`
+/// - `prefix(prefix), args...`: [`PrefixWriter`]`::new(prefix, define_writer!(args...))`
+///   that inserts the prefix at the start of every line
+///   with the module path of the call site and the stringified prefix and arguments
+///   used as the line state key
+/// - `locked, args...`: [`LockedWriter`]`::new(&`[`WRITER_LOCK`]`, define_writer!(args...))`
+///   that holds the global lock while writing
+/// - `locked(lock), args...`: [`LockedWriter`]`::new(lock, define_writer!(args...))`
+///   that holds the specified [`WriterLock`] while writing
+/// - `guarded, args...`: [`GuardedWriter`]`::new(define_writer!(args...))`
+///   that queues nested writes made while writing
+/// - `guarded(policy), args...`:
+///   [`GuardedWriter`]`::new(define_writer!(args...)).with_policy(policy)`
+///   that handles nested writes with the specified [`NestedWrite`] policy
+/// - `global`: [`FmtWriter`]`::new(&`[`GLOBAL_SINK`]`)` that writes into the sink
+///   which can be replaced at runtime
+/// - `global(sink)`: [`FmtWriter`]`::new(sink)` that writes into the specified [`GlobalSink`]
+/// - `capture`: [`FmtWriter`] that writes into the recorder of the innermost [`capture`] call
+///   on the current thread, requires the `testing` feature
+///
+/// The flush function can be specified after the write function arguments
+/// with the `flush:` prefix, for example `concat, write_args..., flush: flush_args...`.
+/// In that case the writer is created with `from_closures` method
+/// and both arguments are passed to the [`define_writer_expr`] separately.
 ///
 /// If only one argument is used, the macro just returns it as a result.
 ///
+/// If the arguments are prefixed with `once:`, the writer defined by the rest arguments
+/// is constructed only once on first use and stored in a shared [`OnceWriter`],
+/// so the same writer instance is locked and reused on every call
+/// by all macros defined with the same arguments that are called from the same module.
+/// The arguments are resolved in the module of the call site,
+/// so calls from other modules use their own writer.
+/// Every call site caches the writer in its own hidden static.
+/// Nested writes, for example from a [`Display`] implementation of a written value,
+/// use a newly constructed writer instead of waiting for the shared one.
+/// This mode requires the `alloc` feature.
+///
+/// The `dbg`-like macros defined with the `locked` modes hold the lock
+/// during the whole macro invocation instead of every write,
+/// so the values of a multi-value `dbg` call are written together.
+/// The `locked` mode can follow the `prefix` mode, like `prefix("> "), locked, args...`.
+/// If it follows the `guarded` mode, the lock is taken for every write inside the guard,
+/// so nested writes from the written values are handled by the guard without waiting for it.
+///
 /// Use [`define_try_writer`] if you need to define a fallible writer.
 /// This macro is used by [`define_printlike`], [`define_dbglike`] and [`define_flush`] macros.
 ///
@@ -30,9 +87,51 @@
 /// assert_eq!(concat_string, "second\n");
 /// ```
 ///
+/// An example with a writer that is constructed once and shared by all call sites:
+#[cfg_attr(feature = "std", doc = "```rust")]
+#[cfg_attr(not(feature = "std"), doc = "```rust,compile_fail")]
+/// use once_cell::sync::Lazy;
+/// use std::io::{LineWriter, Write};
+/// use std::sync::Mutex;
+///
+/// static WRITTEN: Lazy<Mutex<Vec<u8>>> = Lazy::new(Mutex::default);
+///
+/// fn write(value: &[u8]) {
+///     WRITTEN.lock().unwrap().extend_from_slice(value);
+/// }
+///
+/// custom_print::define_macros!({ cprint, flush },
+///     once: LineWriter::new(custom_print::define_writer!(io, crate::write)));
+///
+/// fn main() {
+///     assert_eq!(cprint!("first,"), ());
+///     assert_eq!(cprint!("second\nthird,"), ());
+///     assert_eq!(*WRITTEN.lock().unwrap(), b"first,second\n");
+///     assert_eq!(flush!(), ());
+///     assert_eq!(*WRITTEN.lock().unwrap(), b"first,second\nthird,");
+/// }
+/// ```
+///
 /// [`ConcatWriter`]: struct.ConcatTryWriter.html
+/// [`ArrayConcatWriter`]: struct.ArrayConcatWriter.html
+/// [`ArgsWriter`]: struct.ArgsWriter.html
 /// [`FmtWriter`]: struct.FmtTryWriter.html
 /// [`IoWriter`]: struct.IoTryWriter.html
+/// [`LineBufWriter`]: struct.LineBufWriter.html
+/// [`TeeWriter`]: struct.TeeWriter.html
+/// [`FallbackWriter`]: struct.FallbackWriter.html
+/// [`BufferedConcatWriter`]: struct.BufferedConcatWriter.html
+/// [`PrefixWriter`]: struct.PrefixWriter.html
+/// [`LockedWriter`]: struct.LockedWriter.html
+/// [`WRITER_LOCK`]: static.WRITER_LOCK.html
+/// [`WriterLock`]: trait.WriterLock.html
+/// [`GuardedWriter`]: struct.GuardedWriter.html
+/// [`NestedWrite`]: enum.NestedWrite.html
+/// [`GLOBAL_SINK`]: static.GLOBAL_SINK.html
+/// [`GlobalSink`]: struct.GlobalSink.html
+/// [`capture`]: testing/fn.capture.html
+/// [`OnceWriter`]: struct.OnceWriter.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
 /// [`define_writer_expr`]: macro.define_writer_expr.html
 /// [`define_try_writer`]: macro.define_try_writer.html
 /// [`define_printlike`]: macro.define_printlike.html
@@ -40,14 +139,75 @@
 /// [`define_flush`]: macro.define_flush.html
 #[macro_export]
 macro_rules! define_writer {
+    ( once: $($args:tt)* ) => {
+        &mut *{
+            static SLOT: $crate::_OnceWriterSlot = $crate::_OnceWriterSlot::new();
+            SLOT.lock_or_init(
+                ::core::concat!(::core::module_path!(), "::", ::core::stringify!($($args)*)),
+                || $crate::define_writer!($($args)*),
+            )
+        }
+    };
     ( concat, $($args:tt)* ) => {
-        $crate::ConcatWriter::from_closure($crate::define_writer_expr!($($args)*))
+        $crate::_define_writer_impl!([ConcatWriter], [] $($args)*)
+    };
+    ( concat_stack<$n:tt>, $($args:tt)* ) => {
+        $crate::_define_writer_impl!([ArrayConcatWriter::<$n, _, _>], [] $($args)*)
+    };
+    ( concat_buffered, $($args:tt)* ) => {
+        $crate::BufferedConcatWriter::new($crate::_define_writer_impl!([ConcatWriter], [] $($args)*))
+    };
+    ( concat_buffered<$n:tt>, $($args:tt)* ) => {
+        $crate::BufferedConcatWriter::new($crate::_define_writer_impl!([ConcatWriter], [] $($args)*))
+            .with_max_capacity($n)
+    };
+    ( args, $($args:tt)* ) => {
+        $crate::_define_writer_impl!([ArgsWriter], [] $($args)*)
     };
     ( fmt, $($args:tt)* ) => {
-        $crate::FmtWriter::from_closure($crate::define_writer_expr!($($args)*))
+        $crate::_define_writer_impl!([FmtWriter], [] $($args)*)
     };
     ( io, $($args:tt)* ) => {
-        $crate::IoWriter::from_closure($crate::define_writer_expr!($($args)*))
+        $crate::_define_writer_impl!([IoWriter], [] $($args)*)
+    };
+    ( line, $($args:tt)* ) => {
+        $crate::_define_writer_impl!([LineBufWriter], [] $($args)*)
+            .with_key(::core::concat!(::core::module_path!(), "::", ::core::stringify!($($args)*)))
+    };
+    ( tee( $($args:tt)* ) ) => {
+        $crate::_define_tee_writer!(define_writer, [] [] $($args)*)
+    };
+    ( fallback( $($args:tt)* ) ) => {
+        $crate::_define_fallback_writer!(define_writer, [] $($args)*)
+    };
+    ( prefix($prefix:expr), $($args:tt)* ) => {
+        $crate::PrefixWriter::new($prefix, $crate::define_writer!($($args)*))
+            .with_key(::core::concat!(
+                ::core::module_path!(),
+                "::",
+                ::core::stringify!(prefix($prefix), $($args)*),
+            ))
+    };
+    ( guarded, $($args:tt)* ) => {
+        $crate::GuardedWriter::new($crate::define_writer!($($args)*))
+    };
+    ( guarded($policy:expr), $($args:tt)* ) => {
+        $crate::GuardedWriter::new($crate::define_writer!($($args)*)).with_policy($policy)
+    };
+    ( locked, $($args:tt)* ) => {
+        $crate::LockedWriter::new(&$crate::WRITER_LOCK, $crate::define_writer!($($args)*))
+    };
+    ( locked($lock:expr), $($args:tt)* ) => {
+        $crate::LockedWriter::new($lock, $crate::define_writer!($($args)*))
+    };
+    ( global ) => {
+        $crate::FmtWriter::new(&$crate::GLOBAL_SINK)
+    };
+    ( global($sink:expr) ) => {
+        $crate::FmtWriter::new($sink)
+    };
+    ( capture ) => {
+        $crate::FmtWriter::from_closures($crate::testing::write, $crate::testing::flush)
     };
     ( $expr:expr ) => {
         $expr
@@ -59,11 +219,51 @@ macro_rules! define_writer {
 /// If more than one argument is used, the first argument specifies the writer type,
 /// and the others are used to define the expression:
 /// - `concat, args...`: [`ConcatTryWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
+/// - `concat_stack<N>, args...`:
+///   [`ArrayConcatTryWriter`]`::<N, _, _>::from_closure(`[`define_writer_expr`]`!(args...))`
+/// - `concat_buffered, args...`:
+///   [`BufferedConcatWriter`]`::new(`[`ConcatTryWriter`]`::from_closure(`[`define_writer_expr`]`!(args...)))`
+///   that formats messages into a reused buffer
+/// - `concat_buffered<N>, args...`: the same writer with `.with_max_capacity(N)`
+///   that keeps at most `N` bytes of the buffer capacity between calls
+/// - `args, args...`: [`ArgsTryWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
 /// - `fmt, args...`: [`FmtTryWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
 /// - `io, args...`: [`IoTryWriter`]`::from_closure(`[`define_writer_expr`]`!(args...))`
+/// - `tee(args...; args...; ...)`: [`TeeWriter`] that writes into all writers
+///   defined by `define_try_writer!(args...)` for each `;`-separated arguments group
+/// - `fallback(primary_args...; secondary_args...)`: [`FallbackWriter`] that writes
+///   into `define_try_writer!(primary_args...)` and if it fails,
+///   into `define_try_writer!(secondary_args...)`
+/// - `prefix(prefix), args...`: [`PrefixWriter`]`::new(prefix, define_try_writer!(args...))`
+///   that inserts the prefix at the start of every line
+///   with the module path of the call site and the stringified prefix and arguments
+///   used as the line state key
+/// - `locked, args...`: [`LockedWriter`]`::new(&`[`WRITER_LOCK`]`, define_try_writer!(args...))`
+///   that holds the global lock while writing
+/// - `locked(lock), args...`: [`LockedWriter`]`::new(lock, define_try_writer!(args...))`
+///   that holds the specified [`WriterLock`] while writing
+/// - `guarded, args...`: [`GuardedWriter`]`::new(define_try_writer!(args...))`
+///   that queues nested writes made while writing
+/// - `guarded(policy), args...`:
+///   [`GuardedWriter`]`::new(define_try_writer!(args...)).with_policy(policy)`
+///   that handles nested writes with the specified [`NestedWrite`] policy
+/// - `global`: [`FmtTryWriter`]`::new(&`[`GLOBAL_SINK`]`)` that writes into the sink
+///   which can be replaced at runtime
+/// - `global(sink)`: [`FmtTryWriter`]`::new(sink)` that writes into the specified [`GlobalSink`]
+/// - `capture`: [`FmtTryWriter`] that writes into the recorder of the innermost [`capture`] call
+///   on the current thread, requires the `testing` feature
+///
+/// The flush function can be specified after the write function arguments
+/// with the `flush:` prefix the same way as in [`define_writer`].
 ///
 /// If only one argument is used, the macro just returns it as a result.
 ///
+/// If the arguments are prefixed with `once:`, the writer is constructed only once
+/// and shared the same way as in [`define_writer`].
+///
+/// The `dbg`-like macros defined with the `locked` modes hold the lock
+/// during the whole macro invocation the same way as in [`define_writer`].
+///
 /// Use [`define_writer`] if you need to define a non-fallible writer.
 /// This macro is used by [`define_printlike`], [`define_dbglike`] and [`define_try_flush`] macros.
 ///
@@ -72,6 +272,7 @@ macro_rules! define_writer {
 #[cfg_attr(feature = "alloc", doc = "```rust")]
 #[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
 /// use core::fmt::{self, Write};
+/// use custom_print::FmtWriteError;
 ///
 /// let mut expr_string = String::new();
 /// let mut expr_writer = custom_print::define_try_writer!(&mut expr_string);
@@ -85,12 +286,29 @@ macro_rules! define_writer {
 /// assert_eq!(expr_string, "first\n");
 /// assert_eq!(writeln!(concat_writer, "second"), Ok(()));
 /// assert_eq!(concat_string, "second\n");
-/// assert_eq!(writeln!(fallible_writer, "third"), Err(fmt::Error));
+/// assert_eq!(
+///     writeln!(fallible_writer, "third"),
+///     Err(FmtWriteError::Write(fmt::Error))
+/// );
 /// ```
 ///
 /// [`ConcatTryWriter`]: struct.ConcatTryWriter.html
+/// [`ArrayConcatTryWriter`]: struct.ArrayConcatTryWriter.html
+/// [`ArgsTryWriter`]: struct.ArgsTryWriter.html
 /// [`FmtTryWriter`]: struct.FmtTryWriter.html
 /// [`IoTryWriter`]: struct.IoTryWriter.html
+/// [`TeeWriter`]: struct.TeeWriter.html
+/// [`FallbackWriter`]: struct.FallbackWriter.html
+/// [`BufferedConcatWriter`]: struct.BufferedConcatWriter.html
+/// [`PrefixWriter`]: struct.PrefixWriter.html
+/// [`LockedWriter`]: struct.LockedWriter.html
+/// [`WRITER_LOCK`]: static.WRITER_LOCK.html
+/// [`WriterLock`]: trait.WriterLock.html
+/// [`GuardedWriter`]: struct.GuardedWriter.html
+/// [`NestedWrite`]: enum.NestedWrite.html
+/// [`GLOBAL_SINK`]: static.GLOBAL_SINK.html
+/// [`GlobalSink`]: struct.GlobalSink.html
+/// [`capture`]: testing/fn.capture.html
 /// [`define_writer_expr`]: macro.define_writer_expr.html
 /// [`define_writer`]: macro.define_writer.html
 /// [`define_printlike`]: macro.define_printlike.html
@@ -98,16 +316,166 @@ macro_rules! define_writer {
 /// [`define_try_flush`]: macro.define_try_flush.html
 #[macro_export]
 macro_rules! define_try_writer {
+    ( once: $($args:tt)* ) => {
+        &mut *{
+            static SLOT: $crate::_OnceWriterSlot = $crate::_OnceWriterSlot::new();
+            SLOT.lock_or_init(
+                ::core::concat!(::core::module_path!(), "::", ::core::stringify!($($args)*)),
+                || $crate::define_try_writer!($($args)*),
+            )
+        }
+    };
     ( concat, $($args:tt)* ) => {
-        $crate::ConcatTryWriter::from_closure($crate::define_writer_expr!($($args)*))
+        $crate::_define_writer_impl!([ConcatTryWriter], [] $($args)*)
+    };
+    ( concat_stack<$n:tt>, $($args:tt)* ) => {
+        $crate::_define_writer_impl!([ArrayConcatTryWriter::<$n, _, _>], [] $($args)*)
+    };
+    ( concat_buffered, $($args:tt)* ) => {
+        $crate::BufferedConcatWriter::new($crate::_define_writer_impl!([ConcatTryWriter], [] $($args)*))
+    };
+    ( concat_buffered<$n:tt>, $($args:tt)* ) => {
+        $crate::BufferedConcatWriter::new($crate::_define_writer_impl!([ConcatTryWriter], [] $($args)*))
+            .with_max_capacity($n)
+    };
+    ( args, $($args:tt)* ) => {
+        $crate::_define_writer_impl!([ArgsTryWriter], [] $($args)*)
     };
     ( fmt, $($args:tt)* ) => {
-        $crate::FmtTryWriter::from_closure($crate::define_writer_expr!($($args)*))
+        $crate::_define_writer_impl!([FmtTryWriter], [] $($args)*)
     };
     ( io, $($args:tt)* ) => {
-        $crate::IoTryWriter::from_closure($crate::define_writer_expr!($($args)*))
+        $crate::_define_writer_impl!([IoTryWriter], [] $($args)*)
+    };
+    ( tee( $($args:tt)* ) ) => {
+        $crate::_define_tee_writer!(define_try_writer, [] [] $($args)*)
+    };
+    ( fallback( $($args:tt)* ) ) => {
+        $crate::_define_fallback_writer!(define_try_writer, [] $($args)*)
+    };
+    ( prefix($prefix:expr), $($args:tt)* ) => {
+        $crate::PrefixWriter::new($prefix, $crate::define_try_writer!($($args)*))
+            .with_key(::core::concat!(
+                ::core::module_path!(),
+                "::",
+                ::core::stringify!(prefix($prefix), $($args)*),
+            ))
+    };
+    ( guarded, $($args:tt)* ) => {
+        $crate::GuardedWriter::new($crate::define_try_writer!($($args)*))
+    };
+    ( guarded($policy:expr), $($args:tt)* ) => {
+        $crate::GuardedWriter::new($crate::define_try_writer!($($args)*)).with_policy($policy)
+    };
+    ( locked, $($args:tt)* ) => {
+        $crate::LockedWriter::new(&$crate::WRITER_LOCK, $crate::define_try_writer!($($args)*))
+    };
+    ( locked($lock:expr), $($args:tt)* ) => {
+        $crate::LockedWriter::new($lock, $crate::define_try_writer!($($args)*))
+    };
+    ( global ) => {
+        $crate::FmtTryWriter::new(&$crate::GLOBAL_SINK)
+    };
+    ( global($sink:expr) ) => {
+        $crate::FmtTryWriter::new($sink)
+    };
+    ( capture ) => {
+        $crate::FmtTryWriter::from_closures($crate::testing::write, $crate::testing::flush)
     };
     ( $expr:expr ) => {
         $expr
     };
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_writer_impl {
+    ( [ $($writer:tt)* ], [ $($write:tt)* ], flush: $($flush:tt)* ) => {
+        $crate::$($writer)*::from_closures(
+            $crate::define_writer_expr!($($write)*),
+            $crate::define_writer_expr!($($flush)*),
+        )
+    };
+    ( [ $($writer:tt)* ], [ $($write:tt)* ] ) => {
+        $crate::$($writer)*::from_closure($crate::define_writer_expr!($($write)*))
+    };
+    ( [ $($writer:tt)* ], [ $($write:tt)* ] $next:tt $($rest:tt)* ) => {
+        $crate::_define_writer_impl!([ $($writer)* ], [ $($write)* $next ] $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_tee_writer {
+    ( $writer:ident, [ $($done:tt)* ] [ $($current:tt)* ] ; $($rest:tt)* ) => {
+        $crate::_define_tee_writer!($writer, [ $($done)* [ $($current)* ] ] [] $($rest)*)
+    };
+    ( $writer:ident, [ $($done:tt)* ] [ $($current:tt)* ] $next:tt $($rest:tt)* ) => {
+        $crate::_define_tee_writer!($writer, [ $($done)* ] [ $($current)* $next ] $($rest)*)
+    };
+    ( $writer:ident, [ $($done:tt)* ] [ $($current:tt)* ] ) => {
+        $crate::_define_tee_writer_impl!($writer, $($done)* [ $($current)* ])
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_tee_writer_impl {
+    ( $writer:ident, [ $($last:tt)* ] ) => {
+        $crate::$writer!($($last)*)
+    };
+    ( $writer:ident, [ $($first:tt)* ] $($rest:tt)+ ) => {
+        $crate::TeeWriter::new(
+            $crate::$writer!($($first)*),
+            $crate::_define_tee_writer_impl!($writer, $($rest)+),
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_fallback_writer {
+    ( $writer:ident, [ $($primary:tt)* ] ; $($secondary:tt)* ) => {
+        $crate::FallbackWriter::new(
+            $crate::define_try_writer!($($primary)*),
+            $crate::$writer!($($secondary)*),
+        )
+    };
+    ( $writer:ident, [ $($primary:tt)* ] $next:tt $($rest:tt)* ) => {
+        $crate::_define_fallback_writer!($writer, [ $($primary)* $next ] $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _lock_scope {
+    ( locked, $($args:tt)* ) => {
+        $crate::_WriterLockScope::enter(&$crate::WRITER_LOCK)
+    };
+    ( locked($lock:expr), $($args:tt)* ) => {
+        $crate::_WriterLockScope::enter($lock)
+    };
+    ( prefix($prefix:expr), $($args:tt)* ) => {
+        $crate::_lock_scope!($($args)*)
+    };
+    ( $($args:tt)* ) => {
+        $crate::_WriterLockScope::none()
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_unlocked_writer {
+    ( $writer:ident, [ $($done:tt)* ] locked, $($args:tt)* ) => {
+        $crate::$writer!($($done)* $($args)*)
+    };
+    ( $writer:ident, [ $($done:tt)* ] locked($lock:expr), $($args:tt)* ) => {
+        $crate::$writer!($($done)* $($args)*)
+    };
+    ( $writer:ident, [ $($done:tt)* ] prefix($prefix:expr), $($args:tt)* ) => {
+        $crate::_define_unlocked_writer!($writer, [ $($done)* prefix($prefix), ] $($args)*)
+    };
+    ( $writer:ident, [ $($done:tt)* ] $($args:tt)* ) => {
+        $crate::$writer!($($done)* $($args)*)
+    };
+}
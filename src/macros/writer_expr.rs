@@ -2,6 +2,7 @@
 ///
 /// If unsafe function specified as argument
 /// it just creates an `FnMut` wrapper that calls unsafe fn in unsafe block.
+/// Functions without arguments are supported to be used as flush functions.
 /// The function can be specified by identifier or by braced full path.
 /// See [Safety](#safety) section for important details about safety.
 ///
@@ -10,8 +11,50 @@
 /// creates an `FnMut` wrapper that calls unsafe fn in unsafe block.
 /// See [Safety](#safety) section for important details about safety.
 ///
+/// Both unsafe and extern functions can have any number of parameters
+/// specified as `name: Type`, in which case the parameter is a message parameter
+/// passed from the writer, or as `name: Type = value`, in which case the parameter
+/// is bound to the value expression evaluated on every call,
+/// like `extern "C" fn log_write(prio: c_int = 3, tag: *const c_char = TAG, _: *const c_char)`.
+/// The type of the bound parameters of unsafe functions can be omitted, like `prio = 3`.
+/// The bound values are evaluated outside of the unsafe block,
+/// so they need their own `unsafe` block to call unsafe code.
+/// The message parameters are used to infer the writer function wrapper
+/// with [`IntoWriteFn`] or [`IntoTryWriteFn`] as for closures.
+///
 /// If an expression given as argument, the macro just returns it as a result.
 ///
+/// The arguments can be prefixed with a return convention
+/// if the function does not return `()`, `usize` or [`Result`]:
+/// - `errno: args...`: [`Errno`]`(define_writer_expr!(args...))` for functions returning
+///   `i32`, `i64` or `isize` where negative values are error codes
+///   and non-negative values mean success,
+/// - `errno_count: args...`: [`ErrnoCount`]`(define_writer_expr!(args...))` for functions
+///   returning `i32`, `i64` or `isize` where negative values are error codes
+///   and non-negative values are written bytes counts,
+/// - `bool: args...`: [`Flag`]`(define_writer_expr!(args...))` for functions returning
+///   `bool` where `false` means failure,
+/// - `option: args...`: [`Presence`]`(define_writer_expr!(args...))` for functions returning
+///   [`Option`] where `None` means failure,
+/// - `ignore: args...`: [`Ignored`]`(define_writer_expr!(args...))` for functions returning
+///   a value that should be ignored.
+///
+/// The failures are reported as [`ReturnError`] by fallible writers.
+///
+/// The arguments of functions that take C strings can be prefixed with `lossy:`
+/// or `lossy(policy):` to wrap the function into [`LossyCStr`]`(define_writer_expr!(args...), policy)`,
+/// so interior nul bytes are handled with the specified [`NulPolicy`]
+/// (the [`NulPolicy::ESCAPE`] by default) instead of panicking or returning an error.
+/// The `cstr_stack<N>:` and `cstr_stack<N>(policy):` prefixes wrap the function into
+/// [`ArrayCStr`]`::<N, _>(define_writer_expr!(args...), policy)` instead,
+/// so C strings are built in a stack buffer of `N` bytes without allocations.
+///
+/// The arguments can be prefixed with `chunk<N>:` or `chunk<N>(overflow):`
+/// to wrap the function into
+/// [`ChunkingWriter`]`::<N, _>::new(define_writer_expr!(args...)).with_overflow(overflow)`,
+/// so every message is split into chunks of at most `N` bytes
+/// or truncated according to the specified [`ChunkOverflow`] policy.
+///
 /// This macro is used by [`define_writer`], [`define_try_writer`] macros.
 ///
 /// # Safety
@@ -20,10 +63,134 @@
 /// defined with `unsafe fn` or `extern `fn` do not require unsafe,
 /// so defining writer expression itself should be treated as an unsafe operation.
 ///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::{FmtWriteError, ReturnError};
+///
+/// let mut count = 0;
+/// let mut write = |value: &str| -> i32 {
+///     count += 1;
+///     if count == 2 { -5 } else { value.len() as i32 }
+/// };
+/// custom_print::define_macro!(try_println, fmt, errno: &mut write);
+///
+/// assert_eq!(try_println!("first"), Ok(()));
+/// assert_eq!(
+///     try_println!("second"),
+///     Err(FmtWriteError::Write(ReturnError::Errno(-5)))
+/// );
+/// ```
+///
+/// An example with a bound parameter:
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+///
+/// static STREAM: AtomicU32 = AtomicU32::new(0);
+/// static LEN: AtomicUsize = AtomicUsize::new(0);
+///
+/// unsafe fn write(stream: u32, _: *const u8, len: usize) {
+///     STREAM.store(stream, Ordering::Relaxed);
+///     LEN.store(len, Ordering::Relaxed);
+/// }
+///
+/// custom_print::define_macro!(
+///     ceprintln,
+///     concat,
+///     unsafe fn write(stream: u32 = 2, _: *const u8, _: usize)
+/// );
+///
+/// ceprintln!("first");
+/// assert_eq!(STREAM.load(Ordering::Relaxed), 2);
+/// assert_eq!(LEN.load(Ordering::Relaxed), 6);
+/// ```
+///
+/// The bound values can not call unsafe code without an `unsafe` block:
+///
+/// ```rust,compile_fail
+/// unsafe fn stream() -> u32 {
+///     2
+/// }
+///
+/// unsafe fn write(_stream: u32, _: *const u8, _: usize) {}
+///
+/// custom_print::define_macro!(
+///     ceprint,
+///     concat,
+///     unsafe fn write(stream: u32 = stream(), _: *const u8, _: usize)
+/// );
+///
+/// ceprint!("first");
+/// ```
+///
 /// [`define_writer`]: macro.define_writer.htm
 /// [`define_try_writer`]: macro.define_try_writer.htm
+/// [`IntoWriteFn`]: trait.IntoWriteFn.html
+/// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+/// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
+/// [`Errno`]: struct.Errno.html
+/// [`ErrnoCount`]: struct.ErrnoCount.html
+/// [`Flag`]: struct.Flag.html
+/// [`Presence`]: struct.Presence.html
+/// [`Ignored`]: struct.Ignored.html
+/// [`ReturnError`]: enum.ReturnError.html
+/// [`LossyCStr`]: struct.LossyCStr.html
+/// [`ArrayCStr`]: struct.ArrayCStr.html
+/// [`ChunkingWriter`]: struct.ChunkingWriter.html
+/// [`ChunkOverflow`]: enum.ChunkOverflow.html
+/// [`NulPolicy`]: enum.NulPolicy.html
+/// [`NulPolicy::ESCAPE`]: enum.NulPolicy.html#associatedconstant.ESCAPE
 #[macro_export]
 macro_rules! define_writer_expr {
+    ( errno: $($args:tt)+ ) => {
+        $crate::Errno($crate::define_writer_expr!($($args)+))
+    };
+    ( errno_count: $($args:tt)+ ) => {
+        $crate::ErrnoCount($crate::define_writer_expr!($($args)+))
+    };
+    ( bool: $($args:tt)+ ) => {
+        $crate::Flag($crate::define_writer_expr!($($args)+))
+    };
+    ( option: $($args:tt)+ ) => {
+        $crate::Presence($crate::define_writer_expr!($($args)+))
+    };
+    ( ignore: $($args:tt)+ ) => {
+        $crate::Ignored($crate::define_writer_expr!($($args)+))
+    };
+    ( lossy: $($args:tt)+ ) => {
+        $crate::LossyCStr($crate::define_writer_expr!($($args)+), $crate::NulPolicy::ESCAPE)
+    };
+    ( lossy($policy:expr): $($args:tt)+ ) => {
+        $crate::LossyCStr($crate::define_writer_expr!($($args)+), $policy)
+    };
+    ( cstr_stack<$n:tt>: $($args:tt)+ ) => {
+        $crate::ArrayCStr::<$n, _>($crate::define_writer_expr!($($args)+), $crate::NulPolicy::ESCAPE)
+    };
+    ( cstr_stack<$n:tt>($policy:expr): $($args:tt)+ ) => {
+        $crate::ArrayCStr::<$n, _>($crate::define_writer_expr!($($args)+), $policy)
+    };
+    ( chunk<$n:tt>: $($args:tt)+ ) => {
+        $crate::ChunkingWriter::<$n, _>::new($crate::define_writer_expr!($($args)+))
+    };
+    ( chunk<$n:tt>($overflow:expr): $($args:tt)+ ) => {
+        $crate::ChunkingWriter::<$n, _>::new($crate::define_writer_expr!($($args)+))
+            .with_overflow($overflow)
+    };
+    ( unsafe fn $func:ident() $( -> $ret:ty)? ) => {
+        || unsafe {
+            #[allow(unused_qualifications)]
+            $func()
+        }
+    };
+    ( unsafe fn ($func:path)() $( -> $ret:ty)? ) => {
+        || unsafe {
+            #[allow(unused_qualifications)]
+            $func()
+        }
+    };
     ( unsafe fn $func:ident($ty1:ty) $( -> $ret:ty)? ) => {
         |arg1: $ty1| unsafe {
             #[allow(unused_qualifications)]
@@ -48,23 +215,64 @@ macro_rules! define_writer_expr {
             $func(arg1, arg2)
         }
     };
+    ( unsafe fn $func:ident( $($params:tt)+ ) $( -> $ret:ty)? ) => {
+        $crate::_define_writer_expr_fn!([$func] [] [] [] $($params)+)
+    };
+    ( unsafe fn ($func:path)( $($params:tt)+ ) $( -> $ret:ty)? ) => {
+        $crate::_define_writer_expr_fn!([$func] [] [] [] $($params)+)
+    };
     ( $(#[$extern_meta:meta])* extern $($abi:literal)?
-        $(#[$meta:meta])* fn $func:ident($arg1:tt: $ty1:ty) $( -> $ret:ty)?
-    ) => {{
-        $(#[$extern_meta])* extern $($abi)? {
-            $(#[$meta])* fn $func($arg1: $ty1) $( -> $ret)?;
-        }
-        |arg1: $ty1| unsafe { $func(arg1) }
-    }};
-    ( $(#[$extern_meta:meta])* extern $($abi:literal)?
-        $(#[$meta:meta])* fn $func:ident($arg1:tt: $ty1:ty, $arg2:tt: $ty2:ty) $( -> $ret:ty)?
+        $(#[$meta:meta])* fn $func:ident( $( $arg:tt: $ty:ty $(= $value:expr)? ),* )
+        $( -> $ret:ty)?
     ) => {{
         $(#[$extern_meta])* extern $($abi)? {
-            $(#[$meta])* fn $func($arg1: $ty1, $arg2: $ty2) $( -> $ret)?;
+            $(#[$meta])* fn $func( $( $arg: $ty ),* ) $( -> $ret)?;
         }
-        |arg1: $ty1, arg2: $ty2| unsafe { $func(arg1, arg2) }
+        $crate::_define_writer_expr_fn!([$func] [] [] [] $( $arg: $ty $(= $value)? ),*)
     }};
     ( $expr:expr ) => {
         $expr
     };
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _define_writer_expr_fn {
+    ( [ $func:path ] [ $($params:tt)* ] [ $($args:tt)* ] [ $($lets:tt)* ] ) => {
+        |$($params)*| {
+            $($lets)*
+            unsafe {
+                #[allow(unused_qualifications)]
+                $func($($args)*)
+            }
+        }
+    };
+    (
+        [ $func:path ] [ $($params:tt)* ] [ $($args:tt)* ] [ $($lets:tt)* ]
+        $name:tt: $ty:ty = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        // Bound values are evaluated outside of the `unsafe` block.
+        $crate::_define_writer_expr_fn!(
+            [$func] [ $($params)* ] [ $($args)* value, ]
+            [ $($lets)* let value: $ty = $value; ] $($($rest)*)?
+        )
+    };
+    (
+        [ $func:path ] [ $($params:tt)* ] [ $($args:tt)* ] [ $($lets:tt)* ]
+        $name:tt = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::_define_writer_expr_fn!(
+            [$func] [ $($params)* ] [ $($args)* value, ]
+            [ $($lets)* let value = $value; ] $($($rest)*)?
+        )
+    };
+    (
+        [ $func:path ] [ $($params:tt)* ] [ $($args:tt)* ] [ $($lets:tt)* ]
+        $name:tt: $ty:ty $(, $($rest:tt)*)?
+    ) => {
+        // Each expansion has its own hygiene context, so every `arg` is a distinct binding.
+        $crate::_define_writer_expr_fn!(
+            [$func] [ $($params)* arg: $ty, ] [ $($args)* arg, ] [ $($lets)* ] $($($rest)*)?
+        )
+    };
+}
@@ -0,0 +1,99 @@
+/// A helper trait for results of write functions that are called several times
+/// to write a single message.
+///
+/// It is used by [`LossyCStr`] and [`ArrayCStr`] writers that split messages at nul bytes
+/// or into stack buffer chunks, and by UTF-16 write functions that split long messages
+/// into stack buffer chunks if the `alloc` feature is disabled.
+/// Writing stops after the first failed call and its result is returned,
+/// otherwise the result of the last call is converted into the result for the whole message.
+///
+/// [`LossyCStr`]: struct.LossyCStr.html
+/// [`ArrayCStr`]: struct.ArrayCStr.html
+pub trait MultiWriteResult {
+    /// Returns `true` if the write function call failed.
+    fn is_failure(&self) -> bool;
+
+    /// Converts the result of the last successful call
+    /// into the result of writing the whole message of `len` bytes.
+    ///
+    /// Written counts are replaced with `len`, since the counts returned
+    /// by the write function refer to the converted data instead of the message.
+    fn with_message_len(self, len: usize) -> Self;
+}
+
+/// Returns the failed result as is
+/// or converts the successful one into the result for the whole message.
+pub(crate) fn message_result<R: MultiWriteResult>(result: R, len: usize) -> R {
+    if result.is_failure() {
+        result
+    } else {
+        result.with_message_len(len)
+    }
+}
+
+impl MultiWriteResult for () {
+    fn is_failure(&self) -> bool {
+        false
+    }
+
+    fn with_message_len(self, _len: usize) -> Self {}
+}
+
+impl MultiWriteResult for usize {
+    fn is_failure(&self) -> bool {
+        false
+    }
+
+    fn with_message_len(self, len: usize) -> Self {
+        len
+    }
+}
+
+impl MultiWriteResult for bool {
+    fn is_failure(&self) -> bool {
+        !*self
+    }
+
+    fn with_message_len(self, _len: usize) -> Self {
+        self
+    }
+}
+
+impl<T: MultiWriteResult> MultiWriteResult for Option<T> {
+    fn is_failure(&self) -> bool {
+        self.is_none()
+    }
+
+    fn with_message_len(self, len: usize) -> Self {
+        self.map(|value| value.with_message_len(len))
+    }
+}
+
+impl<T: MultiWriteResult, E> MultiWriteResult for Result<T, E> {
+    fn is_failure(&self) -> bool {
+        self.is_err()
+    }
+
+    fn with_message_len(self, len: usize) -> Self {
+        self.map(|value| value.with_message_len(len))
+    }
+}
+
+macro_rules! define_errno_multi_write_result {
+    ($($ty:ty),*) => {
+        $(
+            impl MultiWriteResult for $ty {
+                fn is_failure(&self) -> bool {
+                    *self < 0
+                }
+
+                // Non-negative values can be written counts, as with `ErrnoCount`.
+                fn with_message_len(self, len: usize) -> Self {
+                    <$ty>::try_from(len).unwrap_or(<$ty>::MAX)
+                }
+            }
+        )*
+    };
+}
+
+define_errno_multi_write_result!(i32, i64, isize);
@@ -0,0 +1,191 @@
+#[cfg(feature = "alloc")]
+use alloc::ffi::CString;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ffi::CStr;
+
+use crate::multi_write_result::message_result;
+use crate::utf8::floor_char_boundary;
+use crate::MultiWriteResult;
+
+/// A policy that specifies how [`LossyCStr`] and [`ArrayCStr`] writers
+/// handle interior nul bytes.
+///
+/// [`LossyCStr`]: struct.LossyCStr.html
+/// [`ArrayCStr`]: struct.ArrayCStr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NulPolicy {
+    /// Replace each nul byte with the specified string.
+    ///
+    /// Nul bytes in the replacement string itself are skipped.
+    Replace(&'static str),
+    /// Split the message at each nul byte and write non-empty parts as separate C strings.
+    ///
+    /// The write function is called once with an empty string
+    /// if the message has no non-empty parts.
+    /// Writing stops after the first failed call and its result is returned,
+    /// otherwise the result of the last call is returned
+    /// with the written count replaced by the message length.
+    Split,
+}
+
+impl NulPolicy {
+    /// Replace each nul byte with the `\0` escape sequence.
+    pub const ESCAPE: Self = Self::Replace("\\0");
+
+    /// Replace each nul byte with the U+FFFD replacement character.
+    pub const REPLACEMENT_CHARACTER: Self = Self::Replace("\u{FFFD}");
+
+    /// Converts the buffer into C strings according to the policy
+    /// and calls the specified function for each of them
+    /// until the first failed call, returning the result for the whole message.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn write<R: MultiWriteResult>(
+        self,
+        buf: &[u8],
+        mut write: impl FnMut(CString) -> R,
+    ) -> R {
+        if !buf.contains(&0) {
+            return message_result(write(new_cstring(buf.to_vec())), buf.len());
+        }
+        let result = match self {
+            Self::Replace(replacement) => {
+                let mut data = Vec::with_capacity(buf.len() + replacement.len());
+                for (index, part) in buf.split(|&byte| byte == 0).enumerate() {
+                    if index > 0 {
+                        data.extend(replacement.bytes().filter(|&byte| byte != 0));
+                    }
+                    data.extend_from_slice(part);
+                }
+                write(new_cstring(data))
+            }
+            Self::Split => {
+                let mut parts = buf.split(|&byte| byte == 0).filter(|part| !part.is_empty());
+                let mut result = write(new_cstring(parts.next().unwrap_or(&[]).to_vec()));
+                for part in parts {
+                    if result.is_failure() {
+                        break;
+                    }
+                    result = write(new_cstring(part.to_vec()));
+                }
+                result
+            }
+        };
+        message_result(result, buf.len())
+    }
+
+    /// Converts the buffer into C strings of at most `N - 1` bytes
+    /// in a stack buffer according to the policy
+    /// and calls the specified function for each of them
+    /// until the first failed call, returning the result for the whole message.
+    ///
+    /// Longer messages are split preferably at UTF-8 character boundaries.
+    pub(crate) fn write_array<const N: usize, R: MultiWriteResult>(
+        self,
+        buf: &[u8],
+        write: impl FnMut(&CStr) -> R,
+    ) -> R {
+        assert!(
+            N > 1,
+            "C string buffer should have space for at least one byte and nul"
+        );
+        let mut array = CStrArray {
+            buf: [0; N],
+            len: 0,
+            write,
+            result: None,
+        };
+        match self {
+            Self::Replace(replacement) => {
+                for (index, part) in buf.split(|&byte| byte == 0).enumerate() {
+                    if index > 0 {
+                        replacement
+                            .as_bytes()
+                            .split(|&byte| byte == 0)
+                            .for_each(|part| array.push(part));
+                    }
+                    array.push(part);
+                }
+            }
+            Self::Split => {
+                for part in buf.split(|&byte| byte == 0).filter(|part| !part.is_empty()) {
+                    array.push(part);
+                    array.write(array.len);
+                }
+            }
+        }
+        message_result(array.finish(), buf.len())
+    }
+}
+
+impl Default for NulPolicy {
+    fn default() -> Self {
+        Self::ESCAPE
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn new_cstring(data: Vec<u8>) -> CString {
+    CString::new(data).expect("nul bytes are already handled")
+}
+
+struct CStrArray<const N: usize, F, R> {
+    buf: [u8; N],
+    len: usize,
+    write: F,
+    result: Option<R>,
+}
+
+impl<const N: usize, F, R> CStrArray<N, F, R>
+where
+    F: FnMut(&CStr) -> R,
+    R: MultiWriteResult,
+{
+    fn failed(&self) -> bool {
+        self.result.as_ref().map_or(false, R::is_failure)
+    }
+
+    fn push(&mut self, mut data: &[u8]) {
+        while !data.is_empty() && !self.failed() {
+            let capacity = N - 1 - self.len;
+            let mut len = floor_char_boundary(data, capacity);
+            if len == 0 {
+                if self.len > 0 {
+                    self.write(self.len);
+                    continue;
+                }
+                // The character does not fit into the empty buffer, so it is split.
+                len = capacity;
+            }
+            self.buf[self.len..self.len + len].copy_from_slice(&data[..len]);
+            self.len += len;
+            data = &data[len..];
+        }
+    }
+
+    /// Writes the first `len` bytes of the buffer as a C string and keeps the rest,
+    /// unless a previous call failed.
+    fn write(&mut self, len: usize) {
+        if self.failed() {
+            return;
+        }
+        let byte = self.buf[len];
+        self.buf[len] = 0;
+        let cstr =
+            CStr::from_bytes_with_nul(&self.buf[..=len]).expect("nul bytes are already handled");
+        self.result = Some((self.write)(cstr));
+        self.buf[len] = byte;
+        self.buf.copy_within(len..self.len, 0);
+        self.len -= len;
+    }
+
+    fn finish(mut self) -> R {
+        if self.len > 0 || self.result.is_none() {
+            self.write(self.len);
+        }
+        match self.result {
+            Some(result) => result,
+            None => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::any::{Any, TypeId};
+use core::fmt::{self, Debug, Formatter};
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+#[cfg(not(feature = "std"))]
+use crate::spin_lock::{SpinLock, SpinLockGuard};
+
+/// A writer that is lazily constructed only once and shared between all its users.
+///
+/// It is used by the `once:` mode of [`define_writer`] and [`define_try_writer`]
+/// to build the writer a single time instead of at every macro call site,
+/// so that stateful writers like [`LineWriter`] or [`BufWriter`]
+/// can be shared by all generated macros.
+///
+/// Writers are identified by their key and by their type,
+/// so writers whose type depends on a closure defined in the writer expression
+/// are constructed separately for every call site.
+/// Use named functions instead of closures in that case.
+/// The `once:` mode uses the module path of the call site and the stringified writer expression
+/// as the key, because the expression is resolved in the module of the call site,
+/// so macros with the same arguments share the writer only within the same module.
+/// Every `once:` call site caches the shared writer in its own hidden static,
+/// so the writers registry is locked only on the first call from that site.
+///
+/// The writer is protected by [`std::sync::Mutex`] if the `std` feature is enabled,
+/// or by a spin lock otherwise.
+/// A nested write from the same thread, for example from a [`Display`] implementation
+/// of a value written by the same writer, cannot lock the writer again,
+/// so [`lock_or_else`] writes it with a newly constructed writer instead.
+/// Without the `std` feature, writes made while the writer is locked by an interrupt handler
+/// or another core use a newly constructed writer too.
+///
+/// # Examples
+///
+/// ```rust
+/// use custom_print::OnceWriter;
+/// use std::io::{LineWriter, Write};
+///
+/// fn writer() -> LineWriter<Vec<u8>> {
+///     LineWriter::new(Vec::new())
+/// }
+///
+/// let first = OnceWriter::get_or_init("example", writer);
+/// let second = OnceWriter::get_or_init("example", writer);
+///
+/// first.lock().write_all(b"first,").unwrap();
+/// second.lock().write_all(b"second\n").unwrap();
+/// assert_eq!(first.lock().get_ref(), b"first,second\n");
+/// ```
+///
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`LineWriter`]: https://doc.rust-lang.org/std/io/struct.LineWriter.html
+/// [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+/// [`std::sync::Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`lock_or_else`]: #method.lock_or_else
+pub struct OnceWriter<W>(Lock<W>);
+
+/// A guard that provides mutable access to the [`OnceWriter`] writer while it is held,
+/// or to the writer constructed for a nested write.
+///
+/// [`OnceWriter`]: struct.OnceWriter.html
+pub struct OnceWriterGuard<'a, W>(GuardState<'a, W>);
+
+enum GuardState<'a, W> {
+    /// The shared writer is locked by the current thread.
+    Locked {
+        guard: LockGuard<'a, W>,
+        _held: Held,
+    },
+    /// The writer constructed for a nested write.
+    Nested(W),
+}
+
+#[cfg(feature = "std")]
+type Lock<W> = std::sync::Mutex<W>;
+#[cfg(feature = "std")]
+type LockGuard<'a, W> = std::sync::MutexGuard<'a, W>;
+#[cfg(not(feature = "std"))]
+type Lock<W> = SpinLock<W>;
+#[cfg(not(feature = "std"))]
+type LockGuard<'a, W> = SpinLockGuard<'a, W>;
+
+type Registry = BTreeMap<(&'static str, TypeId), Entry>;
+
+/// A registry entry of the writer with the specific key and type.
+enum Entry {
+    /// The writer is being constructed by the init function.
+    #[cfg(feature = "std")]
+    Initializing(std::thread::ThreadId),
+    #[cfg(not(feature = "std"))]
+    Initializing,
+    /// The writer is constructed.
+    Ready(&'static (dyn Any + Send + Sync)),
+}
+
+static WRITERS: Lock<Option<Registry>> = Lock::new(None);
+
+fn with_writers<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    #[cfg(feature = "std")]
+    let mut writers = WRITERS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    #[cfg(not(feature = "std"))]
+    let mut writers = WRITERS.lock();
+    f(writers.get_or_insert_with(BTreeMap::new))
+}
+
+impl<W> OnceWriter<W>
+where
+    W: Send + 'static,
+{
+    /// Returns the writer registered with the specified key,
+    /// or constructs it with the given function and registers it.
+    ///
+    /// The init function is called at most once for every key and writer type pair,
+    /// even if `get_or_init` is called concurrently.
+    /// It is called without holding any lock, so it can write with other writers,
+    /// but it panics if it tries to use the writer being constructed.
+    pub fn get_or_init<F>(key: &'static str, init: F) -> &'static Self
+    where
+        F: FnOnce() -> W,
+    {
+        let id = (key, TypeId::of::<Self>());
+        loop {
+            let entry = with_writers(|writers| match writers.get(&id) {
+                Some(Entry::Ready(writer)) => Some(Ok(*writer)),
+                #[cfg(feature = "std")]
+                Some(Entry::Initializing(thread)) => {
+                    assert!(
+                        *thread != std::thread::current().id(),
+                        "once: writer `{}` is used by its own init function",
+                        key
+                    );
+                    Some(Err(()))
+                }
+                #[cfg(not(feature = "std"))]
+                Some(Entry::Initializing) => Some(Err(())),
+                None => {
+                    #[cfg(feature = "std")]
+                    let entry = Entry::Initializing(std::thread::current().id());
+                    #[cfg(not(feature = "std"))]
+                    let entry = Entry::Initializing;
+                    let _ = writers.insert(id, entry);
+                    None
+                }
+            });
+            match entry {
+                Some(Ok(writer)) => {
+                    return writer
+                        .downcast_ref::<Self>()
+                        .expect("once: writer type mismatch");
+                }
+                // Another thread is constructing the writer.
+                #[cfg(feature = "std")]
+                Some(Err(())) => std::thread::yield_now(),
+                #[cfg(not(feature = "std"))]
+                Some(Err(())) => core::hint::spin_loop(),
+                None => break,
+            }
+        }
+
+        let guard = InitGuard(id);
+        let writer: &'static Self = Box::leak(Box::new(Self(Lock::new(init()))));
+        core::mem::forget(guard);
+        let _ = with_writers(|writers| writers.insert(id, Entry::Ready(writer)));
+        writer
+    }
+}
+
+/// Removes the initializing entry if the init function panics,
+/// so the construction can be retried.
+struct InitGuard((&'static str, TypeId));
+
+impl Drop for InitGuard {
+    fn drop(&mut self) {
+        let _ = with_writers(|writers| writers.remove(&self.0));
+    }
+}
+
+/// A hidden static emitted at every `once:` writer call site
+/// that caches the shared writer, so the registry is used only on the first call.
+#[doc(hidden)]
+pub struct _OnceWriterSlot(AtomicPtr<&'static (dyn Any + Send + Sync)>);
+
+impl _OnceWriterSlot {
+    #[doc(hidden)]
+    pub const fn new() -> Self {
+        Self(AtomicPtr::new(ptr::null_mut()))
+    }
+
+    #[doc(hidden)]
+    pub fn get_or_init<W, F>(&self, key: &'static str, init: F) -> &'static OnceWriter<W>
+    where
+        W: Send + 'static,
+        F: FnOnce() -> W,
+    {
+        let cached = self.0.load(Ordering::Acquire);
+        if !cached.is_null() {
+            // Cached pointers are leaked and never freed.
+            let writer: &'static (dyn Any + Send + Sync) = unsafe { *cached };
+            // The call site may be in a generic function
+            // and be shared by writers of different types.
+            if let Some(writer) = writer.downcast_ref() {
+                return writer;
+            }
+        }
+        let writer = OnceWriter::get_or_init(key, init);
+        if cached.is_null() {
+            let erased: &'static (dyn Any + Send + Sync) = writer;
+            let erased = Box::into_raw(Box::new(erased));
+            if self
+                .0
+                .compare_exchange(ptr::null_mut(), erased, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+            {
+                drop(unsafe { Box::from_raw(erased) });
+            }
+        }
+        writer
+    }
+}
+
+impl _OnceWriterSlot {
+    #[doc(hidden)]
+    pub fn lock_or_init<W, F>(&self, key: &'static str, init: F) -> OnceWriterGuard<'static, W>
+    where
+        W: Send + 'static,
+        F: Fn() -> W,
+    {
+        self.get_or_init(key, &init).lock_or_else(init)
+    }
+}
+
+impl Debug for _OnceWriterSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("_OnceWriterSlot").finish_non_exhaustive()
+    }
+}
+
+impl<W> OnceWriter<W> {
+    /// Locks the writer, blocking the current thread until it is available.
+    ///
+    /// The lock is not poisoned by a panic that occurs while writing,
+    /// so writing with the `expect` policy can be continued after the failure.
+    /// The lock is not reentrant, so locking the writer again
+    /// while the current thread holds its guard deadlocks or panics.
+    /// Use [`lock_or_else`] if the writer can be used by nested writes.
+    ///
+    /// [`lock_or_else`]: #method.lock_or_else
+    pub fn lock(&self) -> OnceWriterGuard<'_, W> {
+        #[cfg(feature = "std")]
+        let guard = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let guard = self.0.lock();
+        OnceWriterGuard(GuardState::Locked {
+            guard,
+            _held: Held::enter(self),
+        })
+    }
+
+    /// Locks the writer, or returns a guard with the writer constructed by the function
+    /// if the writer is already locked by the current thread.
+    ///
+    /// Without the `std` feature the function is also used
+    /// if the writer is locked by an interrupt handler or another core.
+    pub fn lock_or_else(&self, f: impl FnOnce() -> W) -> OnceWriterGuard<'_, W> {
+        #[cfg(feature = "std")]
+        if Held::is_held(self) {
+            return OnceWriterGuard(GuardState::Nested(f()));
+        }
+        #[cfg(feature = "std")]
+        return self.lock();
+        #[cfg(not(feature = "std"))]
+        return match self.0.try_lock() {
+            Some(guard) => OnceWriterGuard(GuardState::Locked {
+                guard,
+                _held: Held::enter(self),
+            }),
+            None => OnceWriterGuard(GuardState::Nested(f())),
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static HELD: core::cell::RefCell<alloc::vec::Vec<usize>> =
+        const { core::cell::RefCell::new(alloc::vec::Vec::new()) };
+}
+
+/// Marks the writer as locked by the current thread until dropped.
+struct Held {
+    #[cfg(feature = "std")]
+    address: usize,
+}
+
+impl Held {
+    fn enter<W>(writer: &OnceWriter<W>) -> Self {
+        #[cfg(feature = "std")]
+        {
+            let address = Self::address(writer);
+            HELD.with(|held| held.borrow_mut().push(address));
+            Self { address }
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = writer;
+            Self {}
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn is_held<W>(writer: &OnceWriter<W>) -> bool {
+        let address = Self::address(writer);
+        HELD.with(|held| held.borrow().contains(&address))
+    }
+
+    #[cfg(feature = "std")]
+    fn address<W>(writer: &OnceWriter<W>) -> usize {
+        let address: *const OnceWriter<W> = writer;
+        address as usize
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Held {
+    fn drop(&mut self) {
+        let address = self.address;
+        let _ = HELD.try_with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(index) = held.iter().rposition(|&item| item == address) {
+                let _ = held.remove(index);
+            }
+        });
+    }
+}
+
+impl<W> Debug for OnceWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnceWriter").finish_non_exhaustive()
+    }
+}
+
+impl<W> Deref for OnceWriterGuard<'_, W> {
+    type Target = W;
+
+    fn deref(&self) -> &W {
+        match &self.0 {
+            GuardState::Locked { guard, .. } => guard,
+            GuardState::Nested(writer) => writer,
+        }
+    }
+}
+
+impl<W> DerefMut for OnceWriterGuard<'_, W> {
+    fn deref_mut(&mut self) -> &mut W {
+        match &mut self.0 {
+            GuardState::Locked { guard, .. } => guard,
+            GuardState::Nested(writer) => writer,
+        }
+    }
+}
+
+impl<W> Debug for OnceWriterGuard<'_, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnceWriterGuard").finish_non_exhaustive()
+    }
+}
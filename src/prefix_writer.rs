@@ -0,0 +1,310 @@
+use core::cell::Cell;
+use core::fmt::{self, Arguments, Display, Formatter};
+
+use crate::{Flush, WriteFmt};
+
+/// A writer decorator that inserts a prefix at the start of every line
+/// written into the wrapped writer.
+///
+/// The prefix is written before the first character of every line,
+/// so a trailing newline does not produce a prefix until the next line is written.
+/// The prefix can be a string or a closure that returns a value implementing [`Display`],
+/// which is called for every line, like `|| std::thread::current().id()`.
+///
+/// The writer formats the message with the prefixes through a single [`format_args!`] value,
+/// so it works with any writer that implements [`WriteFmt`],
+/// and [`ConcatWriter`] still writes the whole message at once.
+///
+/// Whether the last written line is complete is stored in the writer itself
+/// unless the writer has a key specified by [`with_key`].
+/// Writers with the same key share the state, so they continue partial lines
+/// even if they are constructed separately.
+/// The shared state is thread-local if the `std` feature is enabled,
+/// or a static state protected by a spin lock otherwise.
+/// If the `alloc` feature is disabled, at most 32 partial lines with different keys
+/// are tracked at once, and other partial lines are continued with a prefix.
+/// The `prefix(prefix), args...` mode of [`define_writer`] and [`define_try_writer`]
+/// uses the module path of the call site and the stringified prefix and writer arguments
+/// as the key, because the arguments are resolved in the module of the call site.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::{ConcatWriter, PrefixWriter};
+///
+/// let mut chunks = Vec::new();
+/// let mut write = |value: &str| chunks.push(value.to_owned());
+/// let mut writer = PrefixWriter::new("[net] ", ConcatWriter::from_closure(&mut write))
+///     .with_key("example");
+/// writer.write_fmt(format_args!("first\nsecond")).unwrap();
+/// writer.write_fmt(format_args!(" third\n")).unwrap();
+/// drop(writer);
+///
+/// assert_eq!(chunks, ["[net] first\n[net] second", " third\n"]);
+/// ```
+///
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+/// [`WriteFmt`]: trait.WriteFmt.html
+/// [`ConcatWriter`]: struct.ConcatWriter.html
+/// [`with_key`]: struct.PrefixWriter.html#method.with_key
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PrefixWriter<P, W>(LineState, P, W);
+
+/// The state of the last line written by a [`PrefixWriter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LineState {
+    /// The state shared by the writers with the same key.
+    Shared(&'static str),
+    /// The state of the writer instance, `true` if the last line is partial.
+    Instance(bool),
+}
+
+/// A trait for line prefixes used by [`PrefixWriter`].
+///
+/// It is implemented for string slices and for closures
+/// that return a value implementing [`Display`].
+///
+/// [`PrefixWriter`]: struct.PrefixWriter.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+pub trait LinePrefix {
+    /// Formats the prefix of a line.
+    fn fmt_prefix(&self, f: &mut Formatter<'_>) -> fmt::Result;
+}
+
+impl LinePrefix for &str {
+    fn fmt_prefix(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl<F, D> LinePrefix for F
+where
+    F: Fn() -> D,
+    D: Display,
+{
+    fn fmt_prefix(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self(), f)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+const MAX_PARTIAL_LINES: usize = 32;
+
+/// The keys of the writers that have written a partial line.
+#[cfg(feature = "alloc")]
+struct PartialLines(alloc::vec::Vec<&'static str>);
+
+#[cfg(feature = "alloc")]
+impl PartialLines {
+    const fn new() -> Self {
+        Self(alloc::vec::Vec::new())
+    }
+
+    fn contains(&self, key: &'static str) -> bool {
+        self.0.contains(&key)
+    }
+
+    fn set(&mut self, key: &'static str, is_partial: bool) {
+        let index = self.0.iter().position(|&item| item == key);
+        match (index, is_partial) {
+            (Some(index), false) => {
+                let _ = self.0.swap_remove(index);
+            }
+            (None, true) => self.0.push(key),
+            _ => {}
+        }
+    }
+}
+
+/// The keys of the writers that have written a partial line.
+#[cfg(not(feature = "alloc"))]
+struct PartialLines {
+    keys: [&'static str; MAX_PARTIAL_LINES],
+    len: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl PartialLines {
+    const fn new() -> Self {
+        Self {
+            keys: [""; MAX_PARTIAL_LINES],
+            len: 0,
+        }
+    }
+
+    fn contains(&self, key: &'static str) -> bool {
+        self.keys[..self.len].contains(&key)
+    }
+
+    fn set(&mut self, key: &'static str, is_partial: bool) {
+        let index = self.keys[..self.len].iter().position(|&item| item == key);
+        match (index, is_partial) {
+            (Some(index), false) => {
+                self.len -= 1;
+                self.keys.swap(index, self.len);
+            }
+            (None, true) if self.len < MAX_PARTIAL_LINES => {
+                self.keys[self.len] = key;
+                self.len += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static PARTIAL_LINES: core::cell::RefCell<PartialLines> =
+        const { core::cell::RefCell::new(PartialLines::new()) };
+}
+
+#[cfg(not(feature = "std"))]
+static PARTIAL_LINES: crate::spin_lock::SpinLock<PartialLines> =
+    crate::spin_lock::SpinLock::new(PartialLines::new());
+
+fn with_partial_lines<R>(f: impl FnOnce(&mut PartialLines) -> R) -> R {
+    #[cfg(feature = "std")]
+    return PARTIAL_LINES.with(|lines| f(&mut lines.borrow_mut()));
+    #[cfg(not(feature = "std"))]
+    return f(&mut PARTIAL_LINES.lock());
+}
+
+impl<P, W> PrefixWriter<P, W>
+where
+    P: LinePrefix,
+{
+    /// Creates a new `PrefixWriter` with the specified prefix and wrapped writer.
+    pub fn new(prefix: P, writer: W) -> Self {
+        Self(LineState::Instance(false), prefix, writer)
+    }
+}
+
+impl<P, W> PrefixWriter<P, W> {
+    /// Returns the writer that uses the line state shared by the writers with the specified key.
+    ///
+    /// Writers created without a key keep the line state in the writer itself.
+    pub fn with_key(self, key: &'static str) -> Self {
+        Self(LineState::Shared(key), self.1, self.2)
+    }
+
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.2
+    }
+}
+
+impl<P, W> PrefixWriter<P, W>
+where
+    Self: WriteFmt,
+{
+    /// Writes a formatted string with line prefixes into the wrapped writer.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteFmt>::Output {
+        WriteFmt::write_fmt(self, args)
+    }
+}
+
+impl<P, W> PrefixWriter<P, W>
+where
+    Self: Flush,
+{
+    /// Flushes the wrapped writer.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<P, W> WriteFmt for PrefixWriter<P, W>
+where
+    P: LinePrefix,
+    W: WriteFmt,
+{
+    type Output = W::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        let is_partial = match self.0 {
+            LineState::Shared(key) => with_partial_lines(|lines| lines.contains(key)),
+            LineState::Instance(is_partial) => is_partial,
+        };
+        let prefixed = Prefixed {
+            prefix: &self.1,
+            args,
+            is_partial,
+            is_partial_after: Cell::new(None),
+        };
+        let output = self.2.write_fmt(format_args!("{}", prefixed));
+        if let Some(is_partial) = prefixed.is_partial_after.get() {
+            match self.0 {
+                LineState::Shared(key) => with_partial_lines(|lines| lines.set(key, is_partial)),
+                LineState::Instance(_) => self.0 = LineState::Instance(is_partial),
+            }
+        }
+        output
+    }
+}
+
+impl<P, W> Flush for PrefixWriter<P, W>
+where
+    W: Flush,
+{
+    type Output = W::Output;
+
+    fn flush(&mut self) -> Self::Output {
+        self.2.flush()
+    }
+}
+
+/// Formats the arguments with line prefixes.
+///
+/// It starts from the same line state every time it is formatted,
+/// so it can be safely formatted several times by writers like [`TeeWriter`].
+///
+/// [`TeeWriter`]: struct.TeeWriter.html
+struct Prefixed<'a, P> {
+    prefix: &'a P,
+    args: Arguments<'a>,
+    is_partial: bool,
+    is_partial_after: Cell<Option<bool>>,
+}
+
+impl<P: LinePrefix> Display for Prefixed<'_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = PrefixedFormatter {
+            prefix: self.prefix,
+            f,
+            is_partial: self.is_partial,
+        };
+        let result = fmt::write(&mut writer, self.args);
+        self.is_partial_after.set(Some(writer.is_partial));
+        result
+    }
+}
+
+struct PrefixedFormatter<'a, 'b, P> {
+    prefix: &'a P,
+    f: &'a mut Formatter<'b>,
+    is_partial: bool,
+}
+
+impl<P: LinePrefix> fmt::Write for PrefixedFormatter<'_, '_, P> {
+    fn write_str(&mut self, buf: &str) -> fmt::Result {
+        for line in buf.split_inclusive('\n') {
+            if !self.is_partial {
+                self.prefix.fmt_prefix(self.f)?;
+            }
+            self.f.write_str(line)?;
+            self.is_partial = !line.ends_with('\n');
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use core::fmt::{self, Display, Formatter};
+
+use crate::Level;
+
+/// A message written by a macro together with its call-site metadata.
+///
+/// Write closures that take `&Record` are wrapped into [`WriteRecordFn`]
+/// by [`IntoWriteFn`] and [`IntoTryWriteFn`].
+/// The metadata is filled in by the macros defined with [`define_macro`], [`define_macros`]
+/// and other macro-defining macros of this crate,
+/// so sinks can route, colour or structure output by its origin without parsing text.
+///
+/// If a writer is used directly rather than from such macro,
+/// the record has [`RecordKind::Unknown`] kind, empty file and module path and zero position.
+///
+/// Every macro call stores a pointer to its static call-site metadata
+/// in a thread-local variable if the `std` feature is enabled,
+/// or in a static atomic pointer otherwise.
+/// The previous pointer is restored when the macro returns, so nested writes,
+/// including ones made from interrupt handlers, get their own metadata.
+/// Without the `std` feature, records written concurrently from several cores
+/// may get the metadata of each other.
+///
+/// A record is passed to the write closure for every written chunk,
+/// so it contains the whole message only with writers like [`ConcatWriter`]
+/// that write the message at once.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::{Record, RecordKind};
+///
+/// let mut records = Vec::new();
+/// custom_print::define_macros!({ cprintln, ceprintln }, concat, |record: &Record| {
+///     records.push((record.kind(), record.line(), record.message().to_owned()))
+/// });
+///
+/// cprintln!("first");
+/// ceprintln!("second");
+/// assert_eq!(
+///     records,
+///     [
+///         (RecordKind::Print, line!() - 5, "first\n".to_owned()),
+///         (RecordKind::Eprint, line!() - 5, "second\n".to_owned())
+///     ]
+/// );
+/// ```
+///
+/// [`WriteRecordFn`]: struct.WriteRecordFn.html
+/// [`IntoWriteFn`]: trait.IntoWriteFn.html
+/// [`IntoTryWriteFn`]: trait.IntoTryWriteFn.html
+/// [`define_macro`]: macro.define_macro.html
+/// [`define_macros`]: macro.define_macros.html
+/// [`ConcatWriter`]: struct.ConcatWriter.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Record<'a> {
+    metadata: &'static _RecordMetadata,
+    message: &'a str,
+}
+
+/// A kind of the macro template that wrote a [`Record`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RecordKind {
+    /// A `print`-like macro that is proposed to be used with `stdout`-like writers,
+    /// like `print`, `println`, `cprint` or `try_println`.
+    Print,
+    /// A `print`-like macro that is proposed to be used with `stderr`-like writers,
+    /// like `eprint`, `eprintln`, `ceprint` or `try_eprintln`.
+    Eprint,
+    /// A `dbg`-like macro that is proposed to be used with `stdout`-like writers,
+    /// like `dbg`, `cdbg` or `try_dbg`.
+    Dbg,
+    /// A `dbg`-like macro that is proposed to be used with `stderr`-like writers,
+    /// like `edbg`, `try_edbg` or `quiet_edbg`.
+    Edbg,
+    /// A logging macro with the specified level.
+    Log(Level),
+    /// The writer was used directly rather than from a macro defined by this crate.
+    Unknown,
+}
+
+/// The call-site metadata of a macro call.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct _RecordMetadata {
+    file: &'static str,
+    line: u32,
+    column: u32,
+    module_path: &'static str,
+    kind: RecordKind,
+}
+
+impl _RecordMetadata {
+    #[doc(hidden)]
+    pub const fn new(
+        file: &'static str,
+        line: u32,
+        column: u32,
+        module_path: &'static str,
+        kind: RecordKind,
+    ) -> Self {
+        Self {
+            file,
+            line,
+            column,
+            module_path,
+            kind,
+        }
+    }
+}
+
+static UNKNOWN: _RecordMetadata = _RecordMetadata::new("", 0, 0, "", RecordKind::Unknown);
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CURRENT: core::cell::Cell<&'static _RecordMetadata> =
+        const { core::cell::Cell::new(&UNKNOWN) };
+}
+
+#[cfg(not(feature = "std"))]
+static CURRENT: core::sync::atomic::AtomicPtr<_RecordMetadata> =
+    core::sync::atomic::AtomicPtr::new(core::ptr::null_mut());
+
+fn replace_current(metadata: &'static _RecordMetadata) -> &'static _RecordMetadata {
+    #[cfg(feature = "std")]
+    return CURRENT.with(|current| current.replace(metadata));
+    #[cfg(not(feature = "std"))]
+    {
+        // Load and store are used instead of swap, which is not supported by all targets.
+        // An interrupt handler between them restores the current value before returning.
+        let previous = current();
+        let metadata: *const _RecordMetadata = metadata;
+        CURRENT.store(metadata as *mut _, core::sync::atomic::Ordering::Release);
+        previous
+    }
+}
+
+fn current() -> &'static _RecordMetadata {
+    #[cfg(feature = "std")]
+    return CURRENT.with(|current| current.get());
+    #[cfg(not(feature = "std"))]
+    {
+        let current = CURRENT.load(core::sync::atomic::Ordering::Acquire);
+        // The pointer is either null or points to a static call-site metadata.
+        unsafe { current.as_ref() }.unwrap_or(&UNKNOWN)
+    }
+}
+
+/// A guard that sets the call-site metadata for records written until it is dropped.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct _RecordScope(&'static _RecordMetadata);
+
+impl _RecordScope {
+    #[doc(hidden)]
+    pub fn enter(metadata: &'static _RecordMetadata) -> Self {
+        Self(replace_current(metadata))
+    }
+}
+
+impl Drop for _RecordScope {
+    fn drop(&mut self) {
+        let _ = replace_current(self.0);
+    }
+}
+
+impl<'a> Record<'a> {
+    /// Creates a record with the specified message and the current call-site metadata.
+    pub(crate) fn current(message: &'a str) -> Self {
+        Self {
+            metadata: current(),
+            message,
+        }
+    }
+
+    /// Returns the written message.
+    pub fn message(&self) -> &'a str {
+        self.message
+    }
+
+    /// Returns the name of the source file where the macro was called.
+    pub fn file(&self) -> &'static str {
+        self.metadata.file
+    }
+
+    /// Returns the line number where the macro was called.
+    pub fn line(&self) -> u32 {
+        self.metadata.line
+    }
+
+    /// Returns the column number where the macro was called.
+    pub fn column(&self) -> u32 {
+        self.metadata.column
+    }
+
+    /// Returns the module path where the macro was called.
+    pub fn module_path(&self) -> &'static str {
+        self.metadata.module_path
+    }
+
+    /// Returns the kind of the macro template that wrote the record.
+    pub fn kind(&self) -> RecordKind {
+        self.metadata.kind
+    }
+}
+
+impl Display for Record<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
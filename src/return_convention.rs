@@ -0,0 +1,281 @@
+use core::fmt::{self, Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{
+    Flush, IntoFlushFn, IntoTryWriteFn, IntoWriteFn, NeverError, WriteBytes, WriteFmt, WriteStr,
+};
+
+/// A trait for write and flush function results that follow a non-`Result` return convention.
+///
+/// It is implemented for the results of functions wrapped into
+/// [`Errno`], [`ErrnoCount`], [`Flag`], [`Presence`] and [`Ignored`],
+/// and allows all writers to map these results onto success,
+/// written bytes count and errors in the same way.
+///
+/// [`Errno`]: struct.Errno.html
+/// [`ErrnoCount`]: struct.ErrnoCount.html
+/// [`Flag`]: struct.Flag.html
+/// [`Presence`]: struct.Presence.html
+/// [`Ignored`]: struct.Ignored.html
+pub trait ReturnConvention {
+    /// The resulting error type after convertion.
+    type Error: Debug;
+
+    /// Converts the result into the written bytes count if it is known, or into an error.
+    fn into_return_result(self) -> Result<Option<usize>, Self::Error>;
+}
+
+/// An error returned by write and flush functions that follow a non-`Result` return convention.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ReturnError<E = NeverError> {
+    /// The function returned a negative error code.
+    Errno(i64),
+    /// The function returned `false`.
+    Failed,
+    /// The function returned `None`.
+    Missing,
+    /// The function wrapper returned an error before or after calling the function,
+    /// like [`NulError`] returned by fallible C-string writers.
+    ///
+    /// [`NulError`]: https://doc.rust-lang.org/std/ffi/struct.NulError.html
+    Inner(E),
+}
+
+/// A write or flush function wrapper for functions that return `i32`, `i64` or `isize`,
+/// where negative values are error codes and non-negative values mean success.
+///
+/// Non-negative values are not treated as written bytes counts,
+/// so the whole buffer is considered written.
+/// Use [`ErrnoCount`] for functions that return written bytes counts.
+///
+/// It can be specified with the `errno:` prefix in [`define_writer_expr`].
+///
+/// [`ErrnoCount`]: struct.ErrnoCount.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Errno<T>(pub T);
+
+/// A write or flush function wrapper for functions that return `i32`, `i64` or `isize`,
+/// where negative values are error codes and non-negative values are written bytes counts,
+/// like POSIX `write`.
+///
+/// Writers that support partial writes, like [`IoWriter`] and [`IoTryWriter`],
+/// use the written bytes count, so returned zero is reported as a write of zero bytes.
+///
+/// It can be specified with the `errno_count:` prefix in [`define_writer_expr`].
+///
+/// [`IoWriter`]: struct.IoWriter.html
+/// [`IoTryWriter`]: struct.IoTryWriter.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ErrnoCount<T>(pub T);
+
+/// A write or flush function wrapper for functions that return `bool`,
+/// where `false` means failure.
+///
+/// It can be specified with the `bool:` prefix in [`define_writer_expr`].
+///
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Flag<T>(pub T);
+
+/// A write or flush function wrapper for functions that return [`Option`],
+/// where `None` means failure.
+///
+/// It can be specified with the `option:` prefix in [`define_writer_expr`].
+///
+/// [`Option`]: https://doc.rust-lang.org/std/option/enum.Option.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Presence<T>(pub T);
+
+/// A write or flush function wrapper for functions that return a value
+/// that should be ignored, like a JS `Promise`.
+///
+/// Note that errors returned by the function wrapper itself are ignored too,
+/// like [`NulError`] returned by fallible C-string writers.
+///
+/// It can be specified with the `ignore:` prefix in [`define_writer_expr`].
+///
+/// [`NulError`]: https://doc.rust-lang.org/std/ffi/struct.NulError.html
+/// [`define_writer_expr`]: macro.define_writer_expr.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Ignored<T>(pub T);
+
+macro_rules! define_convention_fn {
+    ($name:ident) => {
+        impl<F, Ts> IntoWriteFn<Ts> for $name<F>
+        where
+            F: IntoWriteFn<Ts>,
+        {
+            type WriteFn = $name<F::WriteFn>;
+            fn into_write_fn(self) -> Self::WriteFn {
+                $name(self.0.into_write_fn())
+            }
+        }
+
+        impl<F, Ts> IntoTryWriteFn<Ts> for $name<F>
+        where
+            F: IntoTryWriteFn<Ts>,
+        {
+            type TryWriteFn = $name<F::TryWriteFn>;
+            fn into_try_write_fn(self) -> Self::TryWriteFn {
+                $name(self.0.into_try_write_fn())
+            }
+        }
+
+        impl<F> IntoFlushFn for $name<F>
+        where
+            F: IntoFlushFn,
+        {
+            type FlushFn = $name<F::FlushFn>;
+            fn into_flush_fn(self) -> Self::FlushFn {
+                $name(self.0.into_flush_fn())
+            }
+        }
+
+        impl<F: WriteStr> WriteStr for $name<F> {
+            type Output = $name<F::Output>;
+
+            fn write_str(&mut self, buf: &str) -> Self::Output {
+                $name(self.0.write_str(buf))
+            }
+        }
+
+        impl<F: WriteBytes> WriteBytes for $name<F> {
+            type Output = $name<F::Output>;
+
+            fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+                $name(self.0.write_bytes(buf))
+            }
+        }
+
+        impl<F: WriteFmt> WriteFmt for $name<F> {
+            type Output = $name<F::Output>;
+
+            fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> Self::Output {
+                $name(self.0.write_fmt(args))
+            }
+        }
+
+        impl<F: Flush> Flush for $name<F> {
+            type Output = $name<F::Output>;
+
+            fn flush(&mut self) -> Self::Output {
+                $name(self.0.flush())
+            }
+        }
+    };
+}
+
+macro_rules! define_result_convention {
+    ($name:ident) => {
+        impl<T, E: Debug> ReturnConvention for $name<Result<T, E>>
+        where
+            $name<T>: ReturnConvention<Error = ReturnError>,
+        {
+            type Error = ReturnError<E>;
+
+            fn into_return_result(self) -> Result<Option<usize>, Self::Error> {
+                match self.0 {
+                    Ok(value) => $name(value).into_return_result().map_err(ReturnError::cast),
+                    Err(err) => Err(ReturnError::Inner(err)),
+                }
+            }
+        }
+    };
+}
+
+define_convention_fn!(Errno);
+define_convention_fn!(ErrnoCount);
+define_convention_fn!(Flag);
+define_convention_fn!(Presence);
+define_convention_fn!(Ignored);
+
+define_result_convention!(Errno);
+define_result_convention!(ErrnoCount);
+define_result_convention!(Flag);
+define_result_convention!(Presence);
+
+macro_rules! define_errno {
+    ($($ty:ty),*) => {
+        $(
+            impl ReturnConvention for Errno<$ty> {
+                type Error = ReturnError;
+
+                fn into_return_result(self) -> Result<Option<usize>, ReturnError> {
+                    ErrnoCount(self.0).into_return_result().map(|_| None)
+                }
+            }
+
+            impl ReturnConvention for ErrnoCount<$ty> {
+                type Error = ReturnError;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn into_return_result(self) -> Result<Option<usize>, ReturnError> {
+                    if self.0 < 0 {
+                        Err(ReturnError::Errno(self.0 as i64))
+                    } else {
+                        Ok(Some(self.0 as usize))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+define_errno!(i32, i64, isize);
+
+impl ReturnConvention for Flag<bool> {
+    type Error = ReturnError;
+
+    fn into_return_result(self) -> Result<Option<usize>, ReturnError> {
+        if self.0 {
+            Ok(None)
+        } else {
+            Err(ReturnError::Failed)
+        }
+    }
+}
+
+impl<T> ReturnConvention for Presence<Option<T>> {
+    type Error = ReturnError;
+
+    fn into_return_result(self) -> Result<Option<usize>, ReturnError> {
+        self.0.map(|_| None).ok_or(ReturnError::Missing)
+    }
+}
+
+impl<T> ReturnConvention for Ignored<T> {
+    type Error = NeverError;
+
+    fn into_return_result(self) -> Result<Option<usize>, NeverError> {
+        Ok(None)
+    }
+}
+
+impl ReturnError {
+    fn cast<E>(self) -> ReturnError<E> {
+        match self {
+            Self::Errno(code) => ReturnError::Errno(code),
+            Self::Failed => ReturnError::Failed,
+            Self::Missing => ReturnError::Missing,
+            Self::Inner(never) => match never {},
+        }
+    }
+}
+
+impl<E: Display> Display for ReturnError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Errno(code) => write!(f, "function returned error code {}", code),
+            Self::Failed => f.write_str("function returned false"),
+            Self::Missing => f.write_str("function returned none"),
+            Self::Inner(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Debug + Display> Error for ReturnError<E> {}
@@ -0,0 +1,210 @@
+use core::fmt::{self, Arguments, Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{Flush, NeverError, WriteBytes, WriteFmt, WriteStr};
+
+/// A writer that writes the same data into two writers.
+///
+/// Both writers are always written, even if the first one returns an error.
+/// Errors are merged into [`TeeError`].
+/// Writer outputs can be either `()` or `for<T, E> `[`Result`]`<T, E>`.
+///
+/// More than two writers can be combined by nesting, like `TeeWriter<A, TeeWriter<B, C>>`.
+/// The `tee(args...; args...; ...)` mode of [`define_writer`] and [`define_try_writer`]
+/// does it for any number of writers.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```rust,compile_fail")]
+/// use custom_print::{ConcatWriter, FmtWriter, TeeWriter};
+///
+/// let mut concat_chunks = Vec::new();
+/// let mut fmt_chunks = Vec::new();
+/// let mut writer = TeeWriter::new(
+///     ConcatWriter::from_closure(|value: &str| concat_chunks.push(value.to_owned())),
+///     FmtWriter::from_closure(|value: &str| fmt_chunks.push(value.to_owned())),
+/// );
+/// let second = "second";
+/// writer.write_fmt(format_args!("first {}", second)).unwrap();
+/// drop(writer);
+///
+/// assert_eq!(concat_chunks, ["first second"]);
+/// assert_eq!(fmt_chunks, ["first ", "second"]);
+/// ```
+///
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TeeWriter<A, B>(A, B);
+
+/// An error returned by [`TeeWriter`] if any of its writers returns an error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TeeError<A, B> {
+    /// Only the first writer returned an error.
+    First(A),
+    /// Only the second writer returned an error.
+    Second(B),
+    /// Both writers returned errors.
+    Both(A, B),
+}
+
+/// A helper trait used by [`TeeWriter`]
+/// to convert writer outputs to [`Result`]`<(), E>` with error propagation.
+///
+/// [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
+pub trait IntoTeeResult {
+    /// The resulting error type after convertion.
+    type Error;
+
+    /// Performs the conversion with error propagation.
+    fn into_tee_result(self) -> Result<(), Self::Error>;
+}
+
+type TeeResult<A, B> =
+    Result<(), TeeError<<A as IntoTeeResult>::Error, <B as IntoTeeResult>::Error>>;
+
+impl<A, B> TeeWriter<A, B> {
+    /// Creates a new `TeeWriter` that writes into both specified writers.
+    pub fn new(first: A, second: B) -> Self {
+        Self(first, second)
+    }
+
+    /// Returns the wrapped writers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.0, self.1)
+    }
+}
+
+impl<A, B> TeeWriter<A, B>
+where
+    Self: WriteFmt,
+{
+    /// Writes a formatted string into both writers, returning any error encountered.
+    ///
+    /// This method is primarily used to interface with the [`format_args!`] macro,
+    /// but it is rare that this should explicitly be called.
+    /// The [`write!`] macro should be favored to invoke this method instead.
+    ///
+    /// [`write!`]: https://doc.rust-lang.org/std/macro.write.html
+    /// [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+    pub fn write_fmt(&mut self, args: Arguments<'_>) -> <Self as WriteFmt>::Output {
+        WriteFmt::write_fmt(self, args)
+    }
+}
+
+impl<A, B> TeeWriter<A, B>
+where
+    Self: Flush,
+{
+    /// Flushes both writers, returning any error encountered.
+    pub fn flush(&mut self) -> <Self as Flush>::Output {
+        Flush::flush(self)
+    }
+}
+
+impl<A, B> WriteFmt for TeeWriter<A, B>
+where
+    A: WriteFmt,
+    A::Output: IntoTeeResult,
+    B: WriteFmt,
+    B::Output: IntoTeeResult,
+{
+    type Output = TeeResult<A::Output, B::Output>;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        let first = self.0.write_fmt(args).into_tee_result();
+        let second = self.1.write_fmt(args).into_tee_result();
+        TeeError::merge(first, second)
+    }
+}
+
+impl<A, B> WriteStr for TeeWriter<A, B>
+where
+    A: WriteStr,
+    A::Output: IntoTeeResult,
+    B: WriteStr,
+    B::Output: IntoTeeResult,
+{
+    type Output = TeeResult<A::Output, B::Output>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        let first = self.0.write_str(buf).into_tee_result();
+        let second = self.1.write_str(buf).into_tee_result();
+        TeeError::merge(first, second)
+    }
+}
+
+impl<A, B> WriteBytes for TeeWriter<A, B>
+where
+    A: WriteBytes,
+    A::Output: IntoTeeResult,
+    B: WriteBytes,
+    B::Output: IntoTeeResult,
+{
+    type Output = TeeResult<A::Output, B::Output>;
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+        let first = self.0.write_bytes(buf).into_tee_result();
+        let second = self.1.write_bytes(buf).into_tee_result();
+        TeeError::merge(first, second)
+    }
+}
+
+impl<A, B> Flush for TeeWriter<A, B>
+where
+    A: Flush,
+    A::Output: IntoTeeResult,
+    B: Flush,
+    B::Output: IntoTeeResult,
+{
+    type Output = TeeResult<A::Output, B::Output>;
+
+    fn flush(&mut self) -> Self::Output {
+        let first = self.0.flush().into_tee_result();
+        let second = self.1.flush().into_tee_result();
+        TeeError::merge(first, second)
+    }
+}
+
+impl<A, B> TeeError<A, B> {
+    fn merge(first: Result<(), A>, second: Result<(), B>) -> Result<(), Self> {
+        match (first, second) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(first), Ok(())) => Err(Self::First(first)),
+            (Ok(()), Err(second)) => Err(Self::Second(second)),
+            (Err(first), Err(second)) => Err(Self::Both(first, second)),
+        }
+    }
+}
+
+impl<A: Display, B: Display> Display for TeeError<A, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::First(first) => write!(f, "first writer failed: {}", first),
+            Self::Second(second) => write!(f, "second writer failed: {}", second),
+            Self::Both(first, second) => {
+                write!(f, "both writers failed: {}; {}", first, second)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: Debug + Display, B: Debug + Display> Error for TeeError<A, B> {}
+
+impl IntoTeeResult for () {
+    type Error = NeverError;
+    fn into_tee_result(self) -> Result<(), NeverError> {
+        Ok(())
+    }
+}
+
+impl<T, E> IntoTeeResult for Result<T, E> {
+    type Error = E;
+    fn into_tee_result(self) -> Result<(), E> {
+        self.map(|_| ())
+    }
+}
@@ -0,0 +1,256 @@
+//! Utilities to test code that uses the defined macros.
+//!
+//! [`Recorder`] is a sink that records every write and flush call separately,
+//! so the chunking of the written messages and the flushes are visible in tests.
+//! It can be used as a [`GlobalSink`] sink, or the output can be recorded
+//! by the innermost [`capture`] call on the current thread.
+//! A capture records the output of the macros defined with the `capture` writer mode
+//! and overrides the sinks of all [`GlobalSink`] values, including [`GLOBAL_SINK`],
+//! so the existing macros defined with the `global` writer mode are recorded too.
+//! The [`assert_printed`] and [`assert_chunks`] macros compare the recorded output.
+//!
+//! This module requires the `testing` feature.
+//!
+//! # Examples
+//!
+#![cfg_attr(feature = "testing", doc = "```rust")]
+#![cfg_attr(not(feature = "testing"), doc = "```rust,compile_fail")]
+//! use custom_print::testing::capture;
+//! use custom_print::{assert_chunks, assert_printed};
+//!
+//! custom_print::define_macros!({ cprint, cprintln, flush }, capture);
+//!
+//! fn main() {
+//!     let value = 1;
+//!     let recorder = capture(|| {
+//!         cprint!("first {}", value);
+//!         flush!();
+//!         cprintln!("second");
+//!     });
+//!
+//!     assert_printed!(recorder, "first {}second\n", 1);
+//!     assert_chunks!(recorder, ["first ", "1", "second\n"]);
+//!     assert_eq!(recorder.flush_count(), 1);
+//! }
+//! ```
+//!
+//! [`Recorder`]: struct.Recorder.html
+//! [`GlobalSink`]: ../struct.GlobalSink.html
+//! [`GLOBAL_SINK`]: ../static.GLOBAL_SINK.html
+//! [`capture`]: fn.capture.html
+//! [`assert_printed`]: ../macro.assert_printed.html
+//! [`assert_chunks`]: ../macro.assert_chunks.html
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::Arguments;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{Flush, IntoWriteFn, WriteStr};
+
+/// A sink call recorded by [`Recorder`].
+///
+/// [`Recorder`]: struct.Recorder.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Event {
+    /// A write call with the written string.
+    Write(String),
+    /// A flush call.
+    Flush,
+}
+
+/// A sink that records every write and flush call.
+///
+/// Clones of the recorder share the recorded events,
+/// so a clone can be passed to a writer while the original one is used for assertions.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "testing", doc = "```rust")]
+#[cfg_attr(not(feature = "testing"), doc = "```rust,compile_fail")]
+/// use custom_print::testing::{Event, Recorder};
+/// use custom_print::GLOBAL_SINK;
+///
+/// custom_print::define_macro!(cprint, global);
+///
+/// fn main() {
+///     let value = 1;
+///     let recorder = Recorder::new();
+///     GLOBAL_SINK.with_sink(recorder.clone(), || cprint!("first {}", value));
+///
+///     assert_eq!(recorder.text(), "first 1");
+///     assert_eq!(recorder.write_count(), 2);
+///     assert_eq!(
+///         recorder.events(),
+///         [Event::Write("first ".to_owned()), Event::Write("1".to_owned())]
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Recorder(Arc<Mutex<Vec<Event>>>);
+
+impl Recorder {
+    /// Creates a new empty `Recorder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Event>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records a write call.
+    pub fn write(&self, buf: &str) {
+        self.lock().push(Event::Write(buf.into()));
+    }
+
+    /// Records a flush call.
+    pub fn flush(&self) {
+        self.lock().push(Event::Flush);
+    }
+
+    /// Returns all recorded events.
+    pub fn events(&self) -> Vec<Event> {
+        self.lock().clone()
+    }
+
+    /// Returns the strings of all recorded write calls.
+    pub fn chunks(&self) -> Vec<String> {
+        self.lock()
+            .iter()
+            .filter_map(|event| match event {
+                Event::Write(chunk) => Some(chunk.clone()),
+                Event::Flush => None,
+            })
+            .collect()
+    }
+
+    /// Returns the concatenated strings of all recorded write calls.
+    pub fn text(&self) -> String {
+        self.chunks().concat()
+    }
+
+    /// Returns the number of recorded write calls.
+    pub fn write_count(&self) -> usize {
+        self.lock()
+            .iter()
+            .filter(|event| matches!(event, Event::Write(_)))
+            .count()
+    }
+
+    /// Returns the number of recorded flush calls.
+    pub fn flush_count(&self) -> usize {
+        self.lock()
+            .iter()
+            .filter(|event| matches!(event, Event::Flush))
+            .count()
+    }
+
+    /// Removes and returns all recorded events.
+    pub fn take_events(&self) -> Vec<Event> {
+        core::mem::take(&mut *self.lock())
+    }
+
+    /// Removes all recorded events.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+}
+
+impl WriteStr for Recorder {
+    type Output = ();
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        Recorder::write(self, buf);
+    }
+}
+
+impl Flush for Recorder {
+    type Output = ();
+
+    fn flush(&mut self) -> Self::Output {
+        Recorder::flush(self);
+    }
+}
+
+impl IntoWriteFn<(Recorder,)> for Recorder {
+    type WriteFn = Self;
+
+    fn into_write_fn(self) -> Self::WriteFn {
+        self
+    }
+}
+
+std::thread_local! {
+    static CAPTURES: RefCell<Vec<Recorder>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calls the function and returns the recorder with the output
+/// written by the macros defined with the `capture` writer mode
+/// and written into any [`GlobalSink`].
+///
+/// Captures are thread-local, so tests running in parallel do not see each other output.
+/// If captures are nested, only the innermost one records the output.
+/// While a capture is active, the sinks of [`GlobalSink`] values are not called
+/// by the current thread.
+/// The output of the `capture` writer mode written outside of any capture is discarded.
+///
+/// [`GlobalSink`]: ../struct.GlobalSink.html
+pub fn capture(f: impl FnOnce()) -> Recorder {
+    let recorder = Recorder::new();
+    CAPTURES.with(|captures| captures.borrow_mut().push(recorder.clone()));
+    let _guard = CaptureGuard;
+    f();
+    recorder
+}
+
+/// Records a write call into the innermost capture of the current thread.
+///
+/// This function is used by the `capture` writer mode.
+pub fn write(buf: &str) {
+    let _ = with_capture(|recorder| recorder.write(buf));
+}
+
+/// Records a flush call into the innermost capture of the current thread.
+///
+/// This function is used by the `capture` writer mode.
+pub fn flush() {
+    let _ = with_capture(Recorder::flush);
+}
+
+/// Records a write call into the innermost capture of the current thread
+/// and returns `true` if there is one.
+///
+/// This function is used by [`GlobalSink`] to override its sink.
+///
+/// [`GlobalSink`]: ../struct.GlobalSink.html
+pub(crate) fn write_captured(buf: &str) -> bool {
+    with_capture(|recorder| recorder.write(buf))
+}
+
+fn with_capture(f: impl FnOnce(&Recorder)) -> bool {
+    match CAPTURES.with(|captures| captures.borrow().last().cloned()) {
+        Some(recorder) => {
+            f(&recorder);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Ends the capture when dropped, even if the captured function panics.
+struct CaptureGuard;
+
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        let _ = CAPTURES.with(|captures| captures.borrow_mut().pop());
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn _assert_printed(recorder: &Recorder, expected: Arguments<'_>) {
+    assert_eq!(recorder.text(), alloc::fmt::format(expected));
+}
@@ -0,0 +1,105 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::multi_write_result::message_result;
+use crate::MultiWriteResult;
+
+/// The length of the stack buffer in UTF-16 code units
+/// used to transcode messages without allocations.
+const CHUNK_LEN: usize = 256;
+
+/// An error returned by [`TryWriteUtf16PtrFn`] if the message contains a nul character
+/// and cannot be written as a nul-terminated UTF-16 string.
+///
+/// [`TryWriteUtf16PtrFn`]: struct.TryWriteUtf16PtrFn.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Utf16NulError(usize);
+
+impl Utf16NulError {
+    /// Returns the byte position of the nul character in the UTF-8 message.
+    pub fn nul_position(&self) -> usize {
+        self.0
+    }
+}
+
+impl Display for Utf16NulError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "nul character found in provided data at position: {}",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Utf16NulError {}
+
+/// Returns an error if the message contains a nul character.
+pub(crate) fn check_nul(buf: &str) -> Result<(), Utf16NulError> {
+    match buf.bytes().position(|byte| byte == 0) {
+        Some(position) => Err(Utf16NulError(position)),
+        None => Ok(()),
+    }
+}
+
+/// Transcodes the message into UTF-16 and calls the specified function
+/// with the transcoded code units, optionally followed by a nul terminator,
+/// until the first failed call, returning the result for the whole message.
+///
+/// Short messages are transcoded in a stack buffer.
+/// Longer messages are transcoded into a single heap buffer if `alloc` feature is enabled
+/// and split into several stack buffer chunks at character boundaries otherwise.
+pub(crate) fn write_utf16<R: MultiWriteResult>(
+    buf: &str,
+    nul: bool,
+    mut write: impl FnMut(&[u16]) -> R,
+) -> R {
+    // A UTF-8 message never has fewer bytes than UTF-16 code units.
+    #[cfg(feature = "alloc")]
+    if buf.len() + usize::from(nul) > CHUNK_LEN {
+        let mut data: Vec<u16> = buf.encode_utf16().collect();
+        if nul {
+            data.push(0);
+        }
+        return message_result(write(&data), buf.len());
+    }
+
+    let capacity = CHUNK_LEN - usize::from(nul);
+    let mut chunk = [0; CHUNK_LEN];
+    let mut len = 0;
+    let mut result = None;
+    for ch in buf.chars() {
+        if len + ch.len_utf16() > capacity {
+            let chunk_result = write_chunk(&mut chunk, len, nul, &mut write);
+            if chunk_result.is_failure() {
+                return chunk_result;
+            }
+            result = Some(chunk_result);
+            len = 0;
+        }
+        len += ch.encode_utf16(&mut chunk[len..]).len();
+    }
+    let result = match result {
+        Some(result) if len == 0 => result,
+        _ => write_chunk(&mut chunk, len, nul, &mut write),
+    };
+    message_result(result, buf.len())
+}
+
+fn write_chunk<R>(
+    chunk: &mut [u16; CHUNK_LEN],
+    len: usize,
+    nul: bool,
+    write: &mut impl FnMut(&[u16]) -> R,
+) -> R {
+    if nul {
+        chunk[len] = 0;
+        write(&chunk[..=len])
+    } else {
+        write(&chunk[..len])
+    }
+}
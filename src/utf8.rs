@@ -0,0 +1,14 @@
+/// Returns the largest index not exceeding the specified one
+/// that is not in the middle of a UTF-8 character.
+///
+/// If the buffer is not valid UTF-8 and no character start is found
+/// within the last 4 bytes, the specified index is returned.
+pub(crate) fn floor_char_boundary(buf: &[u8], index: usize) -> usize {
+    if index >= buf.len() {
+        return buf.len();
+    }
+    (index.saturating_sub(3)..=index)
+        .rev()
+        .find(|&index| buf[index] & 0b1100_0000 != 0b1000_0000)
+        .unwrap_or(index)
+}
@@ -0,0 +1,26 @@
+use core::fmt::Arguments;
+
+/// A trait for writers which can write formatted arguments returning a specific output.
+///
+/// This trait is implemented by all writers of this crate by calling their `write_fmt` method,
+/// and it is used by [`TeeWriter`] to write formatted arguments into several writers.
+///
+/// [`TeeWriter`]: struct.TeeWriter.html
+pub trait WriteFmt {
+    /// The resulting type after writing.
+    type Output;
+
+    /// Performs formatted arguments writing.
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output;
+}
+
+impl<W> WriteFmt for &mut W
+where
+    W: WriteFmt + ?Sized,
+{
+    type Output = W::Output;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        (**self).write_fmt(args)
+    }
+}
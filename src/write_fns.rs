@@ -1,11 +1,15 @@
 #[cfg(feature = "alloc")]
+use alloc::ffi::{CString, NulError};
+#[cfg(feature = "alloc")]
 use alloc::string::{String, ToString};
-#[cfg(feature = "std")]
-use std::ffi::{CStr, CString, NulError};
-#[cfg(feature = "std")]
-use std::os::raw::c_char;
+use core::ffi::{c_char, CStr};
+use core::fmt::Arguments;
 
-use crate::{WriteBytes, WriteStr, WriteStrAsBytes};
+use crate::utf16::{check_nul, write_utf16};
+use crate::{
+    MultiWriteResult, NulPolicy, Record, Utf16NulError, WriteBytes, WriteFmt, WriteStr,
+    WriteStrAsBytes,
+};
 
 macro_rules! with_docs {
     ( docs: { $( $doc:expr ),* $(,)? }, item: { $item:item } ) => {
@@ -116,7 +120,7 @@ macro_rules! define_write_bytes_fn {
     };
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 macro_rules! define_write_cstr_fn {
     ($name:ident, ($($params:ty),*), $cstr:ident => ($($args:tt)*)) => {
         define_write_fn!(
@@ -149,7 +153,7 @@ macro_rules! define_write_cstr_fn {
     };
 }
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 macro_rules! define_try_write_cstr_fn {
     ($name:ident, ($($params:ty),*), $cstr:ident => ($($args:tt)*)) => {
         define_write_fn!(
@@ -176,24 +180,274 @@ macro_rules! define_try_write_cstr_fn {
     };
 }
 
+#[cfg(feature = "alloc")]
+macro_rules! define_lossy_write_cstr_fn {
+    ($name:ident, ($($params:ty),*), $cstr:ident => ($($args:tt)*)) => {
+        with_docs!(
+            docs: {
+                ::core::concat!(
+                    "A wrapper for write functions `for<R> FnMut(",
+                    ::core::stringify!($($params),*),
+                    ") -> R` that handles interior nul bytes with the specified [`NulPolicy`]."
+                ),
+                "",
+                "It implements [`WriteBytes`] and [`WriteStr`] traits \
+                and is constructed by [`IntoWriteFn`] and [`IntoTryWriteFn`] traits \
+                from [`LossyCStr`] wrapper.",
+                "",
+                "[`NulPolicy`]: enum.NulPolicy.html",
+                "[`LossyCStr`]: struct.LossyCStr.html",
+                "[`IntoWriteFn`]: trait.IntoWriteFn.html",
+                "[`IntoTryWriteFn`]: trait.IntoTryWriteFn.html"
+            },
+            item: {
+                #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+                pub struct $name<F, R>(F, NulPolicy)
+                where
+                    F: FnMut($($params),*) -> R;
+            }
+        );
+
+        impl<F, R> $name<F, R>
+        where
+            F: FnMut($($params),*) -> R,
+        {
+            with_docs!(
+                docs: {
+                    ::core::concat!(
+                        "Creates a new `",
+                        ::core::stringify!($name),
+                        "` containing the given closure or function and nul bytes policy."
+                    )
+                },
+                item: {
+                    pub fn new(closure: F, policy: NulPolicy) -> Self {
+                        Self(closure, policy)
+                    }
+                }
+            );
+        }
+
+        impl<F, R> WriteBytes for $name<F, R>
+        where
+            F: FnMut($($params),*) -> R,
+            R: MultiWriteResult,
+        {
+            type Output = R;
+
+            fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+                let func = &mut self.0;
+                self.1.write(buf, |$cstr| func($($args)*))
+            }
+        }
+
+        impl<F, R> WriteStrAsBytes for $name<F, R>
+        where
+            F: FnMut($($params),*) -> R,
+            R: MultiWriteResult,
+        {
+        }
+    };
+}
+
+macro_rules! define_array_write_cstr_fn {
+    ($name:ident, ($($params:ty),*), $cstr:ident => ($($args:tt)*)) => {
+        with_docs!(
+            docs: {
+                ::core::concat!(
+                    "A wrapper for write functions `for<R> FnMut(",
+                    ::core::stringify!($($params),*),
+                    ") -> R` that builds nul-terminated strings in a stack buffer of `N` bytes."
+                ),
+                "",
+                "It implements [`WriteBytes`] and [`WriteStr`] traits \
+                and is constructed by [`IntoWriteFn`] and [`IntoTryWriteFn`] traits \
+                from [`ArrayCStr`] wrapper.",
+                "",
+                "[`NulPolicy`]: enum.NulPolicy.html",
+                "[`ArrayCStr`]: struct.ArrayCStr.html",
+                "[`IntoWriteFn`]: trait.IntoWriteFn.html",
+                "[`IntoTryWriteFn`]: trait.IntoTryWriteFn.html"
+            },
+            item: {
+                #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+                pub struct $name<const N: usize, F, R>(F, NulPolicy)
+                where
+                    F: FnMut($($params),*) -> R;
+            }
+        );
+
+        impl<const N: usize, F, R> $name<N, F, R>
+        where
+            F: FnMut($($params),*) -> R,
+        {
+            with_docs!(
+                docs: {
+                    ::core::concat!(
+                        "Creates a new `",
+                        ::core::stringify!($name),
+                        "` containing the given closure or function and nul bytes policy."
+                    )
+                },
+                item: {
+                    pub fn new(closure: F, policy: NulPolicy) -> Self {
+                        Self(closure, policy)
+                    }
+                }
+            );
+        }
+
+        impl<const N: usize, F, R> WriteBytes for $name<N, F, R>
+        where
+            F: FnMut($($params),*) -> R,
+            R: MultiWriteResult,
+        {
+            type Output = R;
+
+            fn write_bytes(&mut self, buf: &[u8]) -> Self::Output {
+                let func = &mut self.0;
+                self.1.write_array::<N, _>(buf, |$cstr| func($($args)*))
+            }
+        }
+
+        impl<const N: usize, F, R> WriteStrAsBytes for $name<N, F, R>
+        where
+            F: FnMut($($params),*) -> R,
+            R: MultiWriteResult,
+        {
+        }
+    };
+}
+
+macro_rules! define_write_utf16_fn {
+    ($name:ident, ($($params:ty),*), $data:ident => ($($args:tt)*)) => {
+        define_write_fn!(
+            $name,
+            ($($params),*),
+            "[`WriteStr`] trait",
+            "[`IntoWriteFn`] and [`IntoTryWriteFn`] traits"
+        );
+
+        impl<F, R> WriteStr for $name<F, R>
+        where
+            F: FnMut($($params),*) -> R,
+            R: MultiWriteResult,
+        {
+            type Output = R;
+
+            fn write_str(&mut self, buf: &str) -> Self::Output {
+                let func = &mut self.0;
+                write_utf16(buf, false, |$data| func($($args)*))
+            }
+        }
+    };
+}
+
+define_write_fn!(
+    WriteArgsFn,
+    (Arguments<'_>),
+    "[`WriteFmt`] and [`WriteStr`] traits",
+    "[`IntoWriteFn`] and [`IntoTryWriteFn`] traits"
+);
+
+impl<F, R> WriteFmt for WriteArgsFn<F, R>
+where
+    F: FnMut(Arguments<'_>) -> R,
+{
+    type Output = R;
+
+    fn write_fmt(&mut self, args: Arguments<'_>) -> Self::Output {
+        self.0(args)
+    }
+}
+
+impl<F, R> WriteStr for WriteArgsFn<F, R>
+where
+    F: FnMut(Arguments<'_>) -> R,
+{
+    type Output = R;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        self.0(format_args!("{}", buf))
+    }
+}
+
 define_write_bytes_fn!(WritePtrLenFn, (*const u8, usize), buf => (buf.as_ptr(), buf.len()));
 define_write_bytes_fn!(WriteLenPtrFn, (usize, *const u8), buf => (buf.len(), buf.as_ptr()));
 define_write_bytes_fn!(WriteBytesFn, (&[u8]), buf => (buf));
 
 define_write_str_fn!(WriteStrFn, (&str), buf => (buf));
+define_write_str_fn!(WriteRecordFn, (&Record<'_>), buf => (&Record::current(buf)));
 #[cfg(feature = "alloc")]
 define_write_str_fn!(WriteStringFn, (String), buf => (buf.to_string()));
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 define_write_cstr_fn!(WriteCStrFn, (&CStr), cstr => (cstr.as_c_str()));
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 define_write_cstr_fn!(WriteCStringFn, (CString), cstr => (cstr));
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 define_write_cstr_fn!(WriteCCharPtrFn, (*const c_char), cstr => (cstr.as_ptr()));
 
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 define_try_write_cstr_fn!(TryWriteCStrFn, (&CStr), cstr => (cstr.as_c_str()));
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 define_try_write_cstr_fn!(TryWriteCStringFn, (CString), cstr => (cstr));
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 define_try_write_cstr_fn!(TryWriteCCharPtrFn, (*const c_char), cstr => (cstr.as_ptr()));
+
+#[cfg(feature = "alloc")]
+define_lossy_write_cstr_fn!(LossyWriteCStrFn, (&CStr), cstr => (cstr.as_c_str()));
+#[cfg(feature = "alloc")]
+define_lossy_write_cstr_fn!(LossyWriteCStringFn, (CString), cstr => (cstr));
+#[cfg(feature = "alloc")]
+define_lossy_write_cstr_fn!(LossyWriteCCharPtrFn, (*const c_char), cstr => (cstr.as_ptr()));
+
+define_array_write_cstr_fn!(ArrayWriteCStrFn, (&CStr), cstr => (cstr));
+define_array_write_cstr_fn!(ArrayWriteCCharPtrFn, (*const c_char), cstr => (cstr.as_ptr()));
+
+define_write_utf16_fn!(WriteUtf16Fn, (&[u16]), data => (data));
+define_write_utf16_fn!(WriteUtf16PtrLenFn, (*const u16, usize), data => (data.as_ptr(), data.len()));
+
+define_write_fn!(
+    WriteUtf16PtrFn,
+    (*const u16),
+    "[`WriteStr`] trait",
+    "[`IntoWriteFn`] trait"
+);
+
+impl<F, R> WriteStr for WriteUtf16PtrFn<F, R>
+where
+    F: FnMut(*const u16) -> R,
+    R: MultiWriteResult,
+{
+    type Output = R;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        if let Err(err) = check_nul(buf) {
+            panic!("{}, buffer: {:?}", err, buf);
+        }
+        let func = &mut self.0;
+        write_utf16(buf, true, |data| func(data.as_ptr()))
+    }
+}
+
+define_write_fn!(
+    TryWriteUtf16PtrFn,
+    (*const u16),
+    "[`WriteStr`] trait",
+    "[`IntoTryWriteFn`] trait"
+);
+
+impl<F, R> WriteStr for TryWriteUtf16PtrFn<F, R>
+where
+    F: FnMut(*const u16) -> R,
+    R: MultiWriteResult,
+{
+    type Output = Result<R, Utf16NulError>;
+
+    fn write_str(&mut self, buf: &str) -> Self::Output {
+        check_nul(buf)?;
+        let func = &mut self.0;
+        Ok(write_utf16(buf, true, |data| func(data.as_ptr())))
+    }
+}
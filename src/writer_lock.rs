@@ -0,0 +1,192 @@
+use core::fmt::{self, Debug, Formatter};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A lock used by [`LockedWriter`] and the `locked` writer modifier
+/// to prevent output of different threads from interleaving.
+///
+/// The lock only serializes writes, so it can be implemented with any synchronization primitive,
+/// like a spin lock or a critical section on embedded targets.
+/// Every [`lock`] call is followed by exactly one [`unlock`] call in the same thread.
+///
+/// # Safety
+///
+/// Writers rely on the lock to exclude other threads,
+/// so a thread must not return from [`lock`] while another thread holds the lock.
+///
+/// [`LockedWriter`]: struct.LockedWriter.html
+/// [`lock`]: #tymethod.lock
+/// [`unlock`]: #tymethod.unlock
+pub unsafe trait WriterLock {
+    /// Acquires the lock, blocking the current thread until it is available.
+    fn lock(&self);
+
+    /// Releases the lock acquired by the current thread.
+    ///
+    /// # Safety
+    ///
+    /// The lock must be held by the current thread,
+    /// and every [`lock`] call must be followed by at most one `unlock` call.
+    ///
+    /// [`lock`]: #tymethod.lock
+    unsafe fn unlock(&self);
+}
+
+/// A [`WriterLock`] that spins until the lock is available.
+///
+/// The lock is not reentrant, so a nested write with the same lock deadlocks,
+/// for example a write from a [`Display`] implementation of a value written
+/// by a locked writer, or a write from an interrupt handler
+/// that interrupts a locked writer on a single-core target.
+/// It is the default lock of the `locked` writer modifier if the `std` feature is disabled.
+///
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`WriterLock`]: trait.WriterLock.html
+#[derive(Debug, Default)]
+pub struct SpinWriterLock(AtomicBool);
+
+/// A reentrant [`WriterLock`] based on [`std::sync::Mutex`].
+///
+/// The lock can be acquired again by the thread that holds it,
+/// so values formatted by a locked writer can use writers with the same lock.
+///
+/// [`WriterLock`]: trait.WriterLock.html
+/// [`std::sync::Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct MutexWriterLock {
+    owner: std::sync::Mutex<Option<(std::thread::ThreadId, usize)>>,
+    released: std::sync::Condvar,
+}
+
+/// The [`WriterLock`] type used by default:
+/// [`MutexWriterLock`] if the `std` feature is enabled, or [`SpinWriterLock`] otherwise.
+///
+/// [`WriterLock`]: trait.WriterLock.html
+/// [`MutexWriterLock`]: struct.MutexWriterLock.html
+/// [`SpinWriterLock`]: struct.SpinWriterLock.html
+#[cfg(feature = "std")]
+pub type DefaultWriterLock = MutexWriterLock;
+
+/// The [`WriterLock`] type used by default:
+/// [`MutexWriterLock`] if the `std` feature is enabled, or [`SpinWriterLock`] otherwise.
+///
+/// [`WriterLock`]: trait.WriterLock.html
+/// [`MutexWriterLock`]: struct.MutexWriterLock.html
+/// [`SpinWriterLock`]: struct.SpinWriterLock.html
+#[cfg(not(feature = "std"))]
+pub type DefaultWriterLock = SpinWriterLock;
+
+/// The global lock used by the `locked` modifier of [`define_writer`] and [`define_try_writer`].
+///
+/// The lock is not reentrant if the `std` feature is disabled,
+/// so writers with the `locked` modifier must not be used in nested writes in that case.
+///
+/// [`define_writer`]: macro.define_writer.html
+/// [`define_try_writer`]: macro.define_try_writer.html
+pub static WRITER_LOCK: DefaultWriterLock = DefaultWriterLock::new();
+
+impl SpinWriterLock {
+    /// Creates a new unlocked `SpinWriterLock`.
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+}
+
+// `compare_exchange` lets only one thread at a time change the flag from `false` to `true`.
+unsafe impl WriterLock for SpinWriterLock {
+    fn lock(&self) {
+        while self
+            .0
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+    }
+
+    unsafe fn unlock(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(feature = "std")]
+impl MutexWriterLock {
+    /// Creates a new unlocked `MutexWriterLock`.
+    pub const fn new() -> Self {
+        Self {
+            owner: std::sync::Mutex::new(None),
+            released: std::sync::Condvar::new(),
+        }
+    }
+
+    fn owner(&self) -> std::sync::MutexGuard<'_, Option<(std::thread::ThreadId, usize)>> {
+        self.owner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+// The owner thread is changed only while no thread holds the lock.
+#[cfg(feature = "std")]
+unsafe impl WriterLock for MutexWriterLock {
+    fn lock(&self) {
+        let thread = std::thread::current().id();
+        let mut owner = self.owner();
+        while matches!(*owner, Some((id, _)) if id != thread) {
+            owner = self
+                .released
+                .wait(owner)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        match &mut *owner {
+            Some((_, depth)) => *depth += 1,
+            None => *owner = Some((thread, 1)),
+        }
+    }
+
+    unsafe fn unlock(&self) {
+        let mut owner = self.owner();
+        if let Some((_, depth)) = &mut *owner {
+            *depth -= 1;
+            if *depth == 0 {
+                *owner = None;
+                drop(owner);
+                self.released.notify_one();
+            }
+        }
+    }
+}
+
+/// Holds the lock until the end of the `dbg`-like macro invocation.
+#[doc(hidden)]
+pub struct _WriterLockScope<'a>(Option<&'a dyn WriterLock>);
+
+impl<'a> _WriterLockScope<'a> {
+    #[doc(hidden)]
+    pub fn enter(lock: &'a dyn WriterLock) -> Self {
+        lock.lock();
+        Self(Some(lock))
+    }
+
+    #[doc(hidden)]
+    pub fn none() -> Self {
+        Self(None)
+    }
+}
+
+impl Debug for _WriterLockScope<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("_WriterLockScope")
+            .field(&self.0.is_some())
+            .finish()
+    }
+}
+
+impl Drop for _WriterLockScope<'_> {
+    fn drop(&mut self) {
+        if let Some(lock) = self.0 {
+            // The lock was acquired in `enter` and is released only once.
+            unsafe { lock.unlock() };
+        }
+    }
+}
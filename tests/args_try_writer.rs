@@ -0,0 +1,46 @@
+#![no_std]
+
+extern crate std;
+
+use core::fmt::Arguments;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum WriteError {
+    CustomWriteError,
+}
+
+fn write(_: Arguments<'_>) -> Result<(), WriteError> {
+    let should_write_succeed = SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed);
+    if should_write_succeed {
+        Ok(())
+    } else {
+        Err(WriteError::CustomWriteError)
+    }
+}
+
+custom_print::define_macros!(
+    { try_print, try_println, try_dbg, try_flush },
+    args, crate::write
+);
+
+pub mod submodule {
+    #[test]
+    fn test_args_try_writer() {
+        use crate::{WriteError, SHOULD_WRITE_SUCCEED};
+        use core::sync::atomic::Ordering;
+
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+        assert_eq!(try_print!("first"), Ok(()));
+        assert_eq!(try_println!("second {}", 2), Ok(()));
+        assert_eq!(try_dbg!("third"), Ok("third"));
+        assert_eq!(try_flush!(), Ok(()));
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        assert_eq!(try_print!("first"), Err(WriteError::CustomWriteError));
+        assert_eq!(try_println!("second"), Err(WriteError::CustomWriteError));
+        assert_eq!(try_dbg!("third"), Err(WriteError::CustomWriteError));
+    }
+}
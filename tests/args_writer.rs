@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt::Arguments;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CALLS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static FLUSHES: AtomicUsize = AtomicUsize::new(0);
+
+fn take_calls() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CALLS.lock().unwrap())
+}
+
+fn write(args: Arguments<'_>) {
+    CALLS.lock().unwrap().push(std::format!("{}", args));
+}
+
+fn flush() {
+    let _ = FLUSHES.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+custom_print::define_macros!(
+    { print, println, dbg, flush },
+    args, crate::write, flush: crate::flush
+);
+
+pub mod submodule {
+    #[test]
+    fn test_args_writer() {
+        use crate::{black_box, take_calls, FLUSHES};
+        use core::fmt::Arguments;
+        use core::sync::atomic::Ordering;
+        use custom_print::{ArgsWriter, FmtWriter};
+
+        let () = print!("first {} {}", black_box("second"), black_box(3));
+        assert_eq!(take_calls(), &["first second 3"]);
+        let () = println!("{}", black_box("second"));
+        assert_eq!(take_calls(), &["second\n"]);
+        assert_eq!(dbg!(1), 1);
+        assert_eq!(take_calls().len(), 1);
+        let () = flush!();
+        assert_eq!(FLUSHES.load(Ordering::Relaxed), 1);
+
+        let mut writer = ArgsWriter::from_closure(|_: Arguments<'_>| Ok::<_, ()>(4));
+        assert_eq!(writer.write_fmt(format_args!("{}", black_box(1))), Ok(4));
+
+        let mut chunks = std::vec::Vec::new();
+        let mut writer = FmtWriter::from_closure(|args: Arguments<'_>| {
+            chunks.push(std::format!("{}", args));
+        });
+        writer
+            .write_fmt(format_args!(
+                "{} {}",
+                black_box("first"),
+                black_box("second")
+            ))
+            .unwrap();
+        assert_eq!(chunks, ["first", " ", "second"]);
+    }
+}
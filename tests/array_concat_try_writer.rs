@@ -0,0 +1,96 @@
+#![no_std]
+
+extern crate std;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum WriteError {
+    CustomWriteError,
+}
+
+fn write(_: &str) -> Result<(), WriteError> {
+    let should_write_succeed = SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed);
+    if should_write_succeed {
+        Ok(())
+    } else {
+        Err(WriteError::CustomWriteError)
+    }
+}
+
+struct FailingDisplay;
+
+impl core::fmt::Display for FailingDisplay {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Err(core::fmt::Error)
+    }
+}
+
+custom_print::define_macros!(
+    { try_print, try_println, try_dbg },
+    concat_stack<16>, crate::write
+);
+
+pub mod submodule {
+    #[test]
+    fn test_array_concat_try_writer() {
+        use crate::{WriteError, SHOULD_WRITE_SUCCEED};
+        use custom_print::{ArrayConcatError, ArrayConcatTryWriter, ArrayOverflow};
+        use std::sync::atomic::Ordering;
+
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+        let result = try_print!("first");
+        assert_eq!(result, Ok(()));
+        let result = try_println!("second");
+        assert_eq!(result, Ok(()));
+        let result = try_dbg!("third");
+        assert!(result.is_ok());
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        let result = try_print!("first");
+        assert_eq!(
+            result,
+            Err(ArrayConcatError::Write(WriteError::CustomWriteError))
+        );
+        let result = try_println!("second");
+        assert_eq!(
+            result,
+            Err(ArrayConcatError::Write(WriteError::CustomWriteError))
+        );
+
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+        let mut writer = ArrayConcatTryWriter::<4, _>::from_closure(crate::write)
+            .with_overflow(ArrayOverflow::Error);
+        assert_eq!(writer.write_fmt(format_args!("{}", "abcd")), Ok(()));
+        assert_eq!(
+            writer.write_fmt(format_args!("{}", "abcde")),
+            Err(ArrayConcatError::Overflow)
+        );
+
+        let mut writer = writer.with_overflow(ArrayOverflow::Split);
+        assert_eq!(writer.write_fmt(format_args!("{}", "abcde")), Ok(()));
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        assert_eq!(
+            writer.write_fmt(format_args!("{}", "abcde")),
+            Err(ArrayConcatError::Write(WriteError::CustomWriteError))
+        );
+    }
+    #[test]
+    fn test_array_concat_try_writer_format_error() {
+        use crate::{FailingDisplay, SHOULD_WRITE_SUCCEED};
+        use custom_print::ArrayConcatError;
+        use std::sync::atomic::Ordering;
+
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+        let result = try_println!("first {}", FailingDisplay);
+        assert_eq!(result, Err(ArrayConcatError::Format));
+    }
+
+    #[test]
+    #[should_panic(expected = "array buffer should have space for at least one 4-byte character")]
+    fn test_array_concat_try_writer_too_small() {
+        let _ = custom_print::ArrayConcatTryWriter::<3, _>::from_closure(crate::write);
+    }
+}
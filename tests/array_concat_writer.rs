@@ -0,0 +1,98 @@
+#![cfg(feature = "alloc")]
+#![no_std]
+
+extern crate std;
+
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn write(value: &str) {
+    use std::string::ToString;
+    let mut chunks = CHUNKS.lock().unwrap();
+    chunks.push(value.to_string());
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+custom_print::define_macros!({ print, println, dbg }, concat_stack<64>, crate::write);
+
+pub mod submodule {
+    #[test]
+    fn test_array_concat_writer() {
+        use crate::{black_box, take_chunks};
+        use custom_print::{ArrayConcatWriter, ArrayOverflow};
+        use std::format;
+
+        let file = file!();
+
+        let () = print!("first");
+        assert_eq!(take_chunks(), &["first"]);
+        let () = print!("first {}\nthird\n", black_box("second"));
+        assert_eq!(take_chunks(), &["first second\nthird\n"]);
+
+        let () = println!();
+        assert_eq!(take_chunks(), &["\n"]);
+        let () = println!("first {}\nthird\n", black_box("second"));
+        assert_eq!(take_chunks(), &["first second\nthird\n\n"]);
+
+        let (output, line) = (dbg!("first"), line!());
+        assert_eq!(output, "first");
+        assert_eq!(
+            take_chunks(),
+            &[format!("[{}:{}] \"first\" = \"first\"\n", file, line)]
+        );
+
+        let () = print!("{}", black_box("0123456789").repeat(7));
+        assert_eq!(
+            take_chunks(),
+            &[format!("{}...", "0123456789".repeat(7).get(..61).unwrap())]
+        );
+
+        let mut writer = ArrayConcatWriter::<8, _>::from_closure(crate::write)
+            .with_overflow(ArrayOverflow::Truncate("~"));
+        writer
+            .write_fmt(format_args!("{}", black_box("abcdefgh")))
+            .unwrap();
+        writer
+            .write_fmt(format_args!("{}{}", black_box("abcde"), black_box("fghi")))
+            .unwrap();
+        writer
+            .write_fmt(format_args!("{}", black_box("абвгд")))
+            .unwrap();
+        writer.write_fmt(format_args!("{}", "абвгд")).unwrap();
+        assert_eq!(take_chunks(), &["abcdefgh", "abcdefg~", "абв~", "абв~"]);
+
+        let mut writer = writer.with_overflow(ArrayOverflow::Split);
+        writer
+            .write_fmt(format_args!("{}", black_box("abcdefghijklmnopq")))
+            .unwrap();
+        writer
+            .write_fmt(format_args!("{}{}", black_box("абвг"), black_box("д")))
+            .unwrap();
+        assert_eq!(take_chunks(), &["abcdefgh", "ijklmnop", "q", "абвг", "д"]);
+
+        let mut writer = writer.with_overflow(ArrayOverflow::Error);
+        let result = std::panic::catch_unwind(move || {
+            writer.write_fmt(format_args!("{}", black_box("abcdefghi")))
+        });
+        assert!(result.is_err());
+        assert_eq!(take_chunks(), &[""; 0][..]);
+    }
+}
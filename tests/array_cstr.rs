@@ -0,0 +1,97 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write_cstr(value: &CStr) {
+    CHUNKS
+        .lock()
+        .unwrap()
+        .push(value.to_str().unwrap().to_string());
+}
+
+unsafe fn write_c_char_ptr(value: *const c_char) -> isize {
+    let value = CStr::from_ptr(value).to_str().unwrap();
+    CHUNKS.lock().unwrap().push(value.to_string());
+    if value.starts_with("error") {
+        -5
+    } else {
+        value.len() as isize
+    }
+}
+
+custom_print::define_macros!({ print, println }, concat, cstr_stack<8>: crate::write_cstr);
+custom_print::define_macros!(
+    { eprint, eprintln },
+    fmt,
+    cstr_stack<5>(custom_print::NulPolicy::Split): crate::write_cstr
+);
+custom_print::define_macros!(
+    { try_print },
+    fmt,
+    errno: cstr_stack<16>(custom_print::NulPolicy::REPLACEMENT_CHARACTER):
+        unsafe fn (crate::write_c_char_ptr)(*const std::os::raw::c_char)
+);
+
+pub mod submodule {
+    #[test]
+    fn test_array_cstr() {
+        use crate::{black_box, take_chunks};
+
+        print!("first");
+        assert_eq!(take_chunks(), ["first"]);
+        println!("first {}", black_box("second"));
+        assert_eq!(take_chunks(), ["first s", "econd\n"]);
+        print!("");
+        assert_eq!(take_chunks(), [""]);
+        print!("a\0b");
+        assert_eq!(take_chunks(), ["a\\0b"]);
+        print!("ab\u{1F600}");
+        assert_eq!(take_chunks(), ["ab\u{1F600}"]);
+        print!("abcd\u{1F600}");
+        assert_eq!(take_chunks(), ["abcd", "\u{1F600}"]);
+        print!("\u{430}\u{431}\u{432}\u{433}");
+        assert_eq!(take_chunks(), ["\u{430}\u{431}\u{432}", "\u{433}"]);
+
+        eprint!("a\0\0bc\0");
+        assert_eq!(take_chunks(), ["a", "bc"]);
+        eprint!("\0");
+        assert_eq!(take_chunks(), [""]);
+        eprintln!("abcdef\0{}", black_box("g"));
+        assert_eq!(take_chunks(), ["abcd", "ef", "g", "\n"]);
+
+        assert_eq!(try_print!("first\0{}", black_box("second")), Ok(()));
+        assert_eq!(take_chunks(), ["first\u{FFFD}", "second"]);
+        assert_eq!(
+            try_print!("{}", black_box("error message second")),
+            Err(custom_print::FmtWriteError::Write(
+                custom_print::ReturnError::Errno(-5)
+            ))
+        );
+        assert_eq!(take_chunks(), ["error message s"]);
+    }
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+type Chunks = Vec<(i32, &'static str, String, u32)>;
+
+static CHUNKS: Lazy<Mutex<Chunks>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Chunks {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+const TAG: &[u8] = b"app\0";
+
+pub mod ffi_decls {
+    use std::os::raw::{c_char, c_int};
+
+    #[no_mangle]
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub extern "C" fn bound_log_write(prio: c_int, tag: *const c_char, msg: *const c_char) {
+        use std::ffi::CStr;
+        use std::string::ToString;
+
+        let tag = unsafe { CStr::from_ptr(tag) };
+        assert_eq!(tag.to_bytes_with_nul(), crate::TAG);
+        let msg = unsafe { CStr::from_ptr(msg) }.to_str().unwrap();
+        crate::CHUNKS
+            .lock()
+            .unwrap()
+            .push((prio, "app", msg.to_string(), 0));
+    }
+}
+
+unsafe fn write_stream(ptr: *const u8, len: usize, stream: u32) {
+    use std::string::ToString;
+
+    let value = core::str::from_utf8(core::slice::from_raw_parts(ptr, len)).unwrap();
+    CHUNKS
+        .lock()
+        .unwrap()
+        .push((0, "", value.to_string(), stream));
+}
+
+custom_print::define_macros!(
+    { print, println },
+    fmt,
+    extern "C" fn bound_log_write(
+        prio: std::os::raw::c_int = 3,
+        tag: *const std::os::raw::c_char = crate::TAG.as_ptr().cast(),
+        _: *const std::os::raw::c_char
+    )
+);
+custom_print::define_macros!(
+    { eprint, eprintln },
+    concat,
+    unsafe fn (crate::write_stream)(_: *const u8, _: usize, stream = 2)
+);
+
+pub mod submodule {
+    #[test]
+    fn test_bound_args() {
+        use crate::{black_box, take_chunks};
+        use std::string::String;
+
+        print!("first");
+        assert_eq!(take_chunks(), [(3, "app", String::from("first"), 0)]);
+        println!("first {}", black_box("second"));
+        assert_eq!(
+            take_chunks(),
+            [
+                (3, "app", String::from("first "), 0),
+                (3, "app", String::from("second"), 0),
+                (3, "app", String::from("\n"), 0),
+            ]
+        );
+
+        eprint!("third");
+        assert_eq!(take_chunks(), [(0, "", String::from("third"), 2)]);
+        eprintln!("third {}", black_box("fourth"));
+        assert_eq!(take_chunks(), [(0, "", String::from("third fourth\n"), 2)]);
+    }
+}
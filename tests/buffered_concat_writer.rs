@@ -0,0 +1,134 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt::{self, Display, Formatter};
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+fn try_write(value: &str) -> Result<(), &'static str> {
+    if value.contains("error") {
+        Err("write error")
+    } else {
+        write(value);
+        Ok(())
+    }
+}
+
+custom_print::define_macros!({ print, println, dbg }, concat_buffered, crate::write);
+custom_print::define_macros!({ eprint, eprintln }, concat_buffered<16>, crate::write);
+custom_print::define_macros!({ try_println, try_dbg }, concat_buffered, crate::try_write);
+
+struct Nested(&'static str);
+
+impl Display for Nested {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        println!("nested {}", black_box(self.0));
+        f.write_str(self.0)
+    }
+}
+
+struct FailingDisplay;
+
+impl Display for FailingDisplay {
+    fn fmt(&self, _: &mut Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+pub mod submodule {
+    #[test]
+    fn test_buffered_concat_writer() {
+        use crate::{black_box, take_chunks, Nested};
+        use std::string::ToString;
+
+        print!("first {}", black_box(1));
+        assert_eq!(take_chunks(), ["first 1"]);
+        println!("second {} {}", black_box(2), black_box(3));
+        assert_eq!(take_chunks(), ["second 2 3\n"]);
+        println!("third");
+        assert_eq!(take_chunks(), ["third\n"]);
+        assert_eq!(dbg!(black_box(4)), 4);
+        let chunks = take_chunks();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].ends_with("black_box(4) = 4\n"));
+
+        println!("fourth {}", Nested("a"));
+        assert_eq!(take_chunks(), ["nested a\n", "fourth a\n"]);
+
+        eprint!("{}", "long message ".repeat(black_box(10)));
+        eprintln!("fifth {}", black_box(5));
+        assert_eq!(
+            take_chunks(),
+            ["long message ".repeat(10), "fifth 5\n".to_string()]
+        );
+
+        assert_eq!(try_println!("sixth {}", black_box(6)), Ok(()));
+        assert_eq!(take_chunks(), ["sixth 6\n"]);
+        assert_eq!(try_println!("{}", black_box("error")), Err("write error"));
+        assert!(take_chunks().is_empty());
+        assert_eq!(try_dbg!(black_box(7)), Ok(7));
+        assert_eq!(take_chunks().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "a formatting trait implementation returned an error")]
+    fn test_buffered_concat_writer_format_error() {
+        use crate::FailingDisplay;
+
+        let _ = try_println!("{}", FailingDisplay);
+    }
+
+    #[test]
+    fn test_caller_provided_buffer() {
+        use custom_print::{BufferedConcatWriter, ConcatWriter};
+        use std::string::{String, ToString};
+        use std::vec::Vec;
+
+        let mut chunks = Vec::new();
+        let mut buffer = String::with_capacity(64);
+        let mut writer = BufferedConcatWriter::with_buffer(
+            &mut buffer,
+            ConcatWriter::from_closure(|value: &str| chunks.push(value.to_string())),
+        )
+        .with_max_capacity(256);
+
+        let value = 1;
+        writer.write_fmt(format_args!("first {}", value)).unwrap();
+        writer.write_fmt(format_args!("second")).unwrap();
+        writer
+            .write_fmt(format_args!("{}", "x".repeat(1024)))
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[..2], ["first 1", "second"]);
+        assert_eq!(chunks[2].len(), 1024);
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 64);
+        assert!(buffer.capacity() <= 256);
+    }
+}
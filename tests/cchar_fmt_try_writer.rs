@@ -19,10 +19,16 @@ pub mod submodule {
 
     #[test]
     fn test_cchar_fmt_try_writer_failed() {
+        use custom_print::FmtWriteError;
+
+        let nul_position = |result| match result {
+            Err(FmtWriteError::Write(err)) => std::ffi::NulError::nul_position(&err),
+            _ => unreachable!(),
+        };
         assert_eq!(try_println!("first"), Ok(()));
-        assert_eq!(try_println!("first\0second"), Err(core::fmt::Error));
+        assert_eq!(nul_position(try_println!("first\0second")), 5);
         assert_eq!(try_println!("first,second"), Ok(()));
-        assert_eq!(try_println!("\0"), Err(core::fmt::Error));
+        assert_eq!(nul_position(try_println!("\0")), 0);
         assert_eq!(try_println!(""), Ok(()));
     }
 }
@@ -0,0 +1,139 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+fn write_bytes(value: &[u8]) {
+    write(core::str::from_utf8(value).unwrap());
+}
+
+fn write_limited(value: &str) -> Result<(), usize> {
+    if value.contains('!') {
+        return Err(value.len());
+    }
+    write(value);
+    Ok(())
+}
+
+custom_print::define_macros!({ print, println }, concat, chunk<8>: crate::write);
+custom_print::define_macros!(
+    { eprint, eprintln },
+    concat,
+    chunk<10>(custom_print::ChunkOverflow::Split { end: "~", start: "> " }):
+        crate::write
+);
+custom_print::define_macros!(
+    { cprint, cprintln },
+    concat,
+    chunk<8>(custom_print::ChunkOverflow::ELLIPSIS): crate::write
+);
+custom_print::define_macros!({ print as io_print }, io, chunk<4>: crate::write_bytes);
+custom_print::define_macros!({ try_print }, concat, chunk<8>: crate::write_limited);
+
+pub mod submodule {
+    #[test]
+    fn test_chunking_writer() {
+        use crate::{black_box, take_chunks};
+
+        print!("first");
+        assert_eq!(take_chunks(), ["first"]);
+        println!("first {}", black_box("second"));
+        assert_eq!(take_chunks(), ["first se", "cond\n"]);
+        print!("ab\ncdef\nghijkl");
+        assert_eq!(take_chunks(), ["ab\ncdef\n", "ghijkl"]);
+        print!("ab\ncdefghijkl");
+        assert_eq!(take_chunks(), ["ab\n", "cdefghij", "kl"]);
+        print!("abcdef\u{1F600}");
+        assert_eq!(take_chunks(), ["abcdef", "\u{1F600}"]);
+        print!("");
+        assert_eq!(take_chunks(), [""]);
+
+        eprint!("first {}", black_box("second"));
+        assert_eq!(take_chunks(), ["first sec~", "> ond"]);
+        eprint!("{}", black_box("abcdefghijklmnopqrstuvwxyz"));
+        assert_eq!(
+            take_chunks(),
+            ["abcdefghi~", "> jklmnop~", "> qrstuvw~", "> xyz"]
+        );
+
+        cprint!("first");
+        assert_eq!(take_chunks(), ["first"]);
+        cprintln!("first {}", black_box("second"));
+        assert_eq!(take_chunks(), ["first..."]);
+        cprint!("abc\u{1F600}de");
+        assert_eq!(take_chunks(), ["abc..."]);
+
+        io_print!("abc{}", black_box("def\u{430}"));
+        assert_eq!(take_chunks(), ["abc", "def", "\u{430}"]);
+
+        assert_eq!(try_print!("first {}", black_box("second")), Ok(()));
+        assert_eq!(take_chunks(), ["first se", "cond"]);
+        assert_eq!(try_print!("first {}", black_box("second!")), Err(5));
+        assert_eq!(take_chunks(), ["first se"]);
+    }
+
+    #[test]
+    fn test_chunking_writer_invalid_utf8() {
+        use custom_print::{ChunkingWriter, IntoWriteFn, WriteBytes};
+        use std::vec::Vec;
+
+        let mut chunks = Vec::new();
+        {
+            let mut writer =
+                ChunkingWriter::<4, _>::new(|value: &[u8]| chunks.push(value.to_vec()))
+                    .into_write_fn();
+            writer.write_bytes(&[b'a', 0xC3, 0x80, 0x80, 0x80, 0x80, b'b']);
+            writer.write_bytes(&[0x80, 0x80, 0x80, 0x80, 0x80, 0xFF]);
+        }
+
+        assert_eq!(
+            chunks,
+            [
+                &[b'a'][..],
+                &[0xC3, 0x80, 0x80, 0x80],
+                &[0x80, b'b'],
+                &[0x80, 0x80, 0x80, 0x80],
+                &[0x80, 0xFF],
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "chunk length should have space for markers and at least one character"
+    )]
+    fn test_chunking_writer_panic() {
+        use custom_print::{ChunkOverflow, ChunkingWriter, ConcatWriter};
+
+        let mut writer = ConcatWriter::from_closure(
+            ChunkingWriter::<4, _>::new(crate::write).with_overflow(ChunkOverflow::ELLIPSIS),
+        );
+        writer.write_fmt(format_args!("abcdef")).unwrap();
+    }
+}
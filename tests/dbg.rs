@@ -60,7 +60,6 @@ pub mod submodule {
             ]
         );
 
-        let second_var = second_var;
         let (output, line) = (dbg!(("first", second_var)), line!());
         assert_eq!(output, ("first", "second"));
         assert_eq!(
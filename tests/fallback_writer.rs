@@ -0,0 +1,103 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(true);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum WriteError {
+    Closed,
+}
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn write_primary(value: &str) -> Result<(), WriteError> {
+    if SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed) {
+        CHUNKS
+            .lock()
+            .unwrap()
+            .push(std::format!("primary:{}", value));
+        Ok(())
+    } else {
+        Err(WriteError::Closed)
+    }
+}
+
+fn write_secondary(value: &str) {
+    CHUNKS
+        .lock()
+        .unwrap()
+        .push(std::format!("secondary:{}", value));
+}
+
+fn try_write_secondary(_: &str) -> Result<(), WriteError> {
+    Err(WriteError::Closed)
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+custom_print::define_macros!(
+    { print, println, dbg, flush },
+    fallback(concat, crate::write_primary; concat, crate::write_secondary)
+);
+
+custom_print::define_macros!(
+    { try_print },
+    fallback(concat, crate::write_primary; concat, crate::try_write_secondary)
+);
+
+custom_print::define_init_panic_hook!(
+    fallback(concat, crate::write_primary; concat, crate::write_secondary)
+);
+
+mod submodule {
+    use crate::{black_box, init_panic_hook, take_chunks, WriteError, SHOULD_WRITE_SUCCEED};
+    use core::sync::atomic::Ordering;
+    use std::panic::{catch_unwind, take_hook};
+
+    #[test]
+    fn test_fallback_writer() {
+        let () = print!("first {}", black_box("second"));
+        assert_eq!(take_chunks(), &["primary:first second"]);
+        let () = flush!();
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        let () = println!("first {}", black_box("second"));
+        assert_eq!(
+            take_chunks(),
+            &["secondary:[primary writer failed: Closed] first second\n"]
+        );
+        assert_eq!(dbg!("third"), "third");
+        assert!(take_chunks()[0].starts_with("secondary:[primary writer failed: Closed] ["));
+        assert_eq!(try_print!("fourth"), Err(WriteError::Closed));
+
+        init_panic_hook();
+        let result = catch_unwind(|| panic!("fifth"));
+        let _ = take_hook();
+        assert!(result.is_err());
+        let chunks = take_chunks();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].starts_with("secondary:[primary writer failed: Closed] "));
+        assert!(chunks[0].contains("fifth"));
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt;
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<Option<String>>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<Option<String>> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn write(value: &str) {
+    use std::string::ToString;
+    let mut chunks = CHUNKS.lock().unwrap();
+    chunks.push(Some(value.to_string()));
+}
+
+fn try_write(value: &str) -> Result<(), fmt::Error> {
+    write(value);
+    Ok(())
+}
+
+fn flush() {
+    let mut chunks = CHUNKS.lock().unwrap();
+    chunks.push(None);
+}
+
+fn try_flush() -> Result<(), fmt::Error> {
+    flush();
+    Ok(())
+}
+
+fn failed_flush() -> Result<(), fmt::Error> {
+    Err(fmt::Error)
+}
+
+pub mod submodule {
+    use std::string::String;
+
+    fn some(value: &str) -> Option<String> {
+        use std::string::ToString;
+        Some(value.to_string())
+    }
+
+    #[test]
+    fn test_flush_fn() {
+        use crate::take_chunks;
+        use custom_print::{ConcatWriter, FmtTryWriter, IoWriter};
+        use std::io::Write as _;
+        use std::panic::catch_unwind;
+
+        {
+            custom_print::define_macros!(
+                { print, flush, try_print, try_flush },
+                concat, crate::write, flush: crate::flush
+            );
+
+            let () = print!("first");
+            let () = flush!();
+            assert!(try_print!("second").is_ok());
+            assert!(try_flush!().is_ok());
+            assert_eq!(take_chunks(), &[some("first"), None, some("second"), None]);
+        }
+
+        {
+            custom_print::define_macros!(
+                { print, flush, try_print, try_flush },
+                fmt, crate::try_write, flush: crate::try_flush
+            );
+
+            let () = print!("first");
+            let () = flush!();
+            assert!(try_print!("second").is_ok());
+            assert!(try_flush!().is_ok());
+            assert_eq!(take_chunks(), &[some("first"), None, some("second"), None]);
+        }
+
+        {
+            custom_print::define_macros!(
+                { flush, try_flush },
+                fmt, crate::try_write, flush: crate::failed_flush
+            );
+
+            assert!(try_flush!().is_err());
+            assert!(catch_unwind(|| flush!()).is_err());
+        }
+
+        {
+            let mut writer = ConcatWriter::from_closures(crate::write, crate::flush);
+            assert_eq!(write!(writer, "first"), Ok(()));
+            assert_eq!(writer.flush(), Ok(()));
+
+            let mut writer = FmtTryWriter::from_closures(crate::try_write, crate::failed_flush);
+            assert_eq!(write!(writer, "second"), Ok(()));
+            assert_eq!(writer.flush(), Err(core::fmt::Error));
+
+            let mut writer = IoWriter::from_closures(
+                |value: &[u8]| crate::write(core::str::from_utf8(value).unwrap()),
+                crate::flush,
+            );
+            assert!(writer.write_all(b"third").is_ok());
+            assert!(writer.flush().is_ok());
+
+            assert_eq!(
+                take_chunks(),
+                &[some("first"), None, some("second"), some("third"), None]
+            );
+        }
+    }
+}
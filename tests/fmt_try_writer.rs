@@ -2,27 +2,37 @@
 
 extern crate std;
 
-use core::fmt;
 use core::sync::atomic::{AtomicBool, Ordering};
 
 static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(false);
 
-fn write(_: &str) -> fmt::Result {
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WriteError(&'static str);
+
+fn write(_: &str) -> Result<(), WriteError> {
     let should_write_succeed = SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed);
     if should_write_succeed {
         Ok(())
     } else {
-        Err(fmt::Error)
+        Err(WriteError("closed"))
     }
 }
 
 custom_print::define_macros!({ try_print, try_println, try_dbg }, fmt, crate::write);
 
+struct FailingDisplay;
+
+impl core::fmt::Display for FailingDisplay {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Err(core::fmt::Error)
+    }
+}
+
 pub mod submodule {
     #[test]
     fn test_string_writer() {
-        use crate::SHOULD_WRITE_SUCCEED;
-        use core::fmt;
+        use crate::{WriteError, SHOULD_WRITE_SUCCEED};
+        use custom_print::FmtWriteError;
         use std::sync::atomic::Ordering;
 
         SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
@@ -35,10 +45,28 @@ pub mod submodule {
 
         SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
         let result = try_print!("first");
-        assert_eq!(result, Err(fmt::Error));
+        assert_eq!(result, Err(FmtWriteError::Write(WriteError("closed"))));
         let result = try_println!("second");
-        assert_eq!(result, Err(fmt::Error));
+        assert_eq!(result, Err(FmtWriteError::Write(WriteError("closed"))));
         let result = try_dbg!("third");
-        assert_eq!(result, Err(fmt::Error));
+        assert_eq!(result, Err(FmtWriteError::Write(WriteError("closed"))));
+    }
+
+    #[test]
+    fn test_fmt_try_writer_format_error() {
+        use crate::{FailingDisplay, WriteError};
+        use custom_print::{FmtTryWriter, FmtWriteError};
+
+        let mut writer = FmtTryWriter::from_closure(|_: &str| Ok::<(), WriteError>(()));
+        assert_eq!(
+            writer.write_fmt(format_args!("first {}", FailingDisplay)),
+            Err(FmtWriteError::Format)
+        );
+
+        let mut writer = FmtTryWriter::from_closure(|_: &str| Err::<(), _>(WriteError("closed")));
+        assert_eq!(
+            writer.write_fmt(format_args!("first {}", FailingDisplay)),
+            Err(FmtWriteError::Write(WriteError("closed")))
+        );
     }
 }
@@ -0,0 +1,123 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use custom_print::GlobalSink;
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static OTHER_CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static SINK: GlobalSink = GlobalSink::new();
+static NESTED_SINK: GlobalSink = GlobalSink::new();
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn take_other_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut OTHER_CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+fn write_other(value: &str) {
+    OTHER_CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+custom_print::define_macros!({ print, println, try_println, dbg }, global);
+custom_print::define_macros!({ eprintln }, concat, &crate::SINK);
+custom_print::define_macros!({ try_eprint }, global(&crate::SINK));
+custom_print::define_macros!({ cprintln }, global(&crate::NESTED_SINK));
+
+pub mod submodule {
+    #[test]
+    fn test_global_sink() {
+        use crate::{black_box, take_chunks, take_other_chunks, write, write_other};
+        use custom_print::GLOBAL_SINK;
+
+        print!("discarded {}", black_box(1));
+        assert!(take_chunks().is_empty());
+
+        assert!(GLOBAL_SINK.set_sink(write).is_none());
+        print!("first {}", black_box(2));
+        assert_eq!(take_chunks(), ["first ", "2"]);
+        assert_eq!(try_println!("second"), Ok(()));
+        assert_eq!(take_chunks(), ["second\n"]);
+        assert_eq!(dbg!(black_box(3)), 3);
+        assert!(take_chunks().concat().ends_with("black_box(3) = 3\n"));
+
+        let result = GLOBAL_SINK.with_sink(write_other, || {
+            println!("third");
+            4
+        });
+        assert_eq!(result, 4);
+        assert!(take_chunks().is_empty());
+        assert_eq!(take_other_chunks(), ["third\n"]);
+
+        println!("fourth");
+        assert_eq!(take_chunks(), ["fourth\n"]);
+        assert!(GLOBAL_SINK.take_sink().is_some());
+        println!("discarded");
+        assert!(take_chunks().is_empty());
+        assert!(GLOBAL_SINK.take_sink().is_none());
+    }
+
+    #[test]
+    fn test_custom_global_sink() {
+        use crate::{black_box, SINK};
+        use std::string::String;
+        use std::sync::{Arc, Mutex};
+
+        let written = Arc::new(Mutex::new(String::new()));
+        let captured = Arc::clone(&written);
+        let _ = SINK.set_sink(move |value: &str| *captured.lock().unwrap() += value);
+
+        eprintln!("first {}", black_box(1));
+        assert_eq!(try_eprint!("second {}", black_box(2)), Ok(()));
+        assert_eq!(*written.lock().unwrap(), "first 1\nsecond 2");
+
+        let previous = SINK.take_sink();
+        eprintln!("discarded");
+        assert_eq!(*written.lock().unwrap(), "first 1\nsecond 2");
+        let _ = SINK.replace_sink(previous);
+        eprintln!("third");
+        assert_eq!(*written.lock().unwrap(), "first 1\nsecond 2third\n");
+    }
+
+    #[test]
+    fn test_nested_global_sink() {
+        use crate::{black_box, NESTED_SINK};
+        use std::string::{String, ToString};
+        use std::sync::{Arc, Mutex};
+        use std::vec::Vec;
+
+        let written = Arc::new(Mutex::new(Vec::<String>::new()));
+        let captured = Arc::clone(&written);
+        let _ = NESTED_SINK.set_sink(move |value: &str| {
+            captured.lock().unwrap().push(value.to_string());
+            cprintln!("nested {}", black_box(value.len()));
+        });
+
+        cprintln!("first {}", black_box(1));
+        assert_eq!(*written.lock().unwrap(), ["first ", "1", "\n"]);
+        assert!(NESTED_SINK.take_sink().is_some());
+    }
+}
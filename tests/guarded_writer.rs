@@ -0,0 +1,134 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt::{self, Debug, Display, Formatter};
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use custom_print::SpinWriterLock;
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static FALLBACK_CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static LOCK: SpinWriterLock = SpinWriterLock::new();
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn take_fallback_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut FALLBACK_CHUNKS.lock().unwrap())
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+fn write_fallback(value: &str) {
+    FALLBACK_CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+custom_print::define_macros!(
+    { println as queue_println, dbg as queue_dbg },
+    guarded,
+    locked(&crate::LOCK),
+    fmt,
+    crate::write
+);
+custom_print::define_macros!(
+    { dbg as locked_dbg },
+    guarded,
+    locked(&crate::LOCK),
+    concat,
+    crate::write
+);
+custom_print::define_macros!(
+    { println as drop_println, try_dbg as drop_dbg },
+    guarded(custom_print::NestedWrite::Drop),
+    concat,
+    crate::write
+);
+custom_print::define_macro!(
+    println as fallback_println,
+    guarded(custom_print::NestedWrite::Fallback(crate::write_fallback)),
+    fmt,
+    crate::write
+);
+
+struct Nested(&'static str);
+
+impl Display for Nested {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        queue_println!("nested {}", self.0);
+        f.write_str(self.0)
+    }
+}
+
+impl Debug for Nested {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        drop_println!("nested {}", self.0);
+        f.write_str(self.0)
+    }
+}
+
+pub mod submodule {
+    #[test]
+    fn test_guarded_writer() {
+        use crate::{take_chunks, take_fallback_chunks, Nested};
+
+        queue_println!("first {}", Nested("a"));
+        assert_eq!(take_chunks(), ["first ", "a", "\n", "nested a\n"]);
+
+        let _ = queue_dbg!(Nested("b"));
+        let chunks = take_chunks().concat();
+        assert!(chunks.ends_with("Nested(\"b\") = b\n"));
+
+        queue_println!("second {} {}", Nested("c"), Nested("d"));
+        assert_eq!(
+            take_chunks(),
+            ["second ", "c", " ", "d", "\n", "nested c\nnested d\n"]
+        );
+
+        drop_println!("third {:?}", Nested("e"));
+        assert_eq!(take_chunks(), ["third e\n"]);
+        assert!(drop_dbg!(Nested("f")).is_ok());
+        assert_eq!(take_chunks().len(), 1);
+
+        let _ = locked_dbg!(LockedNested);
+        let chunks = take_chunks().concat();
+        let (outer, nested) = chunks.split_once("\n").unwrap();
+        assert!(outer.ends_with("LockedNested = locked"));
+        assert!(nested.ends_with("1 = 1\n"));
+
+        fallback_println!("fourth {:?}", Nested("g"));
+        assert_eq!(take_chunks(), ["fourth ", "g", "\n"]);
+        assert!(take_fallback_chunks().is_empty());
+
+        fallback_println!("fifth {}", FallbackNested);
+        assert_eq!(take_chunks(), ["fifth ", "h", "\n"]);
+        assert_eq!(take_fallback_chunks(), ["nested h\n"]);
+    }
+
+    struct LockedNested;
+
+    impl core::fmt::Debug for LockedNested {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let _ = locked_dbg!(1);
+            f.write_str("locked")
+        }
+    }
+
+    struct FallbackNested;
+
+    impl core::fmt::Display for FallbackNested {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            fallback_println!("nested {}", "h");
+            f.write_str("h")
+        }
+    }
+}
@@ -17,7 +17,23 @@ fn write(value: &[u8]) -> io::Result<usize> {
     }
 }
 
+#[derive(Debug)]
+pub struct WriteError;
+
+impl core::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("write error")
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+fn write_custom(_: &[u8]) -> Result<(), WriteError> {
+    Err(WriteError)
+}
+
 custom_print::define_macros!({ try_print, try_println, try_dbg }, io, crate::write);
+custom_print::define_macro!(try_println as try_custom_println, io, crate::write_custom);
 
 pub mod submodule {
     #[test]
@@ -41,4 +57,31 @@ pub mod submodule {
         let result = try_dbg!("third");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_custom_error_writer() {
+        use crate::WriteError;
+        use custom_print::IoTryWriter;
+        use std::io::{self, BufWriter, Write};
+        use std::string::ToString;
+
+        let err = try_custom_println!("first").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.to_string(), "write error");
+        assert!(err.into_inner().unwrap().is::<WriteError>());
+
+        let writer = IoTryWriter::from_closure(|_: &[u8]| Err::<(), _>("closed"))
+            .with_error_kind(io::ErrorKind::BrokenPipe);
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "second").unwrap();
+        let err = writer.flush().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(err.to_string(), "closed");
+
+        let mut writer = IoTryWriter::from_closure(|_: &[u8]| Err::<usize, _>(WriteError))
+            .map_err(|_: WriteError| io::Error::new(io::ErrorKind::WriteZero, "mapped"));
+        let err = writeln!(writer, "third").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(err.to_string(), "mapped");
+    }
 }
@@ -0,0 +1,199 @@
+#![cfg(feature = "alloc")]
+#![no_std]
+
+extern crate std;
+
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static LINES: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static ERR_LINES: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_lines() -> Vec<String> {
+    use core::mem::take;
+    take(&mut LINES.lock().unwrap())
+}
+
+fn take_err_lines() -> Vec<String> {
+    use core::mem::take;
+    take(&mut ERR_LINES.lock().unwrap())
+}
+
+fn write(value: &str) {
+    use std::string::ToString;
+    LINES.lock().unwrap().push(value.to_string());
+}
+
+fn write_err(value: &str) {
+    use std::string::ToString;
+    ERR_LINES.lock().unwrap().push(value.to_string());
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+custom_print::define_macros!({ print, println, dbg, flush }, line, crate::write);
+custom_print::define_macros!({ eprint, eprintln }, line, crate::write_err);
+
+static MODULE_LINES: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+mod a {
+    fn w(value: &str) {
+        crate::MODULE_LINES
+            .lock()
+            .unwrap()
+            .push(std::format!("A<{}>", value));
+    }
+
+    custom_print::define_macros!({ print as cprint, println as cprintln }, line, w);
+
+    pub fn write_half() {
+        cprint!("half from a, ");
+    }
+
+    pub fn finish_line() {
+        cprintln!("a line");
+    }
+}
+
+mod b {
+    fn w(value: &str) {
+        crate::MODULE_LINES
+            .lock()
+            .unwrap()
+            .push(std::format!("B<{}>", value));
+    }
+
+    custom_print::define_macros!({ print as cprint, println as cprintln }, line, w);
+
+    pub fn write_line() {
+        cprintln!("b line");
+    }
+}
+
+pub mod submodule {
+    #[test]
+    fn test_line_buf_writer() {
+        use crate::{black_box, take_err_lines, take_lines};
+        use std::format;
+
+        let file = file!();
+
+        let () = print!("a");
+        let () = print!("{}", black_box("b"));
+        assert_eq!(take_lines(), &[""; 0][..]);
+        let () = println!("c");
+        assert_eq!(take_lines(), &["abc"]);
+
+        let () = print!("first {}\nthird\n\nfourth", black_box("second"));
+        assert_eq!(take_lines(), &["first second", "third", ""]);
+        let () = eprint!("error");
+        let () = println!();
+        assert_eq!(take_lines(), &["fourth"]);
+        assert_eq!(take_err_lines(), &[""; 0][..]);
+        let () = eprintln!(" line");
+        assert_eq!(take_err_lines(), &["error line"]);
+
+        let (output, line) = (dbg!("first"), line!());
+        assert_eq!(output, "first");
+        assert_eq!(
+            take_lines(),
+            &[format!("[{}:{}] \"first\" = \"first\"", file, line)]
+        );
+
+        let () = print!("partial");
+        let () = flush!();
+        assert_eq!(take_lines(), &["partial"]);
+
+        let () = print!("first\r\nsecond\r");
+        assert_eq!(take_lines(), &["first"]);
+        let () = println!();
+        assert_eq!(take_lines(), &["second"]);
+        let () = flush!();
+        assert_eq!(take_lines(), &[""; 0][..]);
+    }
+
+    #[test]
+    fn test_line_buf_writer_keys() {
+        use custom_print::LineBufWriter;
+        use std::string::{String, ToString};
+        use std::sync::{Arc, Mutex};
+        use std::vec::Vec;
+
+        let lines = Arc::new(Mutex::new(Vec::<String>::new()));
+        let take_lines = || core::mem::take(&mut *lines.lock().unwrap());
+        let write = {
+            let lines = lines.clone();
+            move |value: &str| lines.lock().unwrap().push(value.to_string())
+        };
+
+        let mut first = LineBufWriter::from_closure(write.clone());
+        let mut second = LineBufWriter::from_closure(write.clone());
+        first.write_fmt(format_args!("first")).unwrap();
+        second.write_fmt(format_args!("second")).unwrap();
+        first.write_fmt(format_args!("\r\n")).unwrap();
+        let mut third = first.clone();
+        third.write_fmt(format_args!("third")).unwrap();
+        first.write_fmt(format_args!("fourth\n")).unwrap();
+        assert_eq!(take_lines(), ["first", "fourth"]);
+        drop(second);
+        assert_eq!(take_lines(), ["second"]);
+        drop(third);
+        assert_eq!(take_lines(), ["third"]);
+
+        let mut first = LineBufWriter::from_closure(write.clone()).with_key("shared");
+        let mut second = LineBufWriter::from_closure(write).with_key("shared");
+        first.write_fmt(format_args!("first")).unwrap();
+        drop(first);
+        assert!(take_lines().is_empty());
+        second.write_fmt(format_args!(" second\n")).unwrap();
+        assert_eq!(take_lines(), ["first second"]);
+    }
+
+    #[test]
+    fn test_line_buf_writer_modules() {
+        use crate::{a, b, MODULE_LINES};
+
+        a::write_half();
+        b::write_line();
+        assert_eq!(*MODULE_LINES.lock().unwrap(), ["B<b line>"]);
+        a::finish_line();
+        assert_eq!(
+            *MODULE_LINES.lock().unwrap(),
+            ["B<b line>", "A<half from a, a line>"]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_line_buf_writer_panic_hook() {
+        use crate::{take_err_lines, write_err};
+        use std::panic::{catch_unwind, set_hook, take_hook};
+        use std::thread;
+
+        thread::spawn(|| {
+            let () = eprint!("partial");
+            custom_print::init_panic_hook!(concat, write_err);
+            let result = catch_unwind(|| panic!("failed"));
+            let _ = take_hook();
+            set_hook(std::boxed::Box::new(|_| {}));
+            assert!(result.is_err());
+        })
+        .join()
+        .unwrap();
+
+        let lines = take_err_lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "partial");
+        assert!(lines[1].contains("failed"));
+    }
+}
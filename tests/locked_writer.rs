@@ -0,0 +1,133 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use custom_print::{SpinWriterLock, WriterLock};
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static LINES: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static COUNTING_LOCK: CountingLock = CountingLock(SpinWriterLock::new(), AtomicUsize::new(0));
+
+struct CountingLock(SpinWriterLock, AtomicUsize);
+
+unsafe impl WriterLock for CountingLock {
+    fn lock(&self) {
+        self.0.lock();
+        let _ = self.1.fetch_add(1, Ordering::Relaxed);
+    }
+
+    unsafe fn unlock(&self) {
+        self.0.unlock();
+    }
+}
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn take_lock_count() -> usize {
+    COUNTING_LOCK.1.swap(0, Ordering::Relaxed)
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+fn write_line(value: &str) {
+    let mut lines = LINES.lock().unwrap();
+    match lines.last_mut() {
+        Some(line) if !line.ends_with('\n') => *line += value,
+        _ => lines.push(value.to_string()),
+    }
+}
+
+custom_print::define_macros!({ print, println, dbg }, locked(&crate::COUNTING_LOCK), fmt, crate::write);
+custom_print::define_macros!({ try_print, try_dbg }, locked, fmt, crate::write);
+custom_print::define_macros!({ eprintln }, locked, fmt, crate::write_line);
+custom_print::define_macro!(
+    dbg as prefixed_dbg,
+    prefix("> "),
+    locked(&crate::COUNTING_LOCK),
+    fmt,
+    crate::write
+);
+
+pub mod submodule {
+    #[test]
+    fn test_locked_writer() {
+        use crate::{black_box, take_chunks, take_lock_count};
+
+        print!("first {}", black_box(2));
+        assert_eq!(take_chunks(), ["first ", "2"]);
+        assert_eq!(take_lock_count(), 1);
+        println!();
+        assert_eq!(take_chunks(), ["\n"]);
+        assert_eq!(take_lock_count(), 1);
+
+        assert_eq!(dbg!(black_box(1), black_box(2)), (1, 2));
+        let chunks = take_chunks().concat();
+        assert!(chunks.contains("black_box(1) = 1\n"));
+        assert!(chunks.contains("black_box(2) = 2\n"));
+        assert_eq!(take_lock_count(), 1);
+
+        assert_eq!(prefixed_dbg!(black_box(5), black_box(6)), (5, 6));
+        let chunks = take_chunks().concat();
+        assert!(chunks.starts_with("> ["));
+        assert!(chunks.contains("\n> [tests/locked_writer.rs:"));
+        assert!(chunks.ends_with("black_box(6) = 6\n"));
+        assert_eq!(take_lock_count(), 1);
+
+        assert_eq!(try_print!("third"), Ok(()));
+        assert_eq!(take_chunks(), ["third"]);
+        assert_eq!(try_dbg!(black_box(3), black_box(4)), Ok((3, 4)));
+        let chunks = take_chunks().concat();
+        assert!(chunks.contains("black_box(3) = 3\n"));
+        assert!(chunks.contains("black_box(4) = 4\n"));
+    }
+
+    #[test]
+    fn test_locked_writer_threads() {
+        use crate::LINES;
+        use std::string::{String, ToString};
+        use std::thread;
+        use std::vec::Vec;
+
+        let threads: Vec<_> = (0..8)
+            .map(|index| {
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        eprintln!("{} {} {}", index, index, index);
+                    }
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+
+        let lines = LINES.lock().unwrap();
+        assert_eq!(lines.len(), 800);
+        for line in lines.iter() {
+            let index = line.split(' ').next().unwrap().to_string();
+            let expected: String = [index.as_str(), " ", &index, " ", &index, "\n"].concat();
+            assert_eq!(*line, expected);
+        }
+    }
+}
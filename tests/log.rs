@@ -0,0 +1,104 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use custom_print::Level;
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(true);
+static EVALUATED: AtomicUsize = AtomicUsize::new(0);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn write_error(value: &str) {
+    CHUNKS.lock().unwrap().push(std::format!("E {}", value));
+}
+
+fn write_level(level: Level, value: &str) {
+    CHUNKS
+        .lock()
+        .unwrap()
+        .push(std::format!("[{:5}] {}", level, value));
+}
+
+fn try_write(value: &str) -> Result<(), fmt::Error> {
+    if SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed) {
+        CHUNKS.lock().unwrap().push(String::from(value));
+        Ok(())
+    } else {
+        Err(fmt::Error)
+    }
+}
+
+fn evaluate<T>(value: T) -> T {
+    let _ = EVALUATED.fetch_add(1, Ordering::Relaxed);
+    value
+}
+
+custom_print::define_macro!(error, concat, crate::write_error);
+custom_print::define_macros!({ warn, info, debug, trace }, level, concat, crate::write_level);
+custom_print::define_macros!({ try_info, try_debug as try_dbg_log }, concat, crate::try_write);
+
+pub mod submodule {
+    #[test]
+    fn test_log() {
+        use crate::{evaluate, take_chunks, EVALUATED, SHOULD_WRITE_SUCCEED};
+        use core::fmt;
+        use core::sync::atomic::Ordering;
+        use custom_print::{max_level, set_max_level, LevelFilter};
+
+        assert_eq!(max_level(), LevelFilter::Trace);
+
+        let () = error!("first {}", evaluate(1));
+        let () = warn!("second");
+        let () = info!();
+        let () = debug!("third");
+        let () = trace!("fourth");
+        assert_eq!(
+            take_chunks(),
+            &[
+                "E first 1\n",
+                "[warn ] second\n",
+                "[info ] \n",
+                "[debug] third\n",
+                "[trace] fourth\n"
+            ]
+        );
+        assert_eq!(EVALUATED.swap(0, Ordering::Relaxed), 1);
+
+        set_max_level(LevelFilter::Warn);
+        assert_eq!(max_level(), LevelFilter::Warn);
+        let () = error!("first");
+        let () = warn!("second");
+        let () = info!("third {}", evaluate(3));
+        let () = trace!("fourth {}", evaluate(4));
+        assert_eq!(take_chunks(), &["E first\n", "[warn ] second\n"]);
+        assert_eq!(EVALUATED.load(Ordering::Relaxed), 0);
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        assert_eq!(try_info!("first {}", evaluate(1)), Ok(()));
+        assert_eq!(EVALUATED.load(Ordering::Relaxed), 0);
+        set_max_level(LevelFilter::Info);
+        assert_eq!(try_info!("first"), Err(fmt::Error));
+        assert_eq!(try_dbg_log!("second"), Ok(()));
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+        assert_eq!(try_info!("third"), Ok(()));
+        assert_eq!(take_chunks(), &["third\n"]);
+
+        set_max_level(LevelFilter::Off);
+        let () = error!("first");
+        assert_eq!(take_chunks(), &[""; 0][..]);
+        set_max_level(LevelFilter::Trace);
+    }
+}
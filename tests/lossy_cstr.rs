@@ -0,0 +1,115 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::os::raw::c_char;
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static IO_CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn take_io_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut IO_CHUNKS.lock().unwrap())
+}
+
+fn push_chunk(chunks: &Mutex<Vec<String>>, c_str: *const c_char) {
+    use std::ffi::CStr;
+    use std::string::ToString;
+    let mut chunks = chunks.lock().unwrap();
+    let c_str = unsafe { CStr::from_ptr(c_str) };
+    chunks.push(c_str.to_str().unwrap().to_string());
+}
+
+fn write_fn(c_str: *const c_char) {
+    push_chunk(&CHUNKS, c_str);
+}
+
+fn write_errno_fn(c_str: *const c_char) -> i32 {
+    write_fn(c_str);
+    let c_str = unsafe { std::ffi::CStr::from_ptr(c_str) };
+    if c_str.to_bytes().starts_with(b"error") {
+        -5
+    } else {
+        0
+    }
+}
+
+unsafe fn write_len_fn(c_str: *const c_char) -> usize {
+    push_chunk(&IO_CHUNKS, c_str);
+    std::ffi::CStr::from_ptr(c_str).to_bytes().len()
+}
+
+custom_print::define_macros!({ println, try_dbg }, concat, lossy: crate::write_fn);
+custom_print::define_macros!(
+    { print as replace_print },
+    fmt,
+    lossy(custom_print::NulPolicy::REPLACEMENT_CHARACTER): crate::write_fn
+);
+custom_print::define_macros!(
+    { try_print as split_print },
+    fmt,
+    errno: lossy(custom_print::NulPolicy::Split): crate::write_errno_fn
+);
+custom_print::define_macros!(
+    { print as io_print },
+    io,
+    lossy: unsafe fn (crate::write_len_fn)(*const std::os::raw::c_char) -> usize
+);
+custom_print::define_macros!(
+    { print as io_split_print },
+    io,
+    lossy(custom_print::NulPolicy::Split): unsafe fn (crate::write_len_fn)(
+        *const std::os::raw::c_char
+    ) -> usize
+);
+
+pub mod submodule {
+    #[test]
+    fn test_lossy_cstr() {
+        use crate::take_chunks;
+
+        println!("first\0second");
+        assert_eq!(take_chunks(), &["first\\0second\n"]);
+        assert_eq!(try_dbg!("\0"), Ok("\0"));
+        assert_eq!(take_chunks().len(), 1);
+
+        replace_print!("\0first\0\0second\0");
+        assert_eq!(
+            take_chunks(),
+            &["\u{FFFD}first\u{FFFD}\u{FFFD}second\u{FFFD}"]
+        );
+
+        assert!(split_print!("\0first\0\0second\0").is_ok());
+        assert_eq!(take_chunks(), &["first", "second"]);
+        assert!(split_print!("\0\0").is_ok());
+        assert_eq!(take_chunks(), &[""]);
+        assert_eq!(
+            split_print!("first\0error\0second"),
+            Err(custom_print::FmtWriteError::Write(
+                custom_print::ReturnError::Errno(-5)
+            ))
+        );
+        assert_eq!(take_chunks(), &["first", "error"]);
+    }
+
+    #[test]
+    fn test_lossy_cstr_io_len() {
+        use crate::take_io_chunks;
+
+        io_print!("a\0b");
+        assert_eq!(take_io_chunks(), &["a\\0b"]);
+        io_split_print!("ab\0c");
+        assert_eq!(take_io_chunks(), &["ab", "c"]);
+    }
+}
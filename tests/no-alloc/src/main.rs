@@ -15,6 +15,54 @@ pub extern "C" fn _start() -> ! {
 }
 
 custom_print::define_macros!({ print, println, dbg }, fmt, |_value: &str| { /* ... */ });
+custom_print::define_macros!({ cprint, cprintln }, concat_stack<256>, |_value: &str| { /* ... */ });
+custom_print::define_macros!(
+    { print as c_char_print, println as c_char_println },
+    fmt,
+    cstr_stack<64>: |_value: *const core::ffi::c_char| { /* ... */ }
+);
+custom_print::define_macros!(
+    { print as chunk_print, println as chunk_println },
+    concat_stack<256>,
+    chunk<64>(custom_print::ChunkOverflow::ELLIPSIS): |_value: &str| { /* ... */ }
+);
+custom_print::define_macros!(
+    { print as prefix_print, println as prefix_println, dbg as prefix_dbg },
+    prefix("[test] "),
+    fmt,
+    |_value: &str| { /* ... */ }
+);
+custom_print::define_macros!(
+    {
+        print(prefix = "[test] ") as static_prefix_print,
+        println(prefix = "[test] ", suffix = ";") as static_prefix_println,
+        dbg(prefix = "[test] ") as static_prefix_dbg,
+    },
+    fmt,
+    |_value: &str| { /* ... */ }
+);
+custom_print::define_macros!(
+    { print as locked_print, println as locked_println, dbg as locked_dbg },
+    locked,
+    fmt,
+    |_value: &str| { /* ... */ }
+);
+custom_print::define_macros!(
+    { print as guarded_print, println as guarded_println, dbg as guarded_dbg },
+    guarded,
+    locked,
+    fmt,
+    |_value: &str| { /* ... */ }
+);
+custom_print::define_macros!(
+    { print as global_print, println as global_println, dbg as global_dbg },
+    global
+);
+custom_print::define_macros!(
+    { print as utf16_print, println as utf16_println },
+    fmt,
+    |_value: &[u16]| { /* ... */ }
+);
 
 #[no_mangle]
 pub extern "C" fn test() {
@@ -23,4 +71,29 @@ pub extern "C" fn test() {
     print!("test");
     println!("test");
     dbg!("test");
+    cprint!("test");
+    cprintln!("test");
+    c_char_print!("test");
+    c_char_println!("test");
+    utf16_print!("test");
+    utf16_println!("test");
+    chunk_print!("test");
+    chunk_println!("test");
+    prefix_print!("test");
+    prefix_println!("test");
+    prefix_dbg!("test");
+    static_prefix_print!("test");
+    static_prefix_println!("test");
+    static_prefix_dbg!("test");
+    locked_print!("test");
+    locked_println!("test");
+    locked_dbg!("test", "test");
+    guarded_print!("test");
+    guarded_println!("test");
+    guarded_dbg!("test");
+    let _ = custom_print::GLOBAL_SINK.set_sink(|_value: &str| { /* ... */ });
+    global_print!("test");
+    global_println!("test");
+    global_dbg!("test");
+    let _ = custom_print::GLOBAL_SINK.take_sink();
 }
@@ -0,0 +1,202 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::str::from_utf8;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::io::{self, Write};
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static OTHER_CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static CONSTRUCTED: AtomicUsize = AtomicUsize::new(0);
+static TAGGED: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+#[derive(Clone, Debug)]
+struct ChunkWriter;
+
+impl ChunkWriter {
+    fn new() -> Self {
+        let _ = CONSTRUCTED.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        use std::string::ToString;
+        let mut chunks = CHUNKS.lock().unwrap();
+        chunks.push(from_utf8(buf).unwrap().to_string());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct TagWriter(&'static str);
+
+impl Write for TagWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut tagged = TAGGED.lock().unwrap();
+        tagged.push(std::format!("[{}] {}", self.0, from_utf8(buf).unwrap()));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn take_tagged(tag: &str) -> Vec<String> {
+    let prefix = std::format!("[{}] ", tag);
+    let mut tagged = TAGGED.lock().unwrap();
+    let (taken, rest) = tagged
+        .drain(..)
+        .partition(|chunk: &String| chunk.starts_with(&prefix));
+    *tagged = rest;
+    taken
+}
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+custom_print::define_macros!(
+    { print, println, dbg, flush, try_print, try_flush },
+    once: ::std::io::LineWriter::new(crate::ChunkWriter::new())
+);
+custom_print::define_macro!(eprintln, once: crate::logging_writer());
+custom_print::define_macro!(println as recursive_println, once: crate::recursive_writer());
+custom_print::define_macro!(println as nested_println, once: crate::TagWriter("nested"));
+
+struct Nested;
+
+impl core::fmt::Display for Nested {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        nested_println!("inner");
+        f.write_str("value")
+    }
+}
+
+mod a {
+    use std::io::Write;
+
+    const NAME: &str = "a";
+    custom_print::define_macro!(println as tag_println, once: crate::TagWriter(NAME));
+
+    pub fn run() {
+        tag_println!("from a");
+    }
+}
+
+mod b {
+    use std::io::Write;
+
+    const NAME: &str = "b";
+    custom_print::define_macro!(println as tag_println, once: crate::TagWriter(NAME));
+
+    pub fn run() {
+        tag_println!("from b");
+    }
+}
+
+fn write_other(value: &str) {
+    use std::string::ToString;
+    OTHER_CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+type OtherWriter = custom_print::ConcatWriter<custom_print::WriteStrFn<fn(&str), ()>>;
+
+fn logging_writer() -> OtherWriter {
+    println!("constructing");
+    custom_print::ConcatWriter::from_closure(write_other as fn(&str))
+}
+
+fn recursive_writer() -> OtherWriter {
+    recursive_println!("constructing");
+    custom_print::ConcatWriter::from_closure(write_other as fn(&str))
+}
+
+pub mod submodule {
+    #[test]
+    fn test_once_writer() {
+        use crate::{black_box, take_chunks, CONSTRUCTED};
+        use core::sync::atomic::Ordering;
+        use std::io::Write;
+
+        print!("first");
+        assert_eq!(take_chunks(), &[""; 0][..]);
+        print!("first {}\nthird\n", black_box("second"));
+        assert_eq!(take_chunks(), &["firstfirst second\nthird\n"]);
+
+        println!();
+        assert_eq!(take_chunks(), &["\n"]);
+        println!("first {}\nthird\n", black_box("second"));
+        assert_eq!(take_chunks(), &["first second\nthird\n\n"]);
+
+        print!("first");
+        print!("\nsecond");
+        assert!(try_print!(" third").is_ok());
+        assert_eq!(take_chunks(), &["first\n"]);
+        flush!();
+        assert_eq!(take_chunks(), &["second third"]);
+
+        assert!(try_print!("fourth").is_ok());
+        assert_eq!(take_chunks(), &[""; 0][..]);
+        assert!(try_flush!().is_ok());
+        assert_eq!(take_chunks(), &["fourth"]);
+
+        assert_eq!(CONSTRUCTED.load(Ordering::Relaxed), 1);
+
+        eprintln!("fifth");
+        eprintln!("sixth");
+        assert_eq!(take_chunks(), ["constructing\n"]);
+        assert_eq!(*crate::OTHER_CHUNKS.lock().unwrap(), ["fifth\n", "sixth\n"]);
+    }
+
+    #[test]
+    fn test_once_writer_modules() {
+        use crate::take_tagged;
+
+        crate::a::run();
+        crate::b::run();
+        crate::a::run();
+        assert_eq!(take_tagged("a").concat(), "[a] from a\n[a] from a\n");
+        assert_eq!(take_tagged("b").concat(), "[b] from b\n");
+    }
+
+    #[test]
+    fn test_once_writer_nested() {
+        use crate::{take_tagged, Nested};
+        use std::io::Write;
+
+        nested_println!("outer {}", Nested);
+        assert_eq!(
+            take_tagged("nested").concat(),
+            "[nested] outer [nested] inner\n[nested] value[nested] \n"
+        );
+    }
+
+    #[test]
+    fn test_once_writer_recursive_init() {
+        let result = std::panic::catch_unwind(|| recursive_println!("first"));
+        assert!(result.is_err());
+    }
+}
@@ -43,7 +43,7 @@ mod submodule {
         assert!(result.is_err());
         let message = take_message();
         assert!(message.contains("panicked"));
-        assert!(message.contains("assertion failed"));
+        assert!(message.contains("assertion") && message.contains("failed"));
         assert!(message.contains(&format!("{}:{}:", file, line.load(Ordering::Relaxed))));
     }
 }
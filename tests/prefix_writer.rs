@@ -0,0 +1,189 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static LINE: AtomicUsize = AtomicUsize::new(0);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+fn write_fmt(value: &str) {
+    write(value);
+}
+
+fn line_number() -> usize {
+    LINE.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+custom_print::define_macros!({ print, println, dbg }, prefix("[net] "), concat, crate::write);
+custom_print::define_macros!({ eprint, eprintln }, prefix("> "), fmt, crate::write_fmt);
+custom_print::define_macros!(
+    { try_print, try_println },
+    prefix(|| std::format!("{}: ", crate::line_number())),
+    concat,
+    crate::write
+);
+custom_print::define_macro!(print as aprint, prefix("a "), concat, crate::write);
+custom_print::define_macro!(print as bprint, prefix("b "), concat, crate::write);
+
+static MODULE_CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+mod a {
+    fn w(value: &str) {
+        crate::MODULE_CHUNKS
+            .lock()
+            .unwrap()
+            .push(std::format!("A<{}>", value));
+    }
+
+    custom_print::define_macro!(print as cprint, prefix("[net] "), concat, w);
+
+    pub fn write(value: &str) {
+        cprint!("{}", value);
+    }
+}
+
+mod b {
+    fn w(value: &str) {
+        crate::MODULE_CHUNKS
+            .lock()
+            .unwrap()
+            .push(std::format!("B<{}>", value));
+    }
+
+    custom_print::define_macro!(print as cprint, prefix("[net] "), concat, w);
+
+    pub fn write(value: &str) {
+        cprint!("{}", value);
+    }
+}
+
+pub mod submodule {
+    #[test]
+    fn test_prefix_writer() {
+        use crate::{black_box, take_chunks};
+
+        print!("first");
+        assert_eq!(take_chunks(), ["[net] first"]);
+        println!(" {}", black_box("second"));
+        assert_eq!(take_chunks(), [" second\n"]);
+        println!("third\n{}", black_box("fourth"));
+        assert_eq!(take_chunks(), ["[net] third\n[net] fourth\n"]);
+
+        let _ = dbg!(black_box((1, 2)));
+        let chunks = take_chunks();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].starts_with("[net] [tests/prefix_writer.rs:"));
+        assert!(chunks[0].ends_with("\n[net]     1,\n[net]     2,\n[net] )\n"));
+
+        eprint!("first\nsec{}", black_box("ond"));
+        assert_eq!(take_chunks(), ["> ", "first\n", "> ", "sec", "ond"]);
+        eprintln!();
+        assert_eq!(take_chunks(), ["\n"]);
+        print!("fifth");
+        assert_eq!(take_chunks(), ["[net] fifth"]);
+        println!();
+        assert_eq!(take_chunks(), ["\n"]);
+
+        assert_eq!(try_println!("first\n{}", black_box("second")), Ok(()));
+        assert_eq!(try_print!("third"), Ok(()));
+        assert_eq!(try_println!(), Ok(()));
+        assert_eq!(take_chunks(), ["1: first\n2: second\n", "3: third", "\n"]);
+
+        aprint!("first");
+        bprint!("second");
+        aprint!(" third\n");
+        bprint!("\n");
+        assert_eq!(take_chunks(), ["a first", "b second", " third\n", "\n"]);
+    }
+
+    #[test]
+    fn test_prefix_writer_modules() {
+        use crate::{a, b, MODULE_CHUNKS};
+
+        a::write("first");
+        b::write("second\n");
+        a::write(" third\n");
+        b::write("fourth");
+        assert_eq!(
+            *MODULE_CHUNKS.lock().unwrap(),
+            [
+                "A<[net] first>",
+                "B<[net] second\n>",
+                "A< third\n>",
+                "B<[net] fourth>"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prefix_writer_keys() {
+        use custom_print::{ConcatWriter, PrefixWriter};
+        use std::string::ToString;
+        use std::sync::{Arc, Mutex};
+        use std::vec::Vec;
+
+        let chunks = Arc::new(Mutex::new(Vec::new()));
+        let writer = |prefix: &'static str| {
+            let chunks = chunks.clone();
+            PrefixWriter::new(
+                prefix,
+                ConcatWriter::from_closure(move |value: &str| {
+                    chunks.lock().unwrap().push(value.to_string())
+                }),
+            )
+        };
+        let take = || core::mem::take(&mut *chunks.lock().unwrap());
+
+        let mut first = writer("1: ");
+        let mut second = writer("2: ");
+        first.write_fmt(format_args!("first")).unwrap();
+        second.write_fmt(format_args!("second")).unwrap();
+        first.write_fmt(format_args!(" third\n")).unwrap();
+        assert_eq!(take(), ["1: first", "2: second", " third\n"]);
+
+        let keys: Vec<&'static str> = (0..40)
+            .map(|index| &*std::boxed::Box::leak(std::format!("key{}", index).into_boxed_str()))
+            .collect();
+        for key in &keys {
+            writer("> ")
+                .with_key(key)
+                .write_fmt(format_args!("{}", key))
+                .unwrap();
+        }
+        for key in &keys {
+            writer("> ")
+                .with_key(key)
+                .write_fmt(format_args!("\n"))
+                .unwrap();
+        }
+        let chunks = take();
+        assert_eq!(chunks.len(), 80);
+        assert!(chunks[..40].iter().all(|chunk| chunk.starts_with("> key")));
+        assert!(chunks[40..].iter().all(|chunk| chunk == "\n"));
+    }
+}
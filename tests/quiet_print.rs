@@ -0,0 +1,70 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(true);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct WriteError;
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn write(value: &str) -> Result<(), WriteError> {
+    if SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed) {
+        CHUNKS.lock().unwrap().push(String::from(value));
+        Ok(())
+    } else {
+        Err(WriteError)
+    }
+}
+
+fn flush() -> Result<(), WriteError> {
+    if SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed) {
+        Ok(())
+    } else {
+        Err(WriteError)
+    }
+}
+
+custom_print::define_macros!(
+    { quiet_print, quiet_eprintln, quiet_dbg, quiet_flush },
+    concat, crate::write, flush: crate::flush
+);
+
+pub mod submodule {
+    #[test]
+    fn test_quiet_print() {
+        use crate::{take_chunks, SHOULD_WRITE_SUCCEED};
+        use core::sync::atomic::Ordering;
+
+        let () = quiet_print!("first");
+        let () = quiet_eprintln!("second {}", 2);
+        assert_eq!(quiet_dbg!("third"), "third");
+        let () = quiet_flush!();
+        let chunks = take_chunks();
+        assert_eq!(chunks[..2], ["first", "second 2\n"]);
+        assert_eq!(chunks.len(), 3);
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        let () = quiet_print!("first");
+        let () = quiet_eprintln!();
+        assert_eq!(quiet_dbg!("third"), "third");
+        assert_eq!(quiet_dbg!(1, 2), (1, 2));
+        assert_eq!(quiet_dbg!(), ());
+        let () = quiet_flush!();
+        assert_eq!(take_chunks().len(), 0);
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+    }
+}
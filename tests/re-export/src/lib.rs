@@ -46,7 +46,7 @@ custom_print::define_macros!(#[macro_export] {
     eflush,
     try_flush,
     try_eflush,
-}, concat, $crate::flush_fn);
+}, concat, $crate::write_fn, flush: $crate::flush_fn);
 
 #[test]
 fn test_re_export_macro() {
@@ -65,6 +65,10 @@ fn test_re_export_macro() {
     assert!(result.is_ok());
     let (result, line4) = (try_edbg!("try_edbg"), line!());
     assert!(result.is_ok());
+    flush!();
+    eflush!();
+    assert!(try_flush!().is_ok());
+    assert!(try_eflush!().is_ok());
 
     let chunks = take_chunks();
     let chunks: Vec<_> = chunks
@@ -85,7 +89,11 @@ fn test_re_export_macro() {
             Some("try_println\n"),
             Some("try_eprintln\n"),
             Some(&format!("[{file}:{line3}] \"try_dbg\" = \"try_dbg\"\n")),
-            Some(&format!("[{file}:{line4}] \"try_edbg\" = \"try_edbg\"\n"))
+            Some(&format!("[{file}:{line4}] \"try_edbg\" = \"try_edbg\"\n")),
+            None,
+            None,
+            None,
+            None,
         ]
     );
 }
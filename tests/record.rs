@@ -0,0 +1,135 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt;
+use custom_print::{Record, RecordKind};
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+type Records = Vec<(RecordKind, u32, &'static str, String)>;
+
+static RECORDS: Lazy<Mutex<Records>> = Lazy::new(Mutex::default);
+
+fn take_records() -> Records {
+    use core::mem::take;
+    take(&mut RECORDS.lock().unwrap())
+}
+
+fn write(record: &Record<'_>) {
+    assert!(record.file().ends_with("record.rs"));
+    assert!(record.column() > 0);
+    RECORDS.lock().unwrap().push((
+        record.kind(),
+        record.line(),
+        record.module_path(),
+        String::from(record.message()),
+    ));
+}
+
+fn try_write(record: &Record<'_>) -> Result<(), fmt::Error> {
+    write(record);
+    Ok(())
+}
+
+custom_print::define_macros!({ cprint, ceprintln, cdbg }, concat, crate::write);
+custom_print::define_macros!({ try_eprint, warn, try_edbg }, fmt, crate::try_write);
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+struct Nested;
+
+impl fmt::Display for Nested {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        cprint!("nested");
+        f.write_str("outer")
+    }
+}
+
+pub mod submodule {
+    #[test]
+    fn test_record() {
+        use crate::{black_box, take_records, Nested};
+        use custom_print::{ConcatWriter, Level, Record, RecordKind};
+        use std::string::String;
+
+        let path = "record::submodule";
+
+        let () = cprint!("first");
+        let () = ceprintln!("second");
+        let _ = cdbg!(1);
+        let line = line!();
+        let records = take_records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[..2],
+            [
+                (RecordKind::Print, line - 3, path, String::from("first")),
+                (RecordKind::Eprint, line - 2, path, String::from("second\n")),
+            ]
+        );
+        assert_eq!(records[2].0, RecordKind::Dbg);
+        assert_eq!(records[2].1, line - 1);
+
+        assert_eq!(try_edbg!(2), Ok(2));
+        let line = line!();
+        let records = take_records();
+        assert!(!records.is_empty());
+        assert!(records
+            .iter()
+            .all(|record| record.0 == RecordKind::Edbg && record.1 == line - 1));
+
+        assert_eq!(try_eprint!("third {}", black_box(3)), Ok(()));
+        let () = warn!("{}", black_box("fourth"));
+        let line = line!();
+        assert_eq!(
+            take_records(),
+            [
+                (RecordKind::Eprint, line - 2, path, String::from("third ")),
+                (RecordKind::Eprint, line - 2, path, String::from("3")),
+                (
+                    RecordKind::Log(Level::Warn),
+                    line - 1,
+                    path,
+                    String::from("fourth")
+                ),
+                (
+                    RecordKind::Log(Level::Warn),
+                    line - 1,
+                    path,
+                    String::from("\n")
+                ),
+            ]
+        );
+
+        let () = ceprintln!("{}", Nested);
+        let line = line!();
+        let records = take_records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, RecordKind::Print);
+        assert_eq!(records[0].2, "record");
+        assert_eq!(records[0].3, "nested");
+        assert_eq!(
+            records[1],
+            (RecordKind::Eprint, line - 1, path, String::from("outer\n"))
+        );
+
+        let mut kinds = std::vec::Vec::new();
+        let mut writer = ConcatWriter::from_closure(|record: &Record<'_>| {
+            kinds.push((record.kind(), record.line(), record.message().len()));
+        });
+        writer.write_fmt(format_args!("fifth")).unwrap();
+        assert_eq!(kinds, [(RecordKind::Unknown, 0, 5)]);
+    }
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::string::String;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(true);
+static STRING: Lazy<Mutex<String>> = Lazy::new(Mutex::default);
+
+fn take_string() -> String {
+    use core::mem::take;
+    take(&mut STRING.lock().unwrap())
+}
+
+fn succeed() -> bool {
+    SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed)
+}
+
+fn write_errno(value: &str) -> i32 {
+    if succeed() {
+        STRING.lock().unwrap().push_str(value);
+        value.len() as i32
+    } else {
+        -5
+    }
+}
+
+fn write_errno_bytes(value: &[u8]) -> isize {
+    if succeed() {
+        let half = (value.len() + 1) / 2;
+        STRING
+            .lock()
+            .unwrap()
+            .push_str(core::str::from_utf8(&value[..half]).unwrap());
+        half as isize
+    } else {
+        -11
+    }
+}
+
+unsafe fn write_cstr_errno(value: *const c_char) -> i32 {
+    let value = CStr::from_ptr(value).to_str().unwrap();
+    write_errno(value)
+}
+
+fn write_bool(value: &str) -> bool {
+    if succeed() {
+        STRING.lock().unwrap().push_str(value);
+    }
+    succeed()
+}
+
+fn write_option(value: &str) -> Option<usize> {
+    if write_bool(value) {
+        Some(value.len())
+    } else {
+        None
+    }
+}
+
+fn write_ignored(value: &[u8]) -> &'static str {
+    let _ = write_bool(core::str::from_utf8(value).unwrap());
+    "promise"
+}
+
+fn write_failed(_: &str) -> i32 {
+    -5
+}
+
+fn write_zero(_: &[u8]) -> i32 {
+    0
+}
+
+fn flush_errno() -> i32 {
+    if succeed() {
+        0
+    } else {
+        -1
+    }
+}
+
+custom_print::define_macros!(
+    { cprint, try_print as try_concat_print },
+    concat,
+    errno: crate::write_errno
+);
+custom_print::define_macros!(
+    { try_print as try_fmt_print, try_flush as try_fmt_flush },
+    fmt,
+    errno: crate::write_errno,
+    flush: errno: crate::flush_errno
+);
+custom_print::define_macros!(
+    { try_print as try_io_print },
+    io,
+    errno_count: crate::write_errno_bytes
+);
+custom_print::define_macros!({ print as zero_print, try_print as try_zero_print }, io, errno: crate::write_zero);
+custom_print::define_macro!(try_print as try_zero_count_print, io, errno_count: crate::write_zero);
+custom_print::define_macro!(
+    try_print as try_cstr_print,
+    fmt,
+    errno: unsafe fn (crate::write_cstr_errno)(*const std::os::raw::c_char)
+);
+custom_print::define_macro!(print as failed_print, fmt, errno: crate::write_failed);
+custom_print::define_macro!(try_print as try_bool_print, fmt, bool: crate::write_bool);
+custom_print::define_macro!(try_print as try_option_print, concat, option: crate::write_option);
+custom_print::define_macro!(try_print as try_ignored_print, io, ignore: crate::write_ignored);
+
+pub mod submodule {
+    #[test]
+    fn test_return_convention() {
+        use crate::{take_string, SHOULD_WRITE_SUCCEED};
+        use core::sync::atomic::Ordering;
+        use custom_print::{FmtWriteError, ReturnError};
+
+        let () = cprint!("first");
+        assert_eq!(try_concat_print!("second"), Ok(()));
+        assert_eq!(try_fmt_print!("third"), Ok(()));
+        assert_eq!(try_fmt_flush!(), Ok(()));
+        assert!(try_io_print!("fourth").is_ok());
+        assert_eq!(try_cstr_print!("fifth"), Ok(()));
+        assert_eq!(try_bool_print!("sixth"), Ok(()));
+        assert_eq!(try_option_print!("seventh"), Ok(()));
+        assert!(try_ignored_print!("eighth").is_ok());
+        assert_eq!(
+            take_string(),
+            "firstsecondthirdfourthfifthsixthseventheighth"
+        );
+
+        assert_eq!(
+            try_cstr_print!("\0").unwrap_err(),
+            FmtWriteError::Write(ReturnError::Inner(
+                std::ffi::CString::new("\0").unwrap_err()
+            ))
+        );
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        assert_eq!(try_concat_print!("first"), Err(ReturnError::Errno(-5)));
+        assert_eq!(
+            try_fmt_print!("second"),
+            Err(FmtWriteError::Write(ReturnError::Errno(-5)))
+        );
+        assert_eq!(try_fmt_flush!(), Err(ReturnError::Errno(-1)));
+        let err = try_io_print!("third").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+        assert_eq!(
+            std::string::ToString::to_string(&err),
+            "function returned error code -11"
+        );
+        assert_eq!(
+            try_cstr_print!("fourth"),
+            Err(FmtWriteError::Write(ReturnError::Errno(-5)))
+        );
+        assert_eq!(
+            try_bool_print!("fifth"),
+            Err(FmtWriteError::Write(ReturnError::Failed))
+        );
+        assert_eq!(try_option_print!("sixth"), Err(ReturnError::Missing));
+        assert!(try_ignored_print!("seventh").is_ok());
+        assert_eq!(take_string(), "");
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn test_return_convention_zero() {
+        let () = zero_print!("first");
+        assert!(try_zero_print!("second").is_ok());
+        let err = try_zero_count_print!("third").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed writing: Errno(-5)")]
+    fn test_return_convention_panic() {
+        failed_print!("first");
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write(value: &str) {
+    CHUNKS.lock().unwrap().push(value.to_string());
+}
+
+custom_print::define_macros!(
+    {
+        print(prefix = "[gpu] "),
+        println(prefix = "[gpu] ", suffix = " ;"),
+        dbg(prefix = "[gpu] "),
+        try_println(suffix = " {{ok}}") as try_cprintln,
+    },
+    concat,
+    crate::write
+);
+custom_print::define_macro!(eprintln(suffix = "!", prefix = "> "), fmt, crate::write);
+custom_print::define_macro!(info(prefix = "[gpu] "), concat, crate::write);
+
+pub mod submodule {
+    #[test]
+    fn test_static_prefix() {
+        use crate::{black_box, take_chunks};
+
+        print!("first");
+        assert_eq!(take_chunks(), ["[gpu] first"]);
+        print!();
+        assert_eq!(take_chunks(), ["[gpu] "]);
+        println!("{} {}", black_box("second"), black_box(3));
+        assert_eq!(take_chunks(), ["[gpu] second 3 ;\n"]);
+        println!();
+        assert_eq!(take_chunks(), ["[gpu]  ;\n"]);
+
+        let _ = dbg!(black_box(1));
+        let chunks = take_chunks();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].starts_with("[gpu] [tests/static_prefix.rs:"));
+        assert!(chunks[0].ends_with("] black_box(1) = 1\n"));
+
+        assert_eq!(try_cprintln!("{}", black_box("fourth")), Ok(()));
+        assert_eq!(take_chunks(), ["fourth {ok}\n"]);
+
+        eprintln!("fifth {}", black_box(6));
+        assert_eq!(take_chunks(), ["> fifth ", "6", "!\n"]);
+
+        custom_print::set_max_level(custom_print::LevelFilter::Info);
+        info!("seventh");
+        assert_eq!(take_chunks(), ["[gpu] seventh\n"]);
+    }
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<String>>> = Lazy::new(Mutex::default);
+static SHOULD_WRITE_SUCCEED: AtomicBool = AtomicBool::new(true);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut CHUNKS.lock().unwrap())
+}
+
+fn push(prefix: &str, value: &str) {
+    let mut chunks = CHUNKS.lock().unwrap();
+    chunks.push(std::format!("{}{}", prefix, value));
+}
+
+fn write_log(value: &str) {
+    push("log:", value);
+}
+
+fn write_bytes(value: &[u8]) {
+    push("io:", core::str::from_utf8(value).unwrap());
+}
+
+fn write_fmt(value: &str) {
+    push("fmt:", value);
+}
+
+fn flush() {
+    push("flush", "");
+}
+
+fn try_write(value: &str) -> Result<(), fmt::Error> {
+    if SHOULD_WRITE_SUCCEED.load(Ordering::Relaxed) {
+        push("try:", value);
+        Ok(())
+    } else {
+        Err(fmt::Error)
+    }
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+custom_print::define_macros!(
+    { print, println, dbg, flush },
+    tee(concat, crate::write_log, flush: crate::flush; io, crate::write_bytes; fmt, crate::write_fmt)
+);
+
+custom_print::define_macros!(
+    { try_print, try_println, try_dbg, try_flush },
+    tee(concat, crate::write_log; concat, crate::try_write)
+);
+
+pub mod submodule {
+    #[test]
+    fn test_tee_writer() {
+        use crate::{black_box, take_chunks, SHOULD_WRITE_SUCCEED};
+        use core::fmt;
+        use core::sync::atomic::Ordering;
+        use custom_print::{NeverError, TeeError};
+
+        let () = print!("first");
+        assert_eq!(take_chunks(), &["log:first", "io:first", "fmt:first"]);
+        let () = println!("first {}", black_box("second"));
+        assert_eq!(
+            take_chunks(),
+            &[
+                "log:first second\n",
+                "io:first ",
+                "io:second",
+                "io:\n",
+                "fmt:first ",
+                "fmt:second",
+                "fmt:\n"
+            ]
+        );
+        let output = dbg!(1);
+        assert_eq!(output, 1);
+        assert!(take_chunks()[0].starts_with("log:["));
+        let () = flush!();
+        assert_eq!(take_chunks(), &["flush"]);
+
+        assert_eq!(try_print!("first"), Ok(()));
+        assert_eq!(take_chunks(), &["log:first", "try:first"]);
+        assert_eq!(try_flush!(), Ok(()));
+        assert_eq!(take_chunks(), &[""; 0][..]);
+
+        SHOULD_WRITE_SUCCEED.store(false, Ordering::Relaxed);
+        assert_eq!(
+            try_println!("second"),
+            Err(TeeError::<NeverError, _>::Second(fmt::Error))
+        );
+        assert!(try_dbg!("third").is_err());
+        assert_eq!(take_chunks().len(), 2);
+        SHOULD_WRITE_SUCCEED.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "testing")]
+#![no_std]
+
+extern crate std;
+
+use custom_print::testing::Recorder;
+use custom_print::GlobalSink;
+
+custom_print::define_macros!({ print, println, dbg, flush }, capture);
+custom_print::define_macros!({ cprintln, try_flush }, concat, custom_print::testing::write, flush: custom_print::testing::flush);
+custom_print::define_macros!({ try_println, try_dbg }, capture);
+custom_print::define_macros!({ eprintln }, global);
+custom_print::define_macro!(println as sink_println, global(&crate::SINK));
+
+static SINK: GlobalSink = GlobalSink::new();
+
+fn recorder_with_flush() -> Recorder {
+    let recorder = Recorder::new();
+    recorder.write("first");
+    recorder.flush();
+    recorder
+}
+
+pub mod submodule {
+    #[test]
+    fn test_capture() {
+        use custom_print::testing::{capture, Event};
+        use custom_print::{assert_chunks, assert_printed};
+        use std::string::ToString;
+
+        let value = 1;
+        let recorder = capture(|| {
+            print!("first {}", value);
+            flush!();
+            println!("second");
+        });
+        assert_printed!(recorder, "first 1second\n");
+        assert_chunks!(recorder, ["first ", "1", "second\n"]);
+        assert_eq!(recorder.write_count(), 3);
+        assert_eq!(recorder.flush_count(), 1);
+        assert_eq!(
+            recorder.events(),
+            [
+                Event::Write("first ".to_string()),
+                Event::Write("1".to_string()),
+                Event::Flush,
+                Event::Write("second\n".to_string()),
+            ]
+        );
+
+        let recorder = capture(|| {
+            cprintln!("third {}", 3);
+            assert_eq!(try_flush!(), Ok(()));
+            assert_eq!(try_println!("fourth"), Ok(()));
+            assert_eq!(dbg!(5), 5);
+            assert_eq!(try_dbg!(6), Ok(6));
+        });
+        assert_eq!(recorder.chunks()[..2], ["third 3\n", "fourth\n"]);
+        assert_eq!(recorder.flush_count(), 1);
+        assert!(recorder.text().contains("] 5 = 5\n["));
+        assert!(recorder.text().ends_with("] 6 = 6\n"));
+
+        println!("discarded");
+    }
+
+    #[test]
+    fn test_capture_global_sink() {
+        use crate::SINK;
+        use custom_print::assert_chunks;
+        use custom_print::testing::{capture, Recorder};
+
+        let value = 1;
+        let recorder = capture(|| eprintln!("first {}", value));
+        assert_chunks!(recorder, ["first ", "1", "\n"]);
+
+        let sink = Recorder::new();
+        let recorder = SINK.with_sink(sink.clone(), || capture(|| sink_println!("second")));
+        assert_chunks!(recorder, ["second\n"]);
+        assert!(sink.events().is_empty());
+    }
+
+    #[test]
+    fn test_nested_capture() {
+        use custom_print::assert_printed;
+        use custom_print::testing::capture;
+
+        let mut inner = None;
+        let outer = capture(|| {
+            println!("first");
+            inner = Some(capture(|| println!("second")));
+            println!("third");
+        });
+        assert_printed!(outer, "first\nthird\n");
+        assert_printed!(inner.unwrap(), "second\n");
+
+        let result = std::panic::catch_unwind(|| capture(|| panic!("captured panic")));
+        assert!(result.is_err());
+        let recorder = capture(|| println!("fourth"));
+        assert_printed!(recorder, "fourth\n");
+    }
+
+    #[test]
+    fn test_capture_threads() {
+        use custom_print::assert_printed;
+        use custom_print::testing::capture;
+        use std::thread;
+        use std::vec::Vec;
+
+        let threads: Vec<_> = (0..8)
+            .map(|index| {
+                thread::spawn(move || {
+                    let recorder = capture(|| {
+                        for _ in 0..100 {
+                            println!("{}", index);
+                        }
+                    });
+                    assert_eq!(recorder.write_count(), 200);
+                    assert_printed!(recorder, "{}", std::format!("{}\n", index).repeat(100));
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+    }
+
+    #[test]
+    fn test_recorder() {
+        use crate::recorder_with_flush;
+        use custom_print::testing::{Event, Recorder};
+        use custom_print::{assert_chunks, assert_printed, GLOBAL_SINK};
+        use std::string::ToString;
+
+        let value = 1;
+        let recorder = Recorder::new();
+        GLOBAL_SINK.with_sink(recorder.clone(), || eprintln!("first {}", value));
+        assert_printed!(recorder, "first 1\n");
+        assert_chunks!(recorder, ["first ", "1", "\n"]);
+        recorder.clear();
+        assert!(recorder.events().is_empty());
+
+        let recorder = recorder_with_flush();
+        assert_eq!(
+            recorder.take_events(),
+            [Event::Write("first".to_string()), Event::Flush]
+        );
+        assert_eq!(recorder.write_count(), 0);
+    }
+}
@@ -61,7 +61,6 @@ pub mod submodule {
             ]
         );
 
-        let second_var = second_var;
         let (output, line) = (try_dbg!(("first", second_var)), line!());
         assert_eq!(output, Ok(("first", "second")));
         assert_eq!(
@@ -20,6 +20,10 @@ fn test_use_re_exported_macro() {
     assert!(result.is_ok());
     let (result, line5) = (re_export_test::try_edbg!("try_edbg"), line!());
     assert!(result.is_ok());
+    re_export_test::flush!();
+    re_export_test::eflush!();
+    assert!(re_export_test::try_flush!().is_ok());
+    assert!(re_export_test::try_eflush!().is_ok());
 
     let chunks = re_export_test::take_chunks();
     let chunks: Vec<_> = chunks
@@ -45,7 +49,11 @@ fn test_use_re_exported_macro() {
             Some("try_println\n"),
             Some("try_eprintln\n"),
             Some(&format!("[{file}:{line4}] \"try_dbg\" = \"try_dbg\"\n")),
-            Some(&format!("[{file}:{line5}] \"try_edbg\" = \"try_edbg\"\n"))
+            Some(&format!("[{file}:{line5}] \"try_edbg\" = \"try_edbg\"\n")),
+            None,
+            None,
+            None,
+            None,
         ]
     );
 }
@@ -0,0 +1,33 @@
+#![cfg(not(feature = "alloc"))]
+#![no_std]
+
+extern crate std;
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn write_slice(_: &[u16]) -> Result<(), &'static str> {
+    if CALLS.fetch_add(1, Ordering::Relaxed) == 0 {
+        Err("first chunk failed")
+    } else {
+        Ok(())
+    }
+}
+
+custom_print::define_macro!(try_print, fmt, crate::write_slice);
+
+pub mod submodule {
+    #[test]
+    fn test_utf16_chunks_first_error() {
+        use crate::CALLS;
+        use core::sync::atomic::Ordering;
+
+        let message = "message ".repeat(100);
+        assert_eq!(
+            try_print!("{}", message),
+            Err(custom_print::FmtWriteError::Write("first chunk failed"))
+        );
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+}
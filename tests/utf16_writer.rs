@@ -0,0 +1,122 @@
+#![cfg(feature = "std")]
+#![no_std]
+
+extern crate std;
+
+use std::string::String;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use once_cell::sync::Lazy;
+
+static CHUNKS: Lazy<Mutex<Vec<Vec<u16>>>> = Lazy::new(Mutex::default);
+
+fn take_chunks() -> Vec<String> {
+    use core::mem::take;
+    take(&mut *CHUNKS.lock().unwrap())
+        .into_iter()
+        .map(|chunk| String::from_utf16(&chunk).unwrap())
+        .collect()
+}
+
+#[inline(never)]
+fn black_box<D>(input: D) -> D {
+    unsafe {
+        let output = std::ptr::read_volatile(&input);
+        std::mem::forget(input);
+        output
+    }
+}
+
+fn write_slice(value: &[u16]) {
+    CHUNKS.lock().unwrap().push(value.to_vec());
+}
+
+fn write_slice_len(value: &[u16]) -> usize {
+    write_slice(value);
+    value.len()
+}
+
+unsafe fn write_ptr_len(ptr: *const u16, len: usize) {
+    CHUNKS
+        .lock()
+        .unwrap()
+        .push(core::slice::from_raw_parts(ptr, len).to_vec());
+}
+
+unsafe fn write_nul_terminated(ptr: *const u16) {
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    write_slice(core::slice::from_raw_parts(ptr, len));
+}
+
+custom_print::define_macros!({ print, println }, fmt, crate::write_slice);
+custom_print::define_macros!(
+    { cprint, cprintln },
+    concat,
+    unsafe fn (crate::write_ptr_len)(*const u16, usize)
+);
+custom_print::define_macros!(
+    { eprint, eprintln },
+    fmt,
+    unsafe fn (crate::write_nul_terminated)(*const u16)
+);
+custom_print::define_macros!(
+    { try_print, try_println },
+    fmt,
+    unsafe fn (crate::write_nul_terminated)(*const u16)
+);
+
+pub mod submodule {
+    #[test]
+    fn test_utf16_writer() {
+        use crate::{black_box, take_chunks};
+        use std::string::String;
+
+        print!("first");
+        assert_eq!(take_chunks(), ["first"]);
+        println!("first {}", black_box("\u{1F600}"));
+        assert_eq!(take_chunks(), ["first ", "\u{1F600}", "\n"]);
+        print!("");
+        assert_eq!(take_chunks(), [""]);
+
+        cprint!("second");
+        assert_eq!(take_chunks(), ["second"]);
+        cprintln!("second {}", black_box("\u{430}\u{1F600}"));
+        assert_eq!(take_chunks(), ["second \u{430}\u{1F600}\n"]);
+
+        let long = String::from("\u{1F600}").repeat(100) + &String::from("a").repeat(300);
+        cprint!("{}", black_box(&long));
+        assert_eq!(take_chunks(), [long.as_str()]);
+
+        eprint!("third");
+        assert_eq!(take_chunks(), ["third"]);
+        eprintln!("third {}", black_box("\u{1F600}"));
+        assert_eq!(take_chunks(), ["third ", "\u{1F600}", "\n"]);
+        eprint!("{}", black_box(&long));
+        assert_eq!(take_chunks(), [long.as_str()]);
+
+        assert_eq!(try_print!("fourth"), Ok(()));
+        assert_eq!(try_println!("fourth {}", black_box("fifth")), Ok(()));
+        assert_eq!(take_chunks(), ["fourth", "fourth ", "fifth", "\n"]);
+        match try_print!("sixth\0{}", black_box("seventh")) {
+            Err(custom_print::FmtWriteError::Write(err)) => assert_eq!(err.nul_position(), 5),
+            result => unreachable!("{:?}", result),
+        }
+        assert!(take_chunks().is_empty());
+
+        let mut writer =
+            custom_print::IntoWriteFn::into_write_fn(crate::write_slice_len as fn(&[u16]) -> usize);
+        let len = custom_print::WriteStr::write_str(&mut writer, black_box("\u{430}b"));
+        assert_eq!(len, 3);
+        assert_eq!(take_chunks(), ["\u{430}b"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "nul character found in provided data at position: 5")]
+    fn test_utf16_writer_nul_panic() {
+        eprint!("first\0second");
+    }
+}